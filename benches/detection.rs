@@ -0,0 +1,90 @@
+//! Compares single-pass `RegexSet` detection with the previous one-regex-per-type scan
+//! on a large synthetic CI log.
+//!
+//! Run with `cargo bench --bench detection`.
+
+use error_toon::{detect_error_type, split_into_error_blocks, ErrorType};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const TARGET_LOG_BYTES: usize = 20 * 1024 * 1024;
+
+/// CI log chunk: mostly noise with a few errors and stack frames mixed in.
+const CHUNK: &str = "\
+[12:00:01] INFO  Compiling module src/components/Button.tsx
+[12:00:01] INFO  webpack 5.88.2 compiled successfully in 1342 ms
+[12:00:02] DEBUG cache hit for chunk-THCTKSJA.js (124 kB)
+[12:00:02] INFO  GET /api/health 200 3ms
+TypeError: Cannot read properties of undefined (reading 'map')
+    at ProductList (http://localhost:3000/src/ProductList.tsx:42:17)
+    at renderWithHooks (http://localhost:3000/node_modules/react-dom/cjs/react-dom.development.js:14985:18)
+[12:00:03] INFO  Test suite started: checkout.spec.ts
+[12:00:03] INFO  ✓ renders the cart (43 ms)
+GET https://api.example.com/users 404 (Not Found)
+[12:00:04] WARN  Slow query detected (231 ms)
+[12:00:04] INFO  Lighthouse score: 92
+Error: connect ECONNREFUSED 127.0.0.1:5432
+    at TCPConnectWrap.afterConnect (node:net:1555:16)
+[12:00:05] INFO  Build artifacts uploaded
+";
+
+fn main() {
+    let log = CHUNK.repeat(TARGET_LOG_BYTES / CHUNK.len() + 1);
+    println!("input: {:.1} MB, {} lines\n", log.len() as f64 / 1_048_576.0, log.lines().count());
+
+    // Block splitting runs detection on every line, so compare detection itself
+    // line by line instead of keeping a copy of the splitter's other rules.
+    let lines: Vec<&str> = log.lines().collect();
+    for line in &lines {
+        assert_eq!(sequential_detect(line), detect_error_type(line), "RegexSet detection must match the sequential scan: {}", line);
+    }
+    let blocks = split_into_error_blocks(&log);
+    for block in &blocks {
+        assert_eq!(sequential_detect(block), detect_error_type(block), "RegexSet detection must match the sequential scan");
+    }
+
+    let lines_seq = time(|| lines.iter().filter_map(|l| sequential_detect(l)).count());
+    let lines_set = time(|| lines.iter().filter_map(|l| detect_error_type(l)).count());
+    report("detect_error_type (per line)", lines_seq, lines_set);
+
+    let detect_seq = time(|| blocks.iter().filter_map(|b| sequential_detect(b)).count());
+    let detect_set = time(|| blocks.iter().filter_map(|b| detect_error_type(b)).count());
+    report("detect_error_type (per block)", detect_seq, detect_set);
+
+    report("total", lines_seq + detect_seq, lines_set + detect_set);
+}
+
+/// Previous detection: try each pattern in priority order, then context
+/// patterns ahead of the catch-all.
+fn sequential_detect(input: &str) -> Option<ErrorType> {
+    let matches = |t: &&ErrorType| t.pattern().is_match(input);
+    ErrorType::ALL
+        .iter()
+        .filter(|&&t| t != ErrorType::RuntimeError)
+        .find(matches)
+        .or_else(|| ErrorType::ALL.iter().find(|t| t.context_pattern().is_some_and(|p| p.is_match(input))))
+        .or_else(|| Some(&ErrorType::RuntimeError).filter(matches))
+        .copied()
+}
+
+/// Best of three runs.
+fn time(mut f: impl FnMut() -> usize) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one run")
+}
+
+fn report(name: &str, sequential: Duration, set: Duration) {
+    println!(
+        "{:<32} sequential {:>9.1?}   regex set {:>9.1?}   {:.1}x",
+        name,
+        sequential,
+        set,
+        sequential.as_secs_f64() / set.as_secs_f64()
+    );
+}
@@ -0,0 +1,667 @@
+//! Error type detection and classification.
+//!
+//! All detection patterns are compiled into a single [`RegexSet`] so one scan
+//! of the input reports every matching type; priority is then just the order
+//! of [`ErrorType::ALL`]. Types found only by their
+//! [context pattern](ErrorType::context_pattern), from a second small set, rank
+//! just above the catch-all. [`classify`] turns that scan into a primary type,
+//! secondary tags, and a confidence score.
+
+use crate::error_type::ErrorType;
+use crate::patterns::PATTERNS;
+use once_cell::sync::Lazy;
+use regex::RegexSet;
+
+/// Detection patterns of [`ErrorType::ALL`], indexed the same way.
+static DETECTION_SET: Lazy<RegexSet> = Lazy::new(|| {
+    RegexSet::new(ErrorType::ALL.iter().map(|t| t.pattern().as_str())).expect("Invalid regex pattern")
+});
+
+/// Types with a [context pattern](ErrorType::context_pattern), in priority order.
+static CONTEXT_TYPES: Lazy<Vec<ErrorType>> =
+    Lazy::new(|| ErrorType::ALL.iter().copied().filter(|t| t.context_pattern().is_some()).collect());
+
+/// Context patterns of [`CONTEXT_TYPES`], indexed the same way.
+static CONTEXT_SET: Lazy<RegexSet> = Lazy::new(|| {
+    RegexSet::new(CONTEXT_TYPES.iter().filter_map(|t| t.context_pattern()).map(|p| p.as_str()))
+        .expect("Invalid regex pattern")
+});
+
+/// Detect the error type of `input`, returning the first match in
+/// [`ErrorType::ALL`] priority order.
+pub fn detect_error_type(input: &str) -> Option<ErrorType> {
+    detect_candidates(input).first().copied()
+}
+
+/// Every error type whose pattern matches `input`, in priority order.
+///
+/// A type found only by its context pattern ranks below every type whose own
+/// pattern matched, above the `RUNTIME_ERROR` catch-all.
+pub fn detect_candidates(input: &str) -> Vec<ErrorType> {
+    let mut candidates: Vec<ErrorType> = DETECTION_SET
+        .matches(input)
+        .iter()
+        .map(|i| ErrorType::ALL[i])
+        .filter(|&t| !only_lookalikes(t, input))
+        .collect();
+    for context in CONTEXT_SET.matches(input).iter().map(|i| CONTEXT_TYPES[i]) {
+        if !candidates.contains(&context) {
+            let at = candidates.iter().position(|&t| t == ErrorType::RuntimeError).unwrap_or(candidates.len());
+            candidates.insert(at, context);
+        }
+    }
+    candidates
+}
+
+/// Whether every match of `error_type`'s pattern is a lookalike the pattern
+/// can't rule out itself: Jest's captured console output (`● Console`) uses the
+/// bullet of a failing test.
+fn only_lookalikes(error_type: ErrorType, input: &str) -> bool {
+    match error_type {
+        ErrorType::TestFailure => PATTERNS
+            .test_failure
+            .find_iter(input)
+            .all(|m| m.as_str().trim_start().starts_with('●') && input[m.end()..].starts_with("Console")),
+        _ => false,
+    }
+}
+
+/// `(primary, tag)` pairs where the tag's pattern matches everything the
+/// primary's does, so the tag would only repeat the primary.
+const IMPLIED_TAGS: [(ErrorType, ErrorType); 1] = [(ErrorType::VueError, ErrorType::VueWarn)];
+
+/// Confidence reported when only the [`ErrorType::RuntimeError`] catch-all matched.
+const CATCH_ALL_CONFIDENCE: f64 = 0.25;
+
+/// Outcome of scoring one error block against every detector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// Highest-priority matching type (same as [`detect_error_type`]).
+    pub primary: ErrorType,
+    /// Other matching types in priority order, excluding the catch-all.
+    pub tags: Vec<ErrorType>,
+    /// Share of the evidence supporting `primary`, from 0 to 1, rounded to two decimals.
+    pub confidence: f64,
+}
+
+/// Score `input` against all detectors and classify it, or `None` if nothing matched.
+///
+/// Each matching type is weighted by where it matched: on the header line (the
+/// first non-blank line, which carries the error message) it counts 1.0,
+/// further down the block 0.5. The confidence is the primary type's share of
+/// the total weight, so a primary that only matched a wrapped cause while a tag
+/// matched the header scores low.
+pub fn classify(input: &str) -> Option<Classification> {
+    let candidates = detect_candidates(input);
+    let primary = *candidates.first()?;
+    Some(score(input, primary, &candidates))
+}
+
+/// Classify `input` with `primary` already decided by the caller.
+pub(crate) fn classify_as(input: &str, primary: ErrorType) -> Classification {
+    score(input, primary, &detect_candidates(input))
+}
+
+fn score(input: &str, primary: ErrorType, candidates: &[ErrorType]) -> Classification {
+    let header = input.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+    let on_header = DETECTION_SET.matches(header);
+    let weight = |error_type: ErrorType| {
+        let matched_header = ErrorType::ALL
+            .iter()
+            .position(|&t| t == error_type)
+            .is_some_and(|i| on_header.matched(i));
+        if matched_header { 1.0 } else { 0.5 }
+    };
+
+    let tags: Vec<ErrorType> = candidates
+        .iter()
+        .copied()
+        .filter(|&t| t != primary && t != ErrorType::RuntimeError && !IMPLIED_TAGS.contains(&(primary, t)))
+        .collect();
+
+    let confidence = if primary == ErrorType::RuntimeError {
+        CATCH_ALL_CONFIDENCE
+    } else {
+        let primary_weight = weight(primary);
+        primary_weight / (primary_weight + tags.iter().map(|&t| weight(t)).sum::<f64>())
+    };
+
+    Classification { primary, tags, confidence: (confidence * 100.0).round() / 100.0 }
+}
+
+/// Whether any detection pattern matches `input`. Context patterns don't count:
+/// a framework frame is not an error by itself.
+pub(crate) fn matches_any(input: &str) -> bool {
+    DETECTION_SET.is_match(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Error Type Detection Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_dom_nesting_error() {
+        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::DomNesting)));
+    }
+
+    #[test]
+    fn detects_dom_nesting_case_insensitive() {
+        let input = "Warning: VALIDATEDOMNESTING(...): error";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::DomNesting)));
+    }
+
+    #[test]
+    fn detects_hydration_error() {
+        let input = "Uncaught Error: Hydration failed because the initial UI does not match.";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Hydration)));
+    }
+
+    #[test]
+    fn detects_type_error() {
+        let input = "TypeError: Cannot read properties of undefined (reading 'map')";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::TypeError)));
+    }
+
+    #[test]
+    fn detects_reference_error() {
+        let input = "ReferenceError: myVariable is not defined";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::RefError)));
+    }
+
+    #[test]
+    fn detects_syntax_error() {
+        let input = "SyntaxError: Unexpected token '<'";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::SyntaxError)));
+    }
+
+    #[test]
+    fn detects_system_error_enoent() {
+        let input = "Error: ENOENT: no such file or directory, open '/path/to/file'";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::SystemError)));
+    }
+
+    #[test]
+    fn detects_system_error_econnrefused() {
+        let input = "Error: connect ECONNREFUSED 127.0.0.1:3000";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::SystemError)));
+    }
+
+    #[test]
+    fn detects_storybook_error() {
+        let input = "SB_PREVIEW_API_UNDEFINED: The preview API is not available.";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Storybook)));
+    }
+
+    #[test]
+    fn detects_runtime_error_with_stack_trace() {
+        let input = "Error: Something went wrong\n    at MyComponent (App.tsx:25:10)";
+        let result = detect_error_type(input);
+        // Should match RuntimeError due to stack trace pattern
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn detect_candidates_reports_all_matches_in_priority_order() {
+        let input = "Unhandled Promise Rejection:\nTypeError: Failed to fetch";
+        let candidates = detect_candidates(input);
+        assert_eq!(candidates, vec![ErrorType::NetworkError, ErrorType::UnhandledRejection, ErrorType::TypeError]);
+        assert_eq!(detect_error_type(input), Some(candidates[0]));
+    }
+
+    #[test]
+    fn detection_set_agrees_with_sequential_patterns() {
+        let inputs = [
+            "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.",
+            "GET https://api.example.com/users 404 (Not Found)",
+            "ServiceWorker registration failed: A bad HTTP response code (404) was received",
+            "Error: Something went wrong\n    at MyComponent (App.tsx:25:10)",
+            "Mixed Content: The page at 'https://example.com' requested an insecure resource",
+            "just some text",
+        ];
+        for input in inputs {
+            let sequential = ErrorType::ALL.iter().find(|t| t.pattern().is_match(input)).copied();
+            assert_eq!(detect_error_type(input), sequential, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn detection_set_handles_non_ascii_input() {
+        let input = "✓ checkout → GET https://api.example.com/cart 500 (Internal Server Error)";
+        assert_eq!(detect_error_type(input), Some(ErrorType::HttpError));
+    }
+
+    #[test]
+    fn classify_single_match_is_fully_confident() {
+        let classification = classify("TypeError: foo is not a function").unwrap();
+        assert_eq!(classification.primary, ErrorType::TypeError);
+        assert!(classification.tags.is_empty());
+        assert_eq!(classification.confidence, 1.0);
+    }
+
+    #[test]
+    fn classify_tags_wrapped_error() {
+        let input = "Error: locator.click: Target closed\nTypeError: Cannot read properties of null (reading 'click')";
+        let classification = classify(input).unwrap();
+        assert_eq!(classification.primary, ErrorType::Playwright);
+        assert_eq!(classification.tags, vec![ErrorType::TypeError]);
+        assert_eq!(classification.confidence, 0.67);
+    }
+
+    #[test]
+    fn classify_scores_body_only_primary_low() {
+        let classification = classify("Unhandled Promise Rejection:\nTypeError: Failed to fetch").unwrap();
+        assert_eq!(classification.primary, ErrorType::NetworkError);
+        assert_eq!(classification.tags, vec![ErrorType::UnhandledRejection, ErrorType::TypeError]);
+        assert_eq!(classification.confidence, 0.25);
+    }
+
+    #[test]
+    fn classify_catch_all_has_low_confidence_and_no_tags() {
+        let classification = classify("Error: Something went wrong\n    at MyComponent (App.tsx:25:10)").unwrap();
+        assert_eq!(classification.primary, ErrorType::RuntimeError);
+        assert!(classification.tags.is_empty());
+        assert_eq!(classification.confidence, CATCH_ALL_CONFIDENCE);
+    }
+
+    #[test]
+    fn classify_never_tags_catch_all() {
+        let input = "TypeError: foo\n    at App (src/App.tsx:12:5)";
+        assert!(detect_candidates(input).contains(&ErrorType::RuntimeError));
+        assert!(classify(input).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn classify_returns_none_for_unrecognized_input() {
+        assert_eq!(classify("just some text"), None);
+    }
+
+    #[test]
+    fn returns_none_for_unrecognized_input() {
+        let input = "This is just some random text without any error patterns.";
+        let result = detect_error_type(input);
+        assert!(result.is_none());
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // New Error Type Detection Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_react_minified_error() {
+        let input = "Minified React error #185; visit https://reactjs.org/docs/error-decoder.html";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::ReactMinified));
+    }
+
+    #[test]
+    fn detects_invalid_hook_error() {
+        let input = "Invalid hook call. Hooks can only be called inside of the body of a function component.";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::InvalidHook));
+    }
+
+    #[test]
+    fn detects_react_key_error() {
+        let input = "Encountered two children with the same key, `?path=/docs/foundation-related--docs`. Keys should be unique.";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::ReactKey));
+    }
+
+    #[test]
+    fn detects_react_key_error_verbose() {
+        let input = r#"DocPage.tsx:428 Encountered two children with the same key, `?path=/docs/foundation-related--docs`. Keys should be unique so that components maintain their identity across updates. Non-unique keys may cause children to be duplicated and/or omitted — the behavior is unsupported and could change in a future version.
+(anonymous) @ chunk-ZJ2MJDOW.js?v=9079ec11:4925
+runWithFiberInDEV @ chunk-ZJ2MJDOW.js?v=9079ec11:997"#;
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::ReactKey));
+    }
+
+    #[test]
+    fn detects_cors_error() {
+        let input = "Access to XMLHttpRequest at 'https://api.example.com' from origin 'http://localhost:3000' has been blocked by CORS policy";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::CorsError));
+    }
+
+    #[test]
+    fn detects_network_error() {
+        let input = "TypeError: Failed to fetch";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::NetworkError));
+    }
+
+    #[test]
+    fn detects_network_error_chrome() {
+        let input = "net::ERR_CONNECTION_REFUSED";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::NetworkError));
+    }
+
+    #[test]
+    fn detects_http_error() {
+        let input = "GET https://api.example.com/users 404 (Not Found)";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::HttpError));
+    }
+
+    #[test]
+    fn detects_websocket_error() {
+        let input = "WebSocket connection to 'wss://example.com/socket' failed";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::WebSocketError));
+    }
+
+    #[test]
+    fn detects_csp_error() {
+        let input = "Refused to execute inline script because it violates the following Content-Security-Policy directive";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::CspError));
+    }
+
+    #[test]
+    fn detects_mixed_content_error() {
+        let input = "Mixed Content: The page at 'https://example.com' was loaded over HTTPS, but requested an insecure resource";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::MixedContent));
+    }
+
+    #[test]
+    fn detects_nextjs_error() {
+        let input = "Error: getServerSideProps should return an object";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::NextJs));
+    }
+
+    #[test]
+    fn detects_module_not_found_error() {
+        let input = "Module not found: Can't resolve './components/Button' in '/app/src'";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::ModuleNotFound));
+    }
+
+    #[test]
+    fn detects_range_error() {
+        let input = "RangeError: Maximum call stack size exceeded";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::RangeError));
+    }
+
+    #[test]
+    fn detects_unhandled_rejection() {
+        let input = "Unhandled Promise Rejection: TypeError: Cannot read property 'x' of undefined";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::UnhandledRejection));
+    }
+
+    #[test]
+    fn detects_media_error() {
+        let input = "DOMException: play() failed because the user didn't interact with the document first";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::MediaError));
+    }
+
+    #[test]
+    fn detects_indexeddb_error() {
+        let input = "QuotaExceededError: The IndexedDB quota has been exceeded";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::IndexedDbError));
+    }
+
+    #[test]
+    fn detects_service_worker_error() {
+        let input = "ServiceWorker registration failed: A bad HTTP response code (404) was received";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::ServiceWorker));
+    }
+
+    #[test]
+    fn detects_deprecation_warning() {
+        let input = "Warning: componentWillMount has been renamed, and is not recommended for use. This method will be deprecated in a future version.";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::Deprecation));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // JavaScript Errors with Browser Console Prefix
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_syntax_error_with_file_prefix() {
+        // Browser console format: file.js:line ErrorType: message
+        let input = "vite-app.js:29 SyntaxError: The requested module '/src/stories/brand/BrandComponents.tsx' does not provide an export named 'NEUTRAL' (at 06-Iconography.mdx:7:49)";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::SyntaxError));
+    }
+
+    #[test]
+    fn detects_type_error_with_file_prefix() {
+        let input = "bundle.js:42 TypeError: Cannot read properties of undefined (reading 'map')";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::TypeError));
+    }
+
+    #[test]
+    fn detects_ref_error_with_file_prefix() {
+        let input = "app.js:100 ReferenceError: myVariable is not defined";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::RefError));
+    }
+
+    #[test]
+    fn detects_range_error_with_file_prefix() {
+        let input = "script.js:55 RangeError: Maximum call stack size exceeded";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::RangeError));
+    }
+
+    #[test]
+    fn detects_syntax_error_without_prefix() {
+        // Should still work without file prefix
+        let input = "SyntaxError: Unexpected token '<'";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::SyntaxError));
+    }
+
+    #[test]
+    fn detects_uncaught_type_error() {
+        // Uncaught variant should still work
+        let input = "Uncaught TypeError: foo is not a function";
+        let result = detect_error_type(input);
+        assert_eq!(result, Some(ErrorType::TypeError));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Playwright Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_playwright_timeout_error() {
+        let input = "TimeoutError: locator.click: Timeout 30000ms exceeded.\n    waiting for locator('.nonexistent')";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    #[test]
+    fn detects_playwright_assertion_error() {
+        let input = "Error: Timed out 5000ms waiting for expect(locator).toBeVisible()\n    Locator: locator('.button')";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    #[test]
+    fn detects_playwright_strict_mode_error() {
+        let input = "Error: strict mode violation: locator('.button') resolved to 3 elements";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    #[test]
+    fn detects_playwright_page_goto_error() {
+        let input = "Error: page.goto: Navigation failed because page was closed";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    #[test]
+    fn detects_playwright_locator_fill_error() {
+        let input = "Error: locator.fill: Target closed\n    at LoginPage.fillUsername (tests/login.spec.ts:15:5)";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    #[test]
+    fn detects_playwright_test_import() {
+        let input = "Error: Cannot find module '@playwright/test'\n    from 'tests/example.spec.ts'";
+        let result = detect_error_type(input);
+        assert!(matches!(result, Some(ErrorType::Playwright)));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Vue Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_vue_warning() {
+        let input = "[Vue warn]: Property \"total\" was accessed during render but is not defined on instance.\n  at <Cart>";
+        let classification = classify(input).unwrap();
+        assert_eq!(classification.primary, ErrorType::VueWarn);
+        assert_eq!(classification.confidence, 1.0);
+    }
+
+    #[test]
+    fn detects_vue_template_compile_warning() {
+        let input = "[Vue warn]: Error compiling template:\n\n<div>{{ total }</div>\n\n- invalid expression: Unexpected token '}' in\n\n(found in <Root>)";
+        let classification = classify(input).unwrap();
+        assert_eq!(classification.primary, ErrorType::VueWarn);
+        assert_eq!(classification.confidence, 1.0);
+    }
+
+    #[test]
+    fn detects_vue_error_without_tagging_warning() {
+        for input in [
+            "[Vue warn]: Unhandled error during execution of render function\n  at <ProductList>",
+            "[Vue warn]: Error in mounted hook: \"TypeError: x is undefined\"",
+        ] {
+            let classification = classify(input).unwrap();
+            assert_eq!(classification.primary, ErrorType::VueError, "{}", input);
+            assert!(classification.tags.is_empty(), "{:?}", classification.tags);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Angular Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_angular_errors() {
+        for input in [
+            "ERROR Error: NG0100: ExpressionChangedAfterItHasBeenCheckedError: Expression has changed after it was checked.",
+            "ERROR Error: NG0200: Circular dependency in DI detected for UserService",
+            "ERROR NullInjectorError: R3InjectorError(AppModule)[UserService -> HttpClient]:",
+        ] {
+            assert_eq!(detect_error_type(input), Some(ErrorType::Angular), "{}", input);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Svelte Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_svelte_errors() {
+        for input in [
+            "[plugin:vite-plugin-svelte] /src/routes/+page.svelte:12:4 Expected '}'",
+            "Uncaught Svelte error: state_unsafe_mutation",
+            "SvelteKitError: Not found: /blog/missing",
+            "Error: Not found: /blog/missing\n    at resolve (/app/node_modules/@sveltejs/kit/src/runtime/server/respond.js:330:13)",
+            "Error: Failed to load post\n    at load (/src/routes/blog/+page.server.ts:8:11)",
+        ] {
+            assert_eq!(detect_error_type(input), Some(ErrorType::Svelte), "{}", input);
+        }
+    }
+
+    #[test]
+    fn svelte_frames_tag_javascript_errors() {
+        for input in [
+            "TypeError: Cannot read properties of undefined (reading 'title')\n    at load (/app/src/routes/blog/+page.ts:5:20)",
+            "TypeError: x is undefined\n    at init (/node_modules/svelte/internal/index.mjs:2002:37)",
+        ] {
+            let classification = classify(input).unwrap();
+            assert_eq!(classification.primary, ErrorType::TypeError, "{}", input);
+            assert_eq!(classification.tags, vec![ErrorType::Svelte]);
+        }
+    }
+
+    #[test]
+    fn plain_404_is_not_svelte() {
+        let input = "Error: Not found: /api/users\n    at getUser (/srv/app/server/routes.js:14:11)";
+        assert!(!detect_candidates(input).contains(&ErrorType::Svelte));
+    }
+
+    #[test]
+    fn svelte_hydration_mismatch_is_svelte() {
+        let input = "[svelte] hydration_mismatch\nHydration failed because the initial UI does not match what was rendered on the server";
+        let classification = classify(input).unwrap();
+        assert_eq!(classification.primary, ErrorType::Svelte);
+        assert_eq!(classification.tags, vec![ErrorType::Hydration]);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Test Failure Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_jest_and_vitest_failures() {
+        for input in [
+            "  ● ProductList › renders items\n\n    expect(received).toBe(expected)",
+            " FAIL  src/utils/math.test.ts > math > adds numbers\nAssertionError: expected 2 to be 3",
+            "expect(received).toEqual(expected) // deep equality",
+        ] {
+            assert_eq!(detect_error_type(input), Some(ErrorType::TestFailure), "{}", input);
+        }
+        assert_eq!(detect_error_type("  ● Console\n\n    console.log\n      hello"), None);
+    }
+
+    #[test]
+    fn node_assert_failures_outside_tests_are_not_test_failures() {
+        let input = "AssertionError [ERR_ASSERTION]: Expected values to be strictly equal:\n\n1 !== 2\n\n    at charge (/srv/app/src/billing.js:12:8)";
+        assert!(!detect_candidates(input).contains(&ErrorType::TestFailure));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Cypress Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_cypress_failures() {
+        for input in [
+            "CypressError: Timed out retrying after 4000ms: `cy.click()` failed because this element is detached from the DOM.",
+            "AssertionError: Timed out retrying after 4000ms: Expected to find element: `[data-cy=submit]`, but never found it.",
+            "The following error originated from your application code, not from Cypress.\n\n  > Cannot read properties of undefined (reading 'map')",
+        ] {
+            assert_eq!(detect_error_type(input), Some(ErrorType::Cypress), "{}", input);
+        }
+    }
+
+    #[test]
+    fn cypress_locator_failure_is_not_playwright() {
+        let input = "AssertionError: Timed out retrying after 4000ms: expected '<button>' to be 'visible'\n    at Context.eval (webpack://app/./cypress/e2e/checkout.cy.ts:22:40)";
+        let classification = classify(input).unwrap();
+        assert_eq!(classification.primary, ErrorType::Cypress);
+        assert!(!classification.tags.contains(&ErrorType::Playwright));
+    }
+}
@@ -0,0 +1,296 @@
+//! Error type catalogue: names, colors, icons, and detection patterns.
+
+use crate::patterns::PATTERNS;
+use colored::Color;
+use regex::Regex;
+use serde::{Serialize, Serializer};
+
+/// Category of a detected error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorType {
+    // Framework errors
+    VueWarn,
+    VueError,
+    Angular,
+    Svelte,
+    // DOM/React errors
+    DomNesting,
+    Hydration,
+    ReactMinified,
+    InvalidHook,
+    ReactKey,
+    // JavaScript errors
+    TypeError,
+    RefError,
+    SyntaxError,
+    RangeError,
+    UriError,
+    EvalError,
+    // Network errors
+    CorsError,
+    NetworkError,
+    HttpError,
+    WebSocketError,
+    // Security errors
+    CspError,
+    SecurityError,
+    MixedContent,
+    // Build tool / Testing errors
+    Storybook,
+    NextJs,
+    ModuleNotFound,
+    Playwright,
+    Cypress,
+    TestFailure,
+    // System/Node errors
+    SystemError,
+    // Promise errors
+    UnhandledRejection,
+    // Browser API errors
+    MediaError,
+    IndexedDbError,
+    ServiceWorker,
+    // Deprecation/warnings
+    Deprecation,
+    // Catch-all
+    RuntimeError,
+}
+
+impl ErrorType {
+    /// Every error type in detection priority order.
+    // Order matters! More specific patterns first, RuntimeError (catch-all) last
+    pub const ALL: &'static [ErrorType] = &[
+        // Framework-prefixed messages (the prefix names the source outright)
+        Self::Cypress,           // Before TestFailure (Cypress assertions are chai AssertionErrors)
+        Self::TestFailure,       // A failing test's body may contain any other error
+        Self::VueError,
+        Self::VueWarn,
+        Self::Angular,
+        Self::Svelte,            // Before Hydration (Svelte reports its own hydration mismatches)
+        // DOM/React (most specific first)
+        Self::DomNesting,
+        Self::Hydration,
+        Self::ReactMinified,
+        Self::InvalidHook,
+        Self::ReactKey,
+        // Security errors (MixedContent before SecurityError - more specific)
+        Self::CorsError,
+        Self::CspError,
+        Self::MixedContent,      // Must come before SecurityError (contains "insecure")
+        Self::SecurityError,
+        // Browser APIs (ServiceWorker before HttpError - may contain status codes)
+        Self::ServiceWorker,
+        Self::MediaError,
+        Self::IndexedDbError,
+        // Network errors
+        Self::NetworkError,
+        Self::WebSocketError,
+        Self::HttpError,         // After ServiceWorker (SW errors may contain HTTP codes)
+        // Build tools / Testing
+        Self::Playwright,        // Before Storybook (more specific patterns)
+        Self::Storybook,
+        Self::NextJs,
+        Self::ModuleNotFound,
+        // Promise errors (before JS errors - may contain TypeError text)
+        Self::UnhandledRejection,
+        // JavaScript errors
+        Self::TypeError,
+        Self::RefError,
+        Self::SyntaxError,
+        Self::RangeError,
+        Self::UriError,
+        Self::EvalError,
+        // System errors
+        Self::SystemError,
+        // Warnings
+        Self::Deprecation,
+        // Catch-all (must be last)
+        Self::RuntimeError,
+    ];
+
+    /// Stable upper-case name used in all output formats (e.g. `TYPE_ERROR`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::VueWarn => "VUE_WARN",
+            Self::VueError => "VUE_ERROR",
+            Self::Angular => "ANGULAR",
+            Self::Svelte => "SVELTE",
+            Self::DomNesting => "DOM_NESTING",
+            Self::Hydration => "HYDRATION",
+            Self::ReactMinified => "REACT_MINIFIED",
+            Self::InvalidHook => "INVALID_HOOK",
+            Self::ReactKey => "REACT_KEY",
+            Self::TypeError => "TYPE_ERROR",
+            Self::RefError => "REF_ERROR",
+            Self::SyntaxError => "SYNTAX_ERROR",
+            Self::RangeError => "RANGE_ERROR",
+            Self::UriError => "URI_ERROR",
+            Self::EvalError => "EVAL_ERROR",
+            Self::CorsError => "CORS_ERROR",
+            Self::NetworkError => "NETWORK_ERROR",
+            Self::HttpError => "HTTP_ERROR",
+            Self::WebSocketError => "WEBSOCKET_ERROR",
+            Self::CspError => "CSP_ERROR",
+            Self::SecurityError => "SECURITY_ERROR",
+            Self::MixedContent => "MIXED_CONTENT",
+            Self::Storybook => "STORYBOOK",
+            Self::NextJs => "NEXTJS",
+            Self::ModuleNotFound => "MODULE_NOT_FOUND",
+            Self::Playwright => "PLAYWRIGHT",
+            Self::Cypress => "CYPRESS",
+            Self::TestFailure => "TEST_FAILURE",
+            Self::SystemError => "SYSTEM_ERROR",
+            Self::UnhandledRejection => "UNHANDLED_REJECTION",
+            Self::MediaError => "MEDIA_ERROR",
+            Self::IndexedDbError => "INDEXEDDB_ERROR",
+            Self::ServiceWorker => "SERVICE_WORKER",
+            Self::Deprecation => "DEPRECATION",
+            Self::RuntimeError => "RUNTIME_ERROR",
+        }
+    }
+
+    /// Look up an error type by its [`name`](Self::name), e.g. `"TYPE_ERROR"`.
+    pub fn from_name(name: &str) -> Option<ErrorType> {
+        Self::ALL.iter().find(|t| t.name() == name).copied()
+    }
+
+    /// Terminal color used by the colored formatter.
+    pub fn color(&self) -> Color {
+        match self {
+            // Warnings (yellow)
+            Self::DomNesting | Self::Deprecation | Self::ReactKey | Self::VueWarn => Color::Yellow,
+            // React/Hydration (magenta)
+            Self::Hydration | Self::ReactMinified | Self::InvalidHook => Color::Magenta,
+            // Build tools / Testing (cyan)
+            Self::Storybook | Self::NextJs | Self::ModuleNotFound | Self::Playwright | Self::Cypress | Self::TestFailure => Color::Cyan,
+            // Network (blue)
+            Self::NetworkError | Self::HttpError | Self::WebSocketError => Color::Blue,
+            // Security (bright red)
+            Self::CorsError | Self::CspError | Self::SecurityError | Self::MixedContent => Color::BrightRed,
+            // All other errors (red)
+            _ => Color::Red,
+        }
+    }
+
+    /// Nerd Font icon shown in the colored box header.
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::DomNesting => "󰅖",
+            Self::VueWarn | Self::VueError => "󰡄",
+            Self::Angular => "󰚲",
+            Self::Svelte => "",
+            Self::Hydration | Self::ReactMinified | Self::InvalidHook | Self::ReactKey => "󰜈",
+            Self::Storybook => "󰂺",
+            Self::NextJs => "󰔶",
+            Self::Playwright | Self::Cypress | Self::TestFailure => "󰙨",
+            Self::CorsError | Self::CspError | Self::SecurityError | Self::MixedContent => "󰒃",
+            Self::NetworkError | Self::HttpError => "󰖟",
+            Self::WebSocketError => "󱄙",
+            Self::ModuleNotFound => "󰏗",
+            Self::SystemError => "",
+            Self::UnhandledRejection => "󰜺",
+            Self::MediaError => "󰎁",
+            Self::IndexedDbError => "󰆼",
+            Self::ServiceWorker => "󰖟",
+            Self::Deprecation => "󰀦",
+            _ => "",
+        }
+    }
+
+    /// Detection pattern for this type.
+    pub fn pattern(&self) -> &Regex {
+        match self {
+            Self::VueWarn => &PATTERNS.vue_warn,
+            Self::VueError => &PATTERNS.vue_error,
+            Self::Angular => &PATTERNS.angular,
+            Self::Svelte => &PATTERNS.svelte,
+            Self::DomNesting => &PATTERNS.dom_nesting,
+            Self::Hydration => &PATTERNS.hydration,
+            Self::ReactMinified => &PATTERNS.react_minified,
+            Self::InvalidHook => &PATTERNS.invalid_hook,
+            Self::ReactKey => &PATTERNS.react_key,
+            Self::TypeError => &PATTERNS.type_error,
+            Self::RefError => &PATTERNS.ref_error,
+            Self::SyntaxError => &PATTERNS.syntax_error,
+            Self::RangeError => &PATTERNS.range_error,
+            Self::UriError => &PATTERNS.uri_error,
+            Self::EvalError => &PATTERNS.eval_error,
+            Self::CorsError => &PATTERNS.cors_error,
+            Self::NetworkError => &PATTERNS.network_error,
+            Self::HttpError => &PATTERNS.http_error,
+            Self::WebSocketError => &PATTERNS.websocket_error,
+            Self::CspError => &PATTERNS.csp_error,
+            Self::SecurityError => &PATTERNS.security_error,
+            Self::MixedContent => &PATTERNS.mixed_content,
+            Self::Storybook => &PATTERNS.storybook,
+            Self::NextJs => &PATTERNS.nextjs,
+            Self::ModuleNotFound => &PATTERNS.module_not_found,
+            Self::Playwright => &PATTERNS.playwright,
+            Self::Cypress => &PATTERNS.cypress,
+            Self::TestFailure => &PATTERNS.test_failure,
+            Self::SystemError => &PATTERNS.system_error,
+            Self::UnhandledRejection => &PATTERNS.unhandled_rejection,
+            Self::MediaError => &PATTERNS.media_error,
+            Self::IndexedDbError => &PATTERNS.indexeddb_error,
+            Self::ServiceWorker => &PATTERNS.service_worker,
+            Self::Deprecation => &PATTERNS.deprecation,
+            Self::RuntimeError => &PATTERNS.stack_trace,
+        }
+    }
+
+    /// Stack context that implies this type without an error message of its
+    /// own (framework frames). It decides the type only when no other type
+    /// matched besides the `RUNTIME_ERROR` catch-all, and is a tag otherwise.
+    pub fn context_pattern(&self) -> Option<&Regex> {
+        match self {
+            Self::Svelte => Some(&PATTERNS.svelte_context),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes as the stable upper-case name (e.g. `"TYPE_ERROR"`).
+impl Serialize for ErrorType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Error Type Properties Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn error_type_names_are_uppercase() {
+        for error_type in ErrorType::ALL {
+            let name = error_type.name();
+            assert_eq!(name, name.to_uppercase(), "Error type name should be uppercase: {}", name);
+        }
+    }
+
+    #[test]
+    fn all_error_types_have_patterns() {
+        for error_type in ErrorType::ALL {
+            // Just ensure the pattern can be accessed without panic
+            let _ = error_type.pattern();
+        }
+    }
+
+    #[test]
+    fn error_types_icons_are_valid() {
+        // Some error types have icons, others use a default
+        // Just ensure the icon method doesn't panic
+        for error_type in ErrorType::ALL {
+            let _ = error_type.icon();
+        }
+
+        // Verify specific types have custom icons
+        assert!(!ErrorType::DomNesting.icon().is_empty());
+        assert!(!ErrorType::Hydration.icon().is_empty());
+        assert!(!ErrorType::Storybook.icon().is_empty());
+    }
+}
@@ -0,0 +1,741 @@
+//! Extraction of file location, issue text, and user stack frames from an error block.
+
+use crate::error_type::ErrorType;
+use crate::patterns::PATTERNS;
+use crate::util::truncate;
+use regex::Regex;
+
+/// Find the most relevant `file.ext:line` location, preferring user code over `node_modules`.
+pub fn extract_file_location(input: &str) -> Option<String> {
+    // Prefer user code over node_modules/framework files
+    let all_matches: Vec<_> = PATTERNS.file_location.find_iter(input).collect();
+
+    // First try to find a user file (not in node_modules)
+    for m in &all_matches {
+        // Get the full line containing this match
+        let line_start = input[..m.start()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[m.end()..].find('\n').map(|i| m.end() + i).unwrap_or(input.len());
+        let full_line = &input[line_start..line_end];
+
+        // Skip node_modules and bundled framework runtimes - user code is in src/, pages/, components/, etc.
+        if !full_line.contains("node_modules") && !PATTERNS.vendor_runtime.is_match(full_line) {
+            return Some(m.as_str().to_string());
+        }
+    }
+
+    // Fallback to first match
+    all_matches.first().map(|m| m.as_str().to_string())
+}
+
+/// Extract the one-line issue summary using rules specific to `error_type`.
+pub fn extract_issue(input: &str, error_type: ErrorType) -> Option<String> {
+    match error_type {
+        // Framework errors
+        ErrorType::VueWarn | ErrorType::VueError => extract_first_capture(input, &PATTERNS.vue_message),
+        ErrorType::Angular => angular_message(input),
+        ErrorType::Svelte => svelte_message(input),
+
+        // DOM/React errors
+        ErrorType::DomNesting => extract_by_pattern_or_contains(input, &PATTERNS.dom_issue, "descendant"),
+        ErrorType::Hydration => find_line_containing(input, &["hydration", "mismatch", "server", "client"]),
+        ErrorType::ReactMinified => find_line_containing(input, &["Minified React error", "react.production"]),
+        ErrorType::InvalidHook => find_line_containing(input, &["Invalid hook", "Rules of Hooks", "rendered more hooks"]),
+        ErrorType::ReactKey => find_line_starting_with(input, &["Encountered two children with the same key"]),
+
+        // JavaScript errors
+        ErrorType::TypeError => find_line_starting_with(input, &["TypeError:", "Uncaught TypeError"]),
+        ErrorType::RefError => find_line_starting_with(input, &["ReferenceError:", "Uncaught ReferenceError"]),
+        ErrorType::SyntaxError => find_line_starting_with(input, &["SyntaxError:", "Uncaught SyntaxError"]),
+        ErrorType::RangeError => find_line_starting_with(input, &["RangeError:", "Uncaught RangeError"]),
+        ErrorType::UriError => find_line_starting_with(input, &["URIError:", "Uncaught URIError"]),
+        ErrorType::EvalError => find_line_starting_with(input, &["EvalError:", "Uncaught EvalError"]),
+
+        // Network errors
+        ErrorType::CorsError => find_line_containing(input, &["CORS", "Access-Control", "cross-origin", "blocked"]),
+        ErrorType::NetworkError => find_line_containing(input, &["Failed to fetch", "NetworkError", "net::ERR_", "fetch"]),
+        ErrorType::HttpError => extract_first_match(input, &PATTERNS.http_status)
+            .and_then(|status| find_line_containing(input, &[&status])),
+        ErrorType::WebSocketError => find_line_containing(input, &["WebSocket", "ws://", "wss://"]),
+
+        // Security errors
+        ErrorType::CspError => find_line_containing(input, &["Content-Security-Policy", "CSP", "directive", "violated"]),
+        ErrorType::SecurityError => find_line_containing(input, &["SecurityError", "security", "blocked"]),
+        ErrorType::MixedContent => find_line_containing(input, &["Mixed Content", "insecure", "http://"]),
+
+        // Build tools / Testing
+        ErrorType::Storybook => extract_first_match_truncated(input, &PATTERNS.storybook_code, 100),
+        ErrorType::NextJs => extract_first_match_truncated(input, &PATTERNS.nextjs_code, 100)
+            .or_else(|| find_line_containing(input, &["NEXT_", "getServerSideProps", "getStaticProps"])),
+        ErrorType::ModuleNotFound => find_line_containing(input, &["Module not found", "Cannot find module", "Cannot resolve"]),
+        ErrorType::Cypress => cypress_message(input),
+        ErrorType::TestFailure => test_failure_message(input),
+        ErrorType::Playwright => find_line_containing(input, &["TimeoutError", "locator", "expect(", "waiting for", "strict mode", "Timeout"]),
+
+        // System errors
+        ErrorType::SystemError => extract_first_match(input, &PATTERNS.system_code),
+
+        // Promise errors
+        ErrorType::UnhandledRejection => find_line_containing(input, &["Unhandled", "rejection", "promise"]),
+
+        // Browser API errors
+        ErrorType::MediaError => find_line_containing(input, &["MediaError", "play()", "autoplay", "media"]),
+        ErrorType::IndexedDbError => find_line_containing(input, &["IndexedDB", "IDBDatabase", "QuotaExceeded"]),
+        ErrorType::ServiceWorker => find_line_containing(input, &["ServiceWorker", "service worker", "SW"]),
+
+        // Warnings
+        ErrorType::Deprecation => find_line_containing(input, &["deprecated", "deprecation", "will be removed"]),
+
+        // Catch-all
+        ErrorType::RuntimeError => input.lines().next().map(str::to_string),
+    }
+}
+
+/// The file location for `error_type`: a failing test's own `*.test.*` /
+/// `*.spec.*` (or Cypress `*.cy.*`) file first, else [`extract_file_location`],
+/// else the innermost component's file from a Vue component trace.
+pub(crate) fn extract_location(input: &str, error_type: ErrorType) -> Option<String> {
+    if error_type == ErrorType::Cypress {
+        if let Some(spec) = PATTERNS.cypress_spec.find(input) {
+            return Some(spec.as_str().to_string());
+        }
+    }
+    if matches!(error_type, ErrorType::Cypress | ErrorType::TestFailure) {
+        if let Some(test_file) = PATTERNS.test_file.find(input) {
+            return Some(test_file.as_str().to_string());
+        }
+    }
+    extract_file_location(input).or_else(|| match error_type {
+        ErrorType::VueWarn | ErrorType::VueError => PATTERNS
+            .vue_component
+            .captures_iter(input)
+            .find_map(|c| c.get(3))
+            .map(|file| file.as_str().rsplit('/').next().unwrap_or(file.as_str()).to_string()),
+        _ => None,
+    })
+}
+
+/// Labeled facts specific to `error_type`, rendered after the issue.
+pub fn extract_details(input: &str, error_type: ErrorType) -> Vec<(String, String)> {
+    match error_type {
+        ErrorType::VueWarn | ErrorType::VueError => vue_details(input),
+        ErrorType::Angular => angular_details(input),
+        ErrorType::Svelte => svelte_details(input),
+        ErrorType::Cypress => cypress_details(input),
+        ErrorType::TestFailure => test_failure_details(input),
+        _ => Vec::new(),
+    }
+}
+
+/// Vue: `components`, the component trace from innermost outwards (`ProductList < App < Root`).
+fn vue_details(input: &str) -> Vec<(String, String)> {
+    let components: Vec<&str> =
+        PATTERNS.vue_component.captures_iter(input).filter_map(|c| Some(c.get(1).or_else(|| c.get(2))?.as_str())).collect();
+    if components.is_empty() {
+        return Vec::new();
+    }
+    vec![("components".to_string(), component_chain(&components))]
+}
+
+/// Angular: the NG `code` and the `injector` path of a failed dependency
+/// lookup (`AppModule: UserService -> HttpClient`).
+fn angular_details(input: &str) -> Vec<(String, String)> {
+    let mut details = Vec::new();
+    if let Some(code) = PATTERNS.angular_code.find(input) {
+        details.push(("code".to_string(), code.as_str().to_string()));
+    }
+    if let Some(injector) = PATTERNS.angular_injector.captures(input) {
+        let mut path: Vec<&str> = injector[2].split("->").map(str::trim).collect();
+        path.dedup();
+        details.push(("injector".to_string(), format!("{}: {}", &injector[1], path.join(" -> "))));
+    }
+    details
+}
+
+/// Svelte: the Svelte error `code` and the SvelteKit `route`.
+fn svelte_details(input: &str) -> Vec<(String, String)> {
+    let mut details = Vec::new();
+    if let Some(code) = PATTERNS.svelte_code.captures(input) {
+        details.push(("code".to_string(), code[1].to_string()));
+    }
+    if let Some(route) = svelte_route(input) {
+        details.push(("route".to_string(), route));
+    }
+    details
+}
+
+/// Cypress: the failing `command` chain (from the code frame when it shows
+/// one), the element `selector`, and the retry `timeout`.
+fn cypress_details(input: &str) -> Vec<(String, String)> {
+    let mut details = Vec::new();
+    let command = code_frame_source(input)
+        .filter(|source| source.contains("cy."))
+        .or_else(|| extract_first_capture(input, &PATTERNS.cypress_command));
+    if let Some(command) = command {
+        details.push(("command".to_string(), truncate(&command, 120)));
+    }
+    if let Some(selector) = PATTERNS.cypress_selector.captures(input) {
+        let selector = selector.get(1).or_else(|| selector.get(2)).map_or("", |m| m.as_str());
+        details.push(("selector".to_string(), truncate(selector, 100)));
+    }
+    if let Some(timeout) = extract_first_capture(input, &PATTERNS.cypress_timeout) {
+        details.push(("timeout".to_string(), timeout));
+    }
+    details
+}
+
+/// Jest/Vitest: the `test` path, the failing `matcher` call from the code
+/// frame, and either `expected`/`received` values or a compact `diff`.
+fn test_failure_details(input: &str) -> Vec<(String, String)> {
+    let mut details = Vec::new();
+    if let Some(path) = input.lines().find_map(test_title) {
+        details.push(("test".to_string(), path.replace(" > ", " › ")));
+    }
+    if let Some(matcher) = test_matcher(input) {
+        details.push(("matcher".to_string(), matcher));
+    }
+    let expected = extract_first_capture(input, &PATTERNS.test_expected);
+    let received = extract_first_capture(input, &PATTERNS.test_received);
+    if let (Some(expected), Some(received)) = (expected, received) {
+        details.push(("expected".to_string(), truncate(&expected, 100)));
+        details.push(("received".to_string(), truncate(&received, 100)));
+    } else if let Some(diff) = compact_diff(input) {
+        details.push(("diff".to_string(), diff));
+    }
+    details
+}
+
+/// Longest component chain listed before the rest is elided.
+const MAX_COMPONENTS: usize = 5;
+
+/// The SvelteKit route: the path of a 404 raised by SvelteKit, else the route
+/// directory of the first `src/routes/.../+page|+layout|+server` file, without `(group)` segments.
+fn svelte_route(input: &str) -> Option<String> {
+    if let Some(not_found) = PATTERNS.svelte_not_found.captures(input).filter(|_| PATTERNS.sveltekit.is_match(input)) {
+        return Some(not_found[1].to_string());
+    }
+    let dir = PATTERNS.svelte_route.captures(input)?;
+    let segments: Vec<&str> = dir[1].split('/').filter(|s| !s.is_empty() && !s.starts_with('(')).collect();
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// `Inner < Outer < Root`, consecutive repeats (recursive components) collapsed.
+fn component_chain(components: &[&str]) -> String {
+    let mut chain: Vec<&str> = components.to_vec();
+    chain.dedup();
+    if chain.len() > MAX_COMPONENTS {
+        let root = chain[chain.len() - 1];
+        chain.truncate(MAX_COMPONENTS - 1);
+        chain.extend(["…", root]);
+    }
+    chain.join(" < ")
+}
+
+/// The Angular message without console prefix, NG code, or docs link:
+/// the `No provider for ...` line of an injector error, else the line with the NG code.
+fn angular_message(input: &str) -> Option<String> {
+    let line = find_line_containing(input, &["No provider for"])
+        .or_else(|| input.lines().find(|line| PATTERNS.angular_code.is_match(line)).map(str::to_string))
+        .or_else(|| input.lines().find(|line| !line.trim().is_empty()).map(str::to_string))?;
+
+    let mut message = line.trim();
+    for prefix in ["ERROR", "Error:", "Uncaught"] {
+        message = message.strip_prefix(prefix).map_or(message, str::trim_start);
+    }
+    let message = match PATTERNS.angular_code.find(message) {
+        Some(code) => format!("{}{}", &message[..code.start()], message[code.end()..].trim_start_matches(':').trim_start()),
+        None => message.to_string(),
+    };
+    let message = message.split(" Find more at ").next().unwrap_or(&message).trim_end_matches('.');
+    Some(truncate(message, 160))
+}
+
+/// The Svelte message: a compiler error's text after its location, a runtime
+/// error code's explanation (the line after `Svelte error: code`), a 404, or the first line.
+fn svelte_message(input: &str) -> Option<String> {
+    if let Some(compile) = PATTERNS.svelte_compile.captures(input) {
+        return Some(truncate(compile[1].trim(), 160));
+    }
+    if let Some(code) = PATTERNS.svelte_code.captures(input) {
+        let explanation = input[code.get(0).map_or(0, |m| m.end())..].lines().skip(1).map(str::trim).find(|l| !l.is_empty());
+        if let Some(explanation) = explanation {
+            return Some(truncate(explanation, 160));
+        }
+    }
+    let line = find_line_containing(input, &["Not found: /"])
+        .or_else(|| input.lines().map(str::trim).find(|line| !line.is_empty()).map(str::to_string))?;
+    Some(line.strip_prefix("Uncaught ").unwrap_or(&line).to_string())
+}
+
+/// The title path of a Jest `● describe › it` or Vitest `FAIL file > describe > it`
+/// header line. Jest's `● Console` (captured console output) uses the same bullet
+/// but isn't a test.
+pub(crate) fn test_title(line: &str) -> Option<&str> {
+    let header = PATTERNS.test_header.captures(line)?;
+    let title = header.get(1).or_else(|| header.get(2))?.as_str().trim();
+    (title != "Console").then_some(title)
+}
+
+/// The assertion message of a failing test (Jest's `expect(received)...`
+/// line, Vitest's `AssertionError: ...`, or the error the test threw), without
+/// the matcher's trailing `// deep equality` note.
+fn test_failure_message(input: &str) -> Option<String> {
+    let line = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("FAIL") && !PATTERNS.test_header.is_match(line))?;
+    let message = line.split(" // ").next().unwrap_or(line);
+    Some(truncate(message.trim(), 160))
+}
+
+/// The Cypress message: the application error quoted by an uncaught-exception
+/// wrapper, else the error line without its error name and retry timeout.
+fn cypress_message(input: &str) -> Option<String> {
+    if let Some(wrapper) = input.find("originated from your application code") {
+        let quoted = input[wrapper..].lines().skip(1).find_map(|line| line.trim().strip_prefix("> "));
+        if let Some(quoted) = quoted {
+            return Some(truncate(quoted.trim(), 160));
+        }
+    }
+    let line = input
+        .lines()
+        .map(str::trim)
+        .find(|line| line.contains("Timed out retrying") || line.contains("Error:"))
+        .or_else(|| input.lines().map(str::trim).find(|line| !line.is_empty()))?;
+
+    let mut message = line;
+    for prefix in ["CypressError:", "AssertionError:", "Error:"] {
+        message = message.strip_prefix(prefix).map_or(message, str::trim_start);
+    }
+    if let Some(timeout) = PATTERNS.cypress_timeout.find(message) {
+        message = message[timeout.end()..].trim_start_matches(':').trim_start();
+    }
+    Some(truncate(message, 160))
+}
+
+/// The failing `expect(...)` call from the code frame.
+fn test_matcher(input: &str) -> Option<String> {
+    code_frame_source(input).filter(|source| source.contains("expect"))
+}
+
+/// The failing source line of a code frame: the line marked `>` (Jest,
+/// Cypress), or the line above the `^` marker (Vitest), without its `;`.
+fn code_frame_source(input: &str) -> Option<String> {
+    let mut previous = None;
+    let mut marked = None;
+    for line in input.lines() {
+        if let Some(code) = PATTERNS.code_frame_line.captures(line) {
+            let source = code.get(2).map_or("", |m| m.as_str());
+            if code.get(1).is_some() {
+                marked = Some(source);
+                break;
+            }
+            previous = Some(source);
+        } else if PATTERNS.code_frame_caret.is_match(line) && previous.is_some() {
+            marked = previous;
+            break;
+        }
+    }
+    Some(truncate(marked?.trim().trim_end_matches(';'), 120))
+}
+
+/// Changed lines of a `- Expected / + Received` diff, each prefixed with its
+/// sign, with every run of unchanged lines collapsed to `…`.
+fn compact_diff(input: &str) -> Option<String> {
+    let mut lines = input.lines().map(str::trim);
+    lines.by_ref().find(|line| PATTERNS.diff_received_header.is_match(line))?;
+
+    let mut parts: Vec<String> = Vec::new();
+    for line in lines.skip_while(|line| line.is_empty()).take_while(|line| !line.is_empty()) {
+        match line.chars().next() {
+            Some(sign @ ('-' | '+')) => parts.push(format!("{}{}", sign, line[1..].trim())),
+            _ if parts.last().is_some_and(|last| last == "…") => {}
+            _ => parts.push("…".to_string()),
+        }
+    }
+    if parts.iter().all(|part| part == "…") {
+        return None;
+    }
+    Some(truncate(&parts.join(" "), 200))
+}
+
+/// Collect up to three stack frames that point at user code (framework noise removed).
+pub fn extract_user_frames(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .filter(|line| PATTERNS.user_frame.is_match(line) && !PATTERNS.framework_noise.is_match(line))
+        .take(3)
+        .map(|s| s.trim().to_string())
+        .collect()
+}
+
+// Extraction helpers
+fn extract_by_pattern_or_contains(input: &str, pattern: &Regex, fallback_contains: &str) -> Option<String> {
+    pattern
+        .find(input)
+        .map(|m| m.as_str().to_string())
+        .or_else(|| find_line_containing(input, &[fallback_contains]))
+}
+
+fn extract_first_match(input: &str, pattern: &Regex) -> Option<String> {
+    pattern.find(input).map(|m| m.as_str().to_string())
+}
+
+fn extract_first_capture(input: &str, pattern: &Regex) -> Option<String> {
+    pattern.captures(input).and_then(|c| c.get(1)).map(|m| m.as_str().trim().to_string())
+}
+
+fn extract_first_match_truncated(input: &str, pattern: &Regex, max_len: usize) -> Option<String> {
+    pattern.find(input).map(|m| truncate(m.as_str(), max_len))
+}
+
+fn find_line_containing(input: &str, needles: &[&str]) -> Option<String> {
+    input
+        .lines()
+        .find(|line| {
+            let lower = line.to_lowercase();
+            needles.iter().any(|n| lower.contains(&n.to_lowercase()))
+        })
+        .map(|s| s.trim().to_string())
+}
+
+fn find_line_starting_with(input: &str, prefixes: &[&str]) -> Option<String> {
+    // First try exact start-of-line match
+    if let Some(line) = input
+        .lines()
+        .find(|line| prefixes.iter().any(|p| line.starts_with(p)))
+    {
+        return Some(line.to_string());
+    }
+
+    // Fall back to finding pattern anywhere in line (browser console format: file.js:29 ErrorType:)
+    for line in input.lines() {
+        for prefix in prefixes {
+            if let Some(idx) = line.find(prefix) {
+                // Extract from the error type onwards
+                return Some(line[idx..].to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ToonifiedError;
+
+    #[test]
+    fn extracts_react_key_issue() {
+        let input = "Encountered two children with the same key, `test-key`. Keys should be unique.";
+        let result = ToonifiedError::new(input, ErrorType::ReactKey);
+        assert!(result.issue.is_some());
+        assert!(result.issue.unwrap().contains("same key"));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // File Location Extraction Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn extracts_simple_file_location() {
+        let input = "Error at MyComponent.tsx:42";
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("MyComponent.tsx:42".to_string()));
+    }
+
+    #[test]
+    fn prefers_user_code_over_node_modules() {
+        let input = r#"
+    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
+    at Dashboard (webpack-internal:///./src/pages/Dashboard.tsx:45:23)
+    at App (webpack-internal:///./node_modules/react-router/index.js:100:5)
+"#;
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("Dashboard.tsx:45".to_string()));
+    }
+
+    #[test]
+    fn falls_back_to_node_modules_if_no_user_code() {
+        let input = r#"
+    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
+    at Container (webpack-internal:///./node_modules/@mui/material/Container.js:55:12)
+"#;
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("CardContent.js:82".to_string()));
+    }
+
+    #[test]
+    fn extracts_mdx_file_location() {
+        let input = "Error in iOS-SafeArea-Guide.mdx:79";
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("iOS-SafeArea-Guide.mdx:79".to_string()));
+    }
+
+    #[test]
+    fn extracts_vue_file_location() {
+        let input = "Error at MyComponent.vue:123";
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("MyComponent.vue:123".to_string()));
+    }
+
+    #[test]
+    fn extracts_svelte_file_location() {
+        let input = "Error at App.svelte:42";
+        let result = extract_file_location(input);
+        assert_eq!(result, Some("App.svelte:42".to_string()));
+    }
+
+    #[test]
+    fn returns_none_when_no_file_location() {
+        let input = "Some error without file reference";
+        let result = extract_file_location(input);
+        assert!(result.is_none());
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Issue Extraction Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn extracts_dom_nesting_issue_appear_as() {
+        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.";
+        let result = extract_issue(input, ErrorType::DomNesting);
+        assert_eq!(result, Some("<p> cannot appear as a descendant of <p>".to_string()));
+    }
+
+    #[test]
+    fn extracts_dom_nesting_issue_be_a() {
+        let input = "In HTML, <div> cannot be a descendant of <p>.";
+        let result = extract_issue(input, ErrorType::DomNesting);
+        assert_eq!(result, Some("<div> cannot be a descendant of <p>".to_string()));
+    }
+
+    #[test]
+    fn extracts_hydration_issue() {
+        let input = "Uncaught Error: Hydration failed because the initial UI does not match.";
+        let result = extract_issue(input, ErrorType::Hydration);
+        assert_eq!(result, Some("Uncaught Error: Hydration failed because the initial UI does not match.".to_string()));
+    }
+
+    #[test]
+    fn extracts_type_error_issue() {
+        let input = "TypeError: Cannot read properties of undefined (reading 'map')\n    at Array.map";
+        let result = extract_issue(input, ErrorType::TypeError);
+        assert_eq!(result, Some("TypeError: Cannot read properties of undefined (reading 'map')".to_string()));
+    }
+
+    #[test]
+    fn extracts_system_error_code() {
+        let input = "Error: ENOENT: no such file or directory, open '/path/to/file'";
+        let result = extract_issue(input, ErrorType::SystemError);
+        assert_eq!(result, Some("ENOENT: no such file or directory, open '/path/to/file'".to_string()));
+    }
+
+    #[test]
+    fn extracts_storybook_code() {
+        let input = "SB_PREVIEW_API_UNDEFINED: The preview API is not available.";
+        let result = extract_issue(input, ErrorType::Storybook);
+        assert!(result.unwrap().starts_with("SB_PREVIEW_API_UNDEFINED"));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // User Frame Extraction Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn extracts_user_frames_filters_node_modules() {
+        let input = r#"
+    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
+    at Dashboard (./src/pages/Dashboard.tsx:45:23)
+    at App (./src/App.tsx:18:42)
+    at Router (webpack-internal:///./node_modules/react-router/index.js:100:5)
+"#;
+        let result = extract_user_frames(input);
+        assert_eq!(result.len(), 2);
+        assert!(result[0].contains("Dashboard.tsx"));
+        assert!(result[1].contains("App.tsx"));
+    }
+
+    #[test]
+    fn limits_user_frames_to_three() {
+        let input = r#"
+    at Component1 (./src/Component1.tsx:10:5)
+    at Component2 (./src/Component2.tsx:20:5)
+    at Component3 (./src/Component3.tsx:30:5)
+    at Component4 (./src/Component4.tsx:40:5)
+    at Component5 (./src/Component5.tsx:50:5)
+"#;
+        let result = extract_user_frames(input);
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn returns_empty_when_no_user_frames() {
+        let input = "Some error without stack trace";
+        let result = extract_user_frames(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn extracts_playwright_issue() {
+        let input = "TimeoutError: locator.click: Timeout 30000ms exceeded.\n    waiting for locator('.nonexistent')";
+        let result = ToonifiedError::new(input, ErrorType::Playwright);
+        assert!(result.issue.is_some());
+        assert!(result.issue.unwrap().contains("Timeout"));
+    }
+
+    #[test]
+    fn extracts_vue_3_warning_and_component_chain() {
+        let input = "[Vue warn]: Unhandled error during execution of render function \n  at <ProductList products=undefined > \n  at <Shop>\n  at <App>\n    at callWithErrorHandling (runtime-core.esm-bundler.js:158:18)";
+        let result = ToonifiedError::new(input, ErrorType::VueError);
+        assert_eq!(result.issue.as_deref(), Some("Unhandled error during execution of render function"));
+        assert_eq!(result.details, vec![("components".to_string(), "ProductList < Shop < App".to_string())]);
+        assert!(result.frames.is_empty());
+    }
+
+    #[test]
+    fn extracts_vue_2_component_trace_file() {
+        let input = "[Vue warn]: Error in render: \"TypeError: Cannot read property 'map' of undefined\"\n\nfound in\n\n---> <ProductList> at src/components/ProductList.vue\n       <App> at src/App.vue\n         <Root>";
+        let result = ToonifiedError::new(input, ErrorType::VueError);
+        assert_eq!(result.file_location.as_deref(), Some("ProductList.vue"));
+        assert_eq!(result.details[0].1, "ProductList < App < Root");
+    }
+
+    #[test]
+    fn vue_template_markup_is_not_a_component_trace() {
+        let input = "[Vue warn]: Error compiling template:\n\n<div>{{ total }</div>\n<Cart :items=\"items\"/>\n\n- invalid expression: Unexpected token '}' in\n\n(found in <Root>)";
+        let result = ToonifiedError::new(input, ErrorType::VueWarn);
+        assert_eq!(result.issue.as_deref(), Some("Error compiling template:"));
+        assert!(result.details.is_empty(), "{:?}", result.details);
+    }
+
+    #[test]
+    fn user_files_named_like_angular_runtimes_are_kept() {
+        let input = "TypeError: x is undefined\n    at formatDate (src/polyfills/intl.ts:8:3)\n    at load (src/lib/core.mjs:4:1)\n    at Zone.run (http://localhost:4200/polyfills.js:1:2)\n    at bootstrap (core.mjs:31:8)\n    at ApplicationRef.tick (node_modules/@angular/core/fesm2022/core.mjs:30:1)";
+        assert_eq!(extract_file_location(input).as_deref(), Some("intl.ts:8"));
+        assert_eq!(extract_user_frames(input), vec!["at formatDate (src/polyfills/intl.ts:8:3)".to_string()]);
+        assert!(!PATTERNS.vendor_runtime.is_match("at load (src/lib/core.mjs:4:1)"));
+    }
+
+    #[test]
+    fn long_component_chains_keep_root() {
+        assert_eq!(component_chain(&["A", "B", "B", "C", "D", "E", "F", "Root"]), "A < B < C < D < … < Root");
+    }
+
+    #[test]
+    fn extracts_angular_code_message_and_injector_chain() {
+        let input = "ERROR NullInjectorError: R3InjectorError(AppModule)[UserService -> HttpClient -> HttpClient]: \n  NullInjectorError: NG0201: No provider for HttpClient!\n    at NullInjector.get (core.mjs:7599:27)\n    at _ZoneDelegate.invoke (zone.js:368:26)\n    at UserListComponent_Factory (src/app/user-list.component.ts:12:5)";
+        let result = ToonifiedError::new(input, ErrorType::Angular);
+        assert_eq!(result.issue.as_deref(), Some("NullInjectorError: No provider for HttpClient!"));
+        assert_eq!(
+            result.details,
+            vec![
+                ("code".to_string(), "NG0201".to_string()),
+                ("injector".to_string(), "AppModule: UserService -> HttpClient".to_string()),
+            ]
+        );
+        assert_eq!(result.file_location.as_deref(), Some("user-list.component.ts:12"));
+        assert_eq!(result.frames, vec!["at UserListComponent_Factory (src/app/user-list.component.ts:12:5)".to_string()]);
+    }
+
+    #[test]
+    fn angular_message_drops_code_and_docs_link() {
+        let input = "ERROR Error: NG0100: ExpressionChangedAfterItHasBeenCheckedError: Expression has changed after it was checked. Previous value: 'false'. Current value: 'true'.. Find more at https://angular.io/errors/NG0100";
+        assert_eq!(
+            angular_message(input).as_deref(),
+            Some("ExpressionChangedAfterItHasBeenCheckedError: Expression has changed after it was checked. Previous value: 'false'. Current value: 'true'")
+        );
+    }
+
+    #[test]
+    fn extracts_svelte_compiler_message_and_route() {
+        let input = "[plugin:vite-plugin-svelte] /app/src/routes/blog/+page.svelte:12:4 Expected '}'\n/app/src/routes/blog/+page.svelte:12:4\n10 |  <h1>{title</h1>\n         ^";
+        let result = ToonifiedError::new(input, ErrorType::Svelte);
+        assert_eq!(result.issue.as_deref(), Some("Expected '}'"));
+        assert_eq!(result.file_location.as_deref(), Some("+page.svelte:12"));
+        assert_eq!(result.details, vec![("route".to_string(), "/blog".to_string())]);
+    }
+
+    #[test]
+    fn extracts_sveltekit_load_route_without_groups() {
+        let input = "Error: Failed to load post\n    at load (/app/src/routes/(app)/blog/[slug]/+page.server.ts:8:11)\n    at Module.load_server_data (/app/node_modules/@sveltejs/kit/src/runtime/server/page/load_data.js:61:41)";
+        let result = ToonifiedError::new(input, ErrorType::Svelte);
+        assert_eq!(result.issue.as_deref(), Some("Error: Failed to load post"));
+        assert_eq!(result.details, vec![("route".to_string(), "/blog/[slug]".to_string())]);
+        assert_eq!(result.frames.len(), 1);
+    }
+
+    #[test]
+    fn extracts_svelte_runtime_code_and_not_found_route() {
+        let input = "Uncaught Svelte error: state_unsafe_mutation\nUpdating state inside a derived is forbidden\n    at Counter.svelte:8:3";
+        let result = ToonifiedError::new(input, ErrorType::Svelte);
+        assert_eq!(result.issue.as_deref(), Some("Updating state inside a derived is forbidden"));
+        assert_eq!(result.details, vec![("code".to_string(), "state_unsafe_mutation".to_string())]);
+
+        let result = ToonifiedError::new("SvelteKitError: Not found: /blog/missing", ErrorType::Svelte);
+        assert_eq!(result.details, vec![("route".to_string(), "/blog/missing".to_string())]);
+
+        let result = ToonifiedError::new("Error: Not found: /api/users\n    at getUser (server/routes.js:14:11)", ErrorType::Svelte);
+        assert!(result.details.is_empty());
+    }
+
+    #[test]
+    fn extracts_jest_test_path_matcher_and_diff() {
+        let input = "  ● ProductList › renders items\n\n    expect(received).toEqual(expected) // deep equality\n\n    - Expected  - 1\n    + Received  + 1\n\n      Object {\n        \"name\": \"Widget\",\n    -   \"price\": 10,\n    +   \"price\": 12,\n      }\n\n    > 42 |     expect(item).toEqual({ name: \"Widget\", price: 10 });\n         |                  ^\n\n      at ProductList (src/components/ProductList.tsx:12:20)\n      at Object.<anonymous> (src/__tests__/ProductList.test.tsx:42:18)\n      at Promise.then.completed (node_modules/jest-circus/build/utils.js:298:28)";
+        let result = ToonifiedError::new(input, ErrorType::TestFailure);
+        assert_eq!(result.issue.as_deref(), Some("expect(received).toEqual(expected)"));
+        assert_eq!(result.file_location.as_deref(), Some("ProductList.test.tsx:42"));
+        assert_eq!(
+            result.details,
+            vec![
+                ("test".to_string(), "ProductList › renders items".to_string()),
+                ("matcher".to_string(), "expect(item).toEqual({ name: \"Widget\", price: 10 })".to_string()),
+                ("diff".to_string(), "… -\"price\": 10, +\"price\": 12, …".to_string()),
+            ]
+        );
+        assert_eq!(result.frames.len(), 2);
+    }
+
+    #[test]
+    fn extracts_vitest_failure_with_expected_and_received() {
+        let input = " FAIL  src/utils/math.test.ts > math > adds numbers\nAssertionError: expected 2 to be 3 // Object.is equality\n\nExpected: 3\nReceived: 2\n\n ❯ src/utils/math.test.ts:5:17\n      4|     it('adds numbers', () => {\n      5|       expect(add(1, 1)).toBe(3)\n       |                 ^";
+        let result = ToonifiedError::new(input, ErrorType::TestFailure);
+        assert_eq!(result.issue.as_deref(), Some("AssertionError: expected 2 to be 3"));
+        assert_eq!(result.file_location.as_deref(), Some("math.test.ts:5"));
+        assert_eq!(
+            result.details,
+            vec![
+                ("test".to_string(), "math › adds numbers".to_string()),
+                ("matcher".to_string(), "expect(add(1, 1)).toBe(3)".to_string()),
+                ("expected".to_string(), "3".to_string()),
+                ("received".to_string(), "2".to_string()),
+            ]
+        );
+        assert_eq!(result.frames, vec!["❯ src/utils/math.test.ts:5:17".to_string()]);
+    }
+
+    #[test]
+    fn extracts_cypress_command_selector_and_timeout() {
+        let input = "CypressError: Timed out retrying after 4000ms: Expected to find element: `[data-cy=submit]`, but never found it.\n    at $Cy.ensureExistence (https://localhost:3000/__cypress/runner/cypress_runner.js:150713:26)\n    at Context.eval (https://localhost:3000/__cypress/tests?p=cypress/e2e/login.cy.ts:14:8)\n\n  12 |   cy.visit('/login')\n  13 |   cy.get('[data-cy=email]').type('a@b.c')\n> 14 |   cy.get('[data-cy=submit]').click()\n     |      ^";
+        let result = ToonifiedError::new(input, ErrorType::Cypress);
+        assert_eq!(result.issue.as_deref(), Some("Expected to find element: `[data-cy=submit]`, but never found it."));
+        assert_eq!(result.file_location.as_deref(), Some("login.cy.ts:14"));
+        assert_eq!(
+            result.details,
+            vec![
+                ("command".to_string(), "cy.get('[data-cy=submit]').click()".to_string()),
+                ("selector".to_string(), "[data-cy=submit]".to_string()),
+                ("timeout".to_string(), "4000ms".to_string()),
+            ]
+        );
+        assert_eq!(result.frames, vec!["at Context.eval (https://localhost:3000/__cypress/tests?p=cypress/e2e/login.cy.ts:14:8)".to_string()]);
+    }
+
+    #[test]
+    fn extracts_application_error_from_cypress_uncaught_wrapper() {
+        let input = "The following error originated from your application code, not from Cypress.\n\n  > Cannot read properties of undefined (reading 'map')\n\nWhen Cypress detects uncaught errors originating from your application it will automatically fail the current test.";
+        let result = ToonifiedError::new(input, ErrorType::Cypress);
+        assert_eq!(result.issue.as_deref(), Some("Cannot read properties of undefined (reading 'map')"));
+        assert!(result.details.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! Follow a growing log file, like `tail -F`.
+//!
+//! [`Follow`] is a reader that never reaches end of input: at the end of the
+//! file it waits for more data. If the file is truncated it starts over from
+//! the beginning; if it is rotated (the path now names a different file) it
+//! switches to the new file once the old one is drained. Wrap it in a
+//! `BufReader` and hand it to [`BlockStream`](crate::BlockStream).
+
+use std::fs::{self, File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Default time between checks for new data, truncation, and rotation.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Endless reader over the contents appended to a file.
+pub struct Follow {
+    path: PathBuf,
+    file: Option<File>,
+    /// Bytes read from the current file.
+    pos: u64,
+    poll_interval: Duration,
+}
+
+impl Follow {
+    /// Follow `path` from its current end; only data written from now on is read.
+    /// A file that doesn't exist yet is waited for and read from the start.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let (file, pos) = match File::open(&path) {
+            Ok(mut file) => {
+                let pos = file.seek(SeekFrom::End(0)).unwrap_or(0);
+                (Some(file), pos)
+            }
+            Err(_) => (None, 0),
+        };
+        Self { path, file, pos, poll_interval: DEFAULT_POLL_INTERVAL }
+    }
+
+    /// Follow `path` from its beginning.
+    pub fn from_start(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self { file: File::open(&path).ok(), path, pos: 0, poll_interval: DEFAULT_POLL_INTERVAL }
+    }
+
+    /// Set how long to sleep between checks when there is no new data.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// At end of the current file: reopen or rewind if the file was rotated or
+    /// truncated. Returns whether there may be new data to read.
+    fn check_path(&mut self) -> io::Result<bool> {
+        let Ok(current) = fs::metadata(&self.path) else {
+            // Rotated away and not recreated yet; keep waiting
+            return Ok(false);
+        };
+
+        let Some(file) = &mut self.file else {
+            self.file = Some(File::open(&self.path)?);
+            self.pos = 0;
+            return Ok(true);
+        };
+
+        if !same_file(&file.metadata()?, &current) {
+            self.file = Some(File::open(&self.path)?);
+            self.pos = 0;
+            return Ok(true);
+        }
+
+        if current.len() < self.pos {
+            file.seek(SeekFrom::Start(0))?;
+            self.pos = 0;
+            return Ok(true);
+        }
+
+        Ok(current.len() > self.pos)
+    }
+}
+
+impl Read for Follow {
+    /// Blocks until at least one byte is available; never returns `Ok(0)` for a non-empty `buf`.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if let Some(file) = &mut self.file {
+                let n = file.read(buf)?;
+                if n > 0 {
+                    self.pos += n as u64;
+                    return Ok(n);
+                }
+            }
+            if !self.check_path()? {
+                thread::sleep(self.poll_interval);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn same_file(a: &Metadata, b: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+/// Without inode numbers, a rotated file looks like a truncated one: a new file
+/// shorter than the old position is read from the start.
+#[cfg(not(unix))]
+fn same_file(_: &Metadata, _: &Metadata) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("error-toon-follow-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn next_line(reader: &mut impl BufRead) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line
+    }
+
+    #[test]
+    fn starts_at_end_and_reads_appended_lines() {
+        let path = temp_path("append");
+        append(&path, "old line\n");
+        let mut reader = BufReader::new(Follow::new(&path).poll_interval(Duration::from_millis(10)));
+
+        append(&path, "new line\n");
+        assert_eq!(next_line(&mut reader), "new line\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn restarts_after_truncation() {
+        let path = temp_path("truncate");
+        append(&path, "first line that is long\n");
+        let mut reader = BufReader::new(Follow::from_start(&path).poll_interval(Duration::from_millis(10)));
+        assert_eq!(next_line(&mut reader), "first line that is long\n");
+
+        fs::write(&path, "short\n").unwrap();
+        assert_eq!(next_line(&mut reader), "short\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn switches_to_new_file_after_rotation() {
+        let path = temp_path("rotate");
+        let rotated = path.with_extension("1");
+        append(&path, "before\n");
+        let mut reader = BufReader::new(Follow::from_start(&path).poll_interval(Duration::from_millis(10)));
+        assert_eq!(next_line(&mut reader), "before\n");
+
+        append(&path, "tail of old file\n");
+        fs::rename(&path, &rotated).unwrap();
+        append(&path, "after rotation, a much longer first line\n");
+        assert_eq!(next_line(&mut reader), "tail of old file\n");
+        assert_eq!(next_line(&mut reader), "after rotation, a much longer first line\n");
+
+        fs::remove_file(path).unwrap();
+        fs::remove_file(rotated).unwrap();
+    }
+
+    #[test]
+    fn waits_for_missing_file() {
+        let path = temp_path("missing");
+        let mut reader = BufReader::new(Follow::new(&path).poll_interval(Duration::from_millis(10)));
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                append(&path, "created\n");
+            })
+        };
+        assert_eq!(next_line(&mut reader), "created\n");
+        writer.join().unwrap();
+        fs::remove_file(path).unwrap();
+    }
+}
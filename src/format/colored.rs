@@ -0,0 +1,229 @@
+//! Colored terminal formatter (boxed output).
+
+use crate::format::plain::{format_multi_plain, plain_section};
+use crate::format::stats::{token_summary, total, CompressionStats, Original};
+use crate::model::{FileErrors, ToonifiedError};
+use crate::util::truncate;
+use colored::*;
+
+const BOX_WIDTH: usize = 43;
+const CONTENT_WIDTH: usize = BOX_WIDTH - 4; // Account for "│ " and " │"
+const TRUNCATE_WIDTH: usize = CONTENT_WIDTH - 3; // Account for "..."
+
+impl ToonifiedError {
+    /// Render as a colored terminal box. Stats describe the plain output, which is
+    /// what gets copied alongside colored display.
+    pub fn format_colored(&self) -> String {
+        self.format_colored_for(&self.format_plain())
+    }
+
+    /// Render as a colored terminal box whose stats describe `copied`, the text
+    /// actually handed to the user (plain, TOON, ...).
+    pub fn format_colored_for(&self, copied: &str) -> String {
+        self.format_colored_against(copied, self.original_len)
+    }
+
+    /// Like [`format_colored_for`](Self::format_colored_for), with stats measured
+    /// against `original`; adds a token row when `original` carries token counts.
+    pub fn format_colored_against<'a>(&self, copied: &str, original: impl Into<Original<'a>>) -> String {
+        let stats = CompressionStats::measure(original, copied);
+        let mut box_lines = self.colored_box();
+
+        box_lines.separator();
+        box_lines.row(
+            &format!("📦 {}c → {}c ({}% saved)", stats.original_chars, stats.compressed_chars, stats.savings_pct()),
+            Color::Green,
+        );
+        if let Some(tokens) = token_summary(&stats) {
+            box_lines.row(&format!("   {}", tokens), Color::Green);
+        }
+
+        box_lines.build()
+    }
+
+    /// Box with the header, tags, location, issue, and frames; callers add stats and build it.
+    fn colored_box(&self) -> BoxBuilder {
+        let mut box_lines = BoxBuilder::new(self.error_type.color());
+
+        let mut header = format!("{} {}", self.error_type.icon(), self.error_type.name());
+        if let Some(confidence) = self.confidence {
+            header.push_str(&format!(" ({:.0}%)", confidence * 100.0));
+        }
+        box_lines.header(&header);
+
+        if !self.tags.is_empty() {
+            let tags = format!(" +{}", self.tag_names().join(", "));
+            box_lines.row(&truncate(&tags, TRUNCATE_WIDTH), Color::BrightBlack);
+        }
+
+        if let Some(ref loc) = self.file_location {
+            box_lines.row(&format!(" {}", loc), Color::White);
+        }
+
+        if let Some(ref issue) = self.issue {
+            box_lines.row(&format!(" {}", truncate(issue, TRUNCATE_WIDTH)), Color::Yellow);
+        }
+
+        for (key, value) in &self.details {
+            box_lines.row(&truncate(&format!(" {}: {}", key, value), TRUNCATE_WIDTH), Color::White);
+        }
+
+        if !self.frames.is_empty() {
+            box_lines.row("frames:", Color::BrightBlack);
+            for frame in &self.frames {
+                box_lines.row(&format!("  {}", truncate(frame, TRUNCATE_WIDTH - 2)), Color::Cyan);
+            }
+        }
+
+        box_lines
+    }
+}
+
+/// Format multiple errors in colored format (multiple boxes), with stats for the plain output
+pub fn format_multi_colored<'a>(errors: &[ToonifiedError], original: impl Into<Original<'a>>) -> String {
+    let original = original.into();
+    format_multi_colored_for(errors, original, &format_multi_plain(errors, original))
+}
+
+/// Format multiple errors in colored format, with stats describing `copied`
+pub fn format_multi_colored_for<'a>(errors: &[ToonifiedError], original: impl Into<Original<'a>>, copied: &str) -> String {
+    let boxes: Vec<String> = errors.iter().map(|error| error.colored_box().build()).collect();
+
+    let stats = CompressionStats::measure(original, copied);
+    format!("{}\n\n{}", boxes.join("\n\n"), stats_line(&format!("{} errors", errors.len()), &stats))
+}
+
+/// Format errors from several files as a bold header per file, its boxes, and
+/// that file's stats (for its plain section), then stats describing `copied`.
+pub fn format_files_colored_for(files: &[FileErrors], copied: &str) -> String {
+    let mut sections: Vec<String> = files
+        .iter()
+        .map(|file| {
+            let boxes: Vec<String> = file.errors.iter().map(|error| error.colored_box().build()).collect();
+            let stats = CompressionStats::measure(file.original, &plain_section(file));
+            let label = format!("{} errors", file.errors.len());
+            format!("{}\n{}\n\n{}", format!("── {} ──", file.name).bold(), boxes.join("\n\n"), stats_line(&label, &stats))
+        })
+        .collect();
+
+    let count: usize = files.iter().map(|file| file.errors.len()).sum();
+    let stats = CompressionStats::measure(total(files.iter().map(|file| file.original)), copied);
+    sections.push(stats_line(&format!("total: {} errors in {} files", count, files.len()), &stats));
+    sections.join("\n\n")
+}
+
+/// Green `📦 <label>: 4521c → 198c (95% saved)` line, with tokens when counted.
+fn stats_line(label: &str, stats: &CompressionStats) -> String {
+    let tokens = token_summary(stats).map(|tokens| format!(" · {}", tokens)).unwrap_or_default();
+    format!(
+        "📦 {}: {}c → {}c ({}% saved){}",
+        label,
+        stats.original_chars,
+        stats.compressed_chars,
+        stats.savings_pct(),
+        tokens
+    ).green().to_string()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Box Drawing Helper
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct BoxBuilder {
+    lines: Vec<String>,
+    color: Color,
+}
+
+impl BoxBuilder {
+    fn new(color: Color) -> Self {
+        Self { lines: vec![], color }
+    }
+
+    fn header(&mut self, text: &str) {
+        self.lines.push(self.horizontal_line('╭', '╮'));
+        self.lines.push(self.content_line(text, self.color, true));
+        self.lines.push(self.horizontal_line('├', '┤'));
+    }
+
+    fn row(&mut self, text: &str, content_color: Color) {
+        self.lines.push(self.content_line(text, content_color, false));
+    }
+
+    fn separator(&mut self) {
+        self.lines.push(self.horizontal_line('├', '┤'));
+    }
+
+    fn build(mut self) -> String {
+        self.lines.push(self.horizontal_line('╰', '╯'));
+        self.lines.join("\n")
+    }
+
+    fn horizontal_line(&self, left: char, right: char) -> String {
+        format!(
+            "{}{}{}",
+            left.to_string().color(self.color),
+            "─".repeat(BOX_WIDTH - 2).color(self.color),
+            right.to_string().color(self.color)
+        )
+    }
+
+    fn content_line(&self, text: &str, content_color: Color, bold: bool) -> String {
+        let content = if bold {
+            text.color(content_color).bold().to_string()
+        } else {
+            text.color(content_color).to_string()
+        };
+
+        let visible_len = text.chars().count();
+        let padding = CONTENT_WIDTH.saturating_sub(visible_len);
+
+        format!(
+            "{} {}{} {}",
+            "│".color(self.color),
+            content,
+            " ".repeat(padding),
+            "│".color(self.color)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+    use crate::format::toon::format_multi_toon;
+
+    #[test]
+    fn colored_stats_describe_copied_text() {
+        let error = ToonifiedError::new("TypeError: foo is not a function", ErrorType::TypeError);
+        let toon = error.format_toon();
+        let output = error.format_colored_for(&toon);
+        assert!(output.contains(&format!("→ {}c", toon.chars().count())));
+    }
+
+    #[test]
+    fn multi_colored_stats_describe_copied_text() {
+        let errors = vec![
+            ToonifiedError::new("TypeError: foo", ErrorType::TypeError),
+            ToonifiedError::new("ReferenceError: bar", ErrorType::RefError),
+        ];
+        let toon = format_multi_toon(&errors, 5000);
+        let output = format_multi_colored_for(&errors, 5000, &toon);
+        assert!(output.contains(&format!("2 errors: 5000c → {}c", toon.chars().count())));
+    }
+
+    #[test]
+    fn files_colored_labels_files_with_per_file_and_total_stats() {
+        let files = vec![FileErrors {
+            name: "logs/app.log".to_string(),
+            errors: vec![ToonifiedError::new("TypeError: foo", ErrorType::TypeError)],
+            original: Original::from(900),
+        }];
+        let copied = crate::format::plain::format_files_plain(&files);
+        let output = format_files_colored_for(&files, &copied);
+
+        assert!(output.starts_with(&"── logs/app.log ──".bold().to_string()));
+        assert!(output.contains(&format!("1 errors: 900c → {}c", plain_section(&files[0]).chars().count())));
+        assert!(output.contains(&format!("total: 1 errors in 1 files: 900c → {}c", copied.chars().count())));
+    }
+}
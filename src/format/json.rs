@@ -0,0 +1,321 @@
+//! JSON formatter for scripts and editor integrations.
+//!
+//! Output is a top-level array: one object per error, in the order returned by
+//! [`split_into_error_blocks`](crate::split_into_error_blocks), followed by a
+//! final `{"stats": {...}}` object with aggregate numbers. [`NdjsonWriter`]
+//! streams the same objects one per line. [`format_files_json`] groups errors
+//! into one object per input file.
+
+use crate::format::stats::{render_exact, render_exact_after, total, CompressionStats, Original};
+use crate::model::{FileErrors, ToonifiedError};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// One error plus its compressed size.
+#[derive(Serialize)]
+struct JsonError<'a> {
+    #[serde(flatten)]
+    error: &'a ToonifiedError,
+    /// Characters in the error's plain `key: value` body (no stats footer).
+    compressed_len: usize,
+}
+
+/// Aggregate stats, keyed like the TOON `stats` object. `comp` and `comp_bytes`
+/// are the exact size of the whole JSON output, stats included.
+#[derive(Serialize)]
+struct JsonStats {
+    orig: usize,
+    comp: usize,
+    comp_bytes: usize,
+    pct: usize,
+    count: usize,
+    /// Number of input files, in multi-file output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tokens: Option<JsonTokens>,
+}
+
+/// Token counts, present when a tokenizer was used.
+#[derive(Serialize)]
+struct JsonTokens {
+    orig: usize,
+    comp: usize,
+    pct: usize,
+}
+
+impl JsonStats {
+    fn new(stats: &CompressionStats, count: usize) -> Self {
+        Self {
+            orig: stats.original_chars,
+            comp: stats.compressed_chars,
+            comp_bytes: stats.compressed_bytes,
+            pct: stats.savings_pct(),
+            count,
+            files: None,
+            tokens: stats.tokens().map(|(orig, comp, pct)| JsonTokens { orig, comp, pct }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonItem<'a> {
+    Error(JsonError<'a>),
+    File { source: &'a str, errors: Vec<JsonError<'a>>, stats: JsonStats },
+    Stats { stats: JsonStats },
+}
+
+impl<'a> JsonError<'a> {
+    fn new(error: &'a ToonifiedError) -> Self {
+        Self { error, compressed_len: error.json_compressed_len() }
+    }
+}
+
+impl ToonifiedError {
+    /// Characters in the plain body, used as this error's compressed size in JSON output.
+    fn json_compressed_len(&self) -> usize {
+        self.plain_lines().join("\n").chars().count()
+    }
+}
+
+/// Format one or more errors as a pretty-printed JSON array with a trailing stats object.
+pub fn format_json<'a>(errors: &[ToonifiedError], original: impl Into<Original<'a>>) -> String {
+    let items: Vec<JsonItem> = errors.iter().map(|error| JsonItem::Error(JsonError::new(error))).collect();
+
+    render_exact(original, |stats| {
+        let stats = JsonItem::Stats { stats: JsonStats::new(stats, errors.len()) };
+        let all: Vec<&JsonItem> = items.iter().chain([&stats]).collect();
+        serde_json::to_string_pretty(&all).expect("JSON serialization of errors cannot fail")
+    })
+}
+
+/// Format errors from several files as a JSON array of `{"source", "errors",
+/// "stats"}` objects, one per file, followed by a stats object for the whole
+/// document. A file's `stats` measure its errors rendered on their own by [`format_json`].
+pub fn format_files_json(files: &[FileErrors]) -> String {
+    let items: Vec<JsonItem> = files
+        .iter()
+        .map(|file| {
+            let standalone = CompressionStats::measure(file.original, &format_json(&file.errors, file.original));
+            JsonItem::File {
+                source: &file.name,
+                errors: file.errors.iter().map(JsonError::new).collect(),
+                stats: JsonStats::new(&standalone, file.errors.len()),
+            }
+        })
+        .collect();
+    let count = files.iter().map(|file| file.errors.len()).sum();
+
+    render_exact(total(files.iter().map(|file| file.original)), |stats| {
+        let stats = JsonItem::Stats { stats: JsonStats { files: Some(files.len()), ..JsonStats::new(stats, count) } };
+        let all: Vec<&JsonItem> = items.iter().chain([&stats]).collect();
+        serde_json::to_string_pretty(&all).expect("JSON serialization of errors cannot fail")
+    })
+}
+
+/// Streams errors as newline-delimited JSON, one object per line, with the
+/// stats object written last by [`finish`](Self::finish).
+pub struct NdjsonWriter<W: Write> {
+    out: W,
+    emitted: CompressionStats,
+    count: usize,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out, emitted: CompressionStats::default(), count: 0 }
+    }
+
+    /// Write one error as a single JSON line and flush it immediately.
+    pub fn write_error(&mut self, error: &ToonifiedError) -> io::Result<()> {
+        self.count += 1;
+        self.write_line(&json_line(&JsonItem::Error(JsonError::new(error))))
+    }
+
+    /// Write the trailing stats line. `total_original_len` is the number of
+    /// characters of input consumed; `comp` covers every line written, this one included.
+    pub fn finish(mut self, total_original_len: usize) -> io::Result<W> {
+        self.emitted.original_chars = total_original_len;
+        let count = self.count;
+        let line = render_exact_after(self.emitted, |stats| {
+            json_line(&JsonItem::Stats { stats: JsonStats::new(stats, count) })
+        });
+        self.write_line(&line)?;
+        Ok(self.out)
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.emitted.add_output(line, None);
+        self.out.write_all(line.as_bytes())?;
+        self.out.flush()
+    }
+}
+
+/// One compact JSON object followed by a newline.
+fn json_line(item: &JsonItem) -> String {
+    let mut line = serde_json::to_string(item).expect("JSON serialization of errors cannot fail");
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+    use crate::tokenizer::Tokenizer;
+    use serde_json::Value;
+
+    #[test]
+    fn json_format_serializes_error_fields() {
+        let input = "TypeError: foo is not a function\n    at App (src/App.tsx:12:5)";
+        let errors = vec![ToonifiedError::new(input, ErrorType::TypeError)];
+        let value: Value = serde_json::from_str(&format_json(&errors, input.len())).unwrap();
+
+        let error = &value[0];
+        assert_eq!(error["type"], "TYPE_ERROR");
+        assert_eq!(error["file"], "App.tsx:12");
+        assert_eq!(error["issue"], "TypeError: foo is not a function");
+        assert_eq!(error["frames"][0], "at App (src/App.tsx:12:5)");
+        assert_eq!(error["tags"], Value::Array(vec![]));
+        assert_eq!(error["confidence"], 1.0);
+        assert_eq!(error["original_len"], input.len());
+        assert!(error["compressed_len"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn json_format_serializes_tags_by_name() {
+        let input = "Unhandled Promise Rejection:\nTypeError: Failed to fetch";
+        let errors = vec![ToonifiedError::new(input, ErrorType::NetworkError)];
+        let value: Value = serde_json::from_str(&format_json(&errors, input.len())).unwrap();
+        assert_eq!(value[0]["tags"], serde_json::json!(["UNHANDLED_REJECTION", "TYPE_ERROR"]));
+        assert_eq!(value[0]["confidence"], 0.25);
+    }
+
+    #[test]
+    fn json_format_serializes_details_as_object_when_present() {
+        let plain = ToonifiedError::new("TypeError: boom", ErrorType::TypeError);
+        let detailed = plain.clone().with_detail("test", "Login › signs in").with_detail("retry", "1");
+        let value: Value = serde_json::from_str(&format_json(&[plain, detailed], 100)).unwrap();
+
+        assert!(value[0].get("details").is_none());
+        assert_eq!(value[1]["details"], serde_json::json!({"test": "Login › signs in", "retry": "1"}));
+    }
+
+    #[test]
+    fn json_format_missing_fields_are_null() {
+        let errors = vec![ToonifiedError::new("TypeError: foo", ErrorType::TypeError)];
+        let value: Value = serde_json::from_str(&format_json(&errors, 13)).unwrap();
+        assert!(value[0]["file"].is_null());
+        assert_eq!(value[0]["frames"], Value::Array(vec![]));
+    }
+
+    #[test]
+    fn json_format_preserves_block_order_and_appends_stats() {
+        let errors = vec![
+            ToonifiedError::new("TypeError: foo\n    at a.tsx:1", ErrorType::TypeError),
+            ToonifiedError::new("ReferenceError: bar\n    at b.tsx:2", ErrorType::RefError),
+        ];
+        let value: Value = serde_json::from_str(&format_json(&errors, 1000)).unwrap();
+        let items = value.as_array().unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["type"], "TYPE_ERROR");
+        assert_eq!(items[1]["type"], "REF_ERROR");
+        assert_eq!(items[2]["stats"]["orig"], 1000);
+        assert_eq!(items[2]["stats"]["count"], 2);
+    }
+
+    #[test]
+    fn json_stats_include_tokens_only_when_counted() {
+        let input = "TypeError: foo";
+        let tokenizer = Tokenizer::with_merges(&[]);
+        let errors = vec![ToonifiedError::new(input, ErrorType::TypeError)];
+
+        let output = format_json(&errors, Original::measure(input, Some(&tokenizer)));
+        let value: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value[1]["stats"]["tokens"]["orig"], input.len());
+        assert_eq!(value[1]["stats"]["tokens"]["comp"], output.len());
+
+        let value: Value = serde_json::from_str(&format_json(&errors, input.len())).unwrap();
+        assert!(value[1]["stats"].get("tokens").is_none());
+    }
+
+    #[test]
+    fn files_json_groups_errors_by_source() {
+        let files = vec![
+            FileErrors {
+                name: "a.log".to_string(),
+                errors: vec![ToonifiedError::new("TypeError: foo", ErrorType::TypeError)],
+                original: Original::from(400),
+            },
+            FileErrors {
+                name: "b.log".to_string(),
+                errors: vec![
+                    ToonifiedError::new("ReferenceError: bar", ErrorType::RefError),
+                    ToonifiedError::new("SyntaxError: baz", ErrorType::SyntaxError),
+                ],
+                original: Original::from(600),
+            },
+        ];
+        let output = format_files_json(&files);
+        let value: Value = serde_json::from_str(&output).unwrap();
+        let items = value.as_array().unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["source"], "a.log");
+        assert_eq!(items[0]["errors"][0]["type"], "TYPE_ERROR");
+        assert_eq!(items[0]["stats"]["comp"], format_json(&files[0].errors, 400).chars().count());
+        assert_eq!(items[1]["source"], "b.log");
+        assert_eq!(items[1]["stats"]["count"], 2);
+        assert!(items[1]["stats"].get("files").is_none());
+        assert_eq!(items[2]["stats"]["orig"], 1000);
+        assert_eq!(items[2]["stats"]["comp"], output.chars().count());
+        assert_eq!(items[2]["stats"]["count"], 3);
+        assert_eq!(items[2]["stats"]["files"], 2);
+    }
+
+    #[test]
+    fn ndjson_writes_one_object_per_line_with_stats_last() {
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.write_error(&ToonifiedError::new("TypeError: foo", ErrorType::TypeError)).unwrap();
+        writer.write_error(&ToonifiedError::new("ReferenceError: bar", ErrorType::RefError)).unwrap();
+        let out = String::from_utf8(writer.finish(500).unwrap()).unwrap();
+
+        let lines: Vec<Value> = out.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "TYPE_ERROR");
+        assert_eq!(lines[1]["type"], "REF_ERROR");
+        assert_eq!(lines[2]["stats"]["orig"], 500);
+        assert_eq!(lines[2]["stats"]["count"], 2);
+    }
+
+    #[test]
+    fn ndjson_stats_written_even_without_errors() {
+        let writer = NdjsonWriter::new(Vec::new());
+        let out = String::from_utf8(writer.finish(10).unwrap()).unwrap();
+        assert_eq!(out, "{\"stats\":{\"orig\":10,\"comp\":66,\"comp_bytes\":66,\"pct\":0,\"count\":0}}\n");
+    }
+
+    #[test]
+    fn ndjson_stats_count_every_line_written() {
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.write_error(&ToonifiedError::new("TypeError: café → boom", ErrorType::TypeError)).unwrap();
+        let out = String::from_utf8(writer.finish(5000).unwrap()).unwrap();
+
+        let stats: Value = serde_json::from_str(out.lines().last().unwrap()).unwrap();
+        assert_eq!(stats["stats"]["comp"], out.chars().count());
+        assert_eq!(stats["stats"]["comp_bytes"], out.len());
+    }
+
+    #[test]
+    fn json_stats_match_output_size() {
+        let errors = vec![ToonifiedError::new("TypeError: foo\n    at a.tsx:1", ErrorType::TypeError)];
+        let out = format_json(&errors, 5000);
+        let value: Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(value[1]["stats"]["comp"], out.chars().count());
+        assert_eq!(value[1]["stats"]["comp_bytes"], out.len());
+        assert_eq!(value[1]["stats"]["pct"], (5000 - out.chars().count()) * 100 / 5000);
+    }
+}
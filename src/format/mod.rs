@@ -0,0 +1,18 @@
+//! Output formatters: plain text, TOON, JSON, and colored terminal boxes.
+//!
+//! Single errors render through methods on [`ToonifiedError`](crate::ToonifiedError);
+//! multiple errors render through the `format_multi_*` functions, which append
+//! aggregate stats for the whole input. Reported compressed sizes are exact
+//! ([`CompressionStats`]).
+
+pub mod colored;
+pub mod json;
+pub mod plain;
+pub mod stats;
+pub mod toon;
+
+pub use self::colored::{format_files_colored_for, format_multi_colored, format_multi_colored_for};
+pub use self::json::{format_files_json, format_json, NdjsonWriter};
+pub use self::plain::{format_files_plain, format_multi_plain};
+pub use self::stats::{CompressionStats, Original};
+pub use self::toon::{decode_toon, format_files_toon, format_multi_toon, format_multi_toon_with, DecodedToon};
@@ -0,0 +1,292 @@
+//! Plain text formatter.
+
+use crate::format::stats::{render_exact, token_summary, total, CompressionStats, Original};
+use crate::model::{FileErrors, ToonifiedError};
+
+impl ToonifiedError {
+    /// Render as plain `key: value` lines followed by a compression stats footer.
+    pub fn format_plain(&self) -> String {
+        self.format_plain_against(self.original_len)
+    }
+
+    /// Render as plain text with stats measured against `original`; token
+    /// counts are added to the footer when `original` carries them.
+    pub fn format_plain_against<'a>(&self, original: impl Into<Original<'a>>) -> String {
+        let content = self.plain_lines().join("\n");
+
+        render_exact(original, |stats| {
+            format!(
+                "{}\n\n---\ncompressed: {}c → {}c ({}% saved){}",
+                content,
+                stats.original_chars,
+                stats.compressed_chars,
+                stats.savings_pct(),
+                token_suffix(stats)
+            )
+        })
+    }
+
+    /// Plain `key: value` lines for this error, without the stats footer.
+    pub(crate) fn plain_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("type: {}", self.error_type.name())];
+
+        if !self.tags.is_empty() {
+            lines.push(format!("tags: {}", self.tag_names().join(", ")));
+        }
+
+        if let Some(confidence) = self.confidence {
+            lines.push(format!("confidence: {}", confidence));
+        }
+
+        if let Some(ref loc) = self.file_location {
+            lines.push(format!("file: {}", loc));
+        }
+
+        if let Some(ref issue) = self.issue {
+            lines.push(format!("issue: {}", issue));
+        }
+
+        for (key, value) in &self.details {
+            lines.push(format!("{}: {}", key, value));
+        }
+
+        if !self.frames.is_empty() {
+            lines.push("frames:".to_string());
+            for frame in &self.frames {
+                lines.push(format!("  {}", frame));
+            }
+        }
+
+        lines
+    }
+}
+
+/// Format multiple errors in plain format with separator between errors
+pub fn format_multi_plain<'a>(errors: &[ToonifiedError], original: impl Into<Original<'a>>) -> String {
+    let content = multi_plain_content(errors);
+
+    render_exact(original, |stats| {
+        format!(
+            "{}\n\n---\ncompressed: {}c → {}c ({}% saved, {} errors){}",
+            content,
+            stats.original_chars,
+            stats.compressed_chars,
+            stats.savings_pct(),
+            errors.len(),
+            token_suffix(stats)
+        )
+    })
+}
+
+/// Format errors from several files as `== name ==` sections, each with its own
+/// stats footer, followed by a footer with the totals.
+pub fn format_files_plain(files: &[FileErrors]) -> String {
+    let content = files.iter().map(plain_section).collect::<Vec<_>>().join("\n\n");
+    let count: usize = files.iter().map(|file| file.errors.len()).sum();
+
+    render_exact(total(files.iter().map(|file| file.original)), |stats| {
+        format!(
+            "{}\n\n===\ntotal: {}c → {}c ({}% saved, {} errors in {} files){}",
+            content,
+            stats.original_chars,
+            stats.compressed_chars,
+            stats.savings_pct(),
+            count,
+            files.len(),
+            token_suffix(stats)
+        )
+    })
+}
+
+/// One file's section of [`format_files_plain`]; its footer measures the section itself.
+pub(crate) fn plain_section(file: &FileErrors) -> String {
+    let content = multi_plain_content(&file.errors);
+    let count = match file.errors.len() {
+        1 => String::new(),
+        n => format!(", {} errors", n),
+    };
+
+    render_exact(file.original, |stats| {
+        format!(
+            "== {} ==\n{}\n\n---\ncompressed: {}c → {}c ({}% saved{}){}",
+            file.name,
+            content,
+            stats.original_chars,
+            stats.compressed_chars,
+            stats.savings_pct(),
+            count,
+            token_suffix(stats)
+        )
+    })
+}
+
+/// Error bodies separated by `===` lines.
+fn multi_plain_content(errors: &[ToonifiedError]) -> String {
+    let mut output_parts: Vec<String> = Vec::new();
+
+    for (i, error) in errors.iter().enumerate() {
+        let mut lines = error.plain_lines();
+
+        if i < errors.len() - 1 {
+            lines.push(String::new());
+        }
+
+        output_parts.push(lines.join("\n"));
+    }
+
+    output_parts.join("\n===\n")
+}
+
+/// ` · 1210 → 61 tokens (94% saved)` when tokens were counted, otherwise empty.
+fn token_suffix(stats: &CompressionStats) -> String {
+    token_summary(stats).map(|tokens| format!(" · {}", tokens)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detect::detect_error_type;
+    use crate::error_type::ErrorType;
+    use crate::tokenizer::Tokenizer;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Output Format Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn plain_format_includes_type() {
+        let input = "TypeError: test error";
+        let result = ToonifiedError::new(input, ErrorType::TypeError);
+        let output = result.format_plain();
+        assert!(output.contains("type: TYPE_ERROR"));
+    }
+
+    #[test]
+    fn plain_format_includes_tags_and_confidence() {
+        let input = "Unhandled Promise Rejection:\nTypeError: Failed to fetch";
+        let result = ToonifiedError::new(input, ErrorType::NetworkError);
+        let output = result.format_plain();
+        assert!(output.starts_with("type: NETWORK_ERROR\ntags: UNHANDLED_REJECTION, TYPE_ERROR\nconfidence: 0.25\n"));
+    }
+
+    #[test]
+    fn plain_format_omits_empty_tags() {
+        let result = ToonifiedError::new("TypeError: test error", ErrorType::TypeError);
+        let output = result.format_plain();
+        assert!(!output.contains("tags:"));
+        assert!(output.contains("confidence: 1\n"));
+    }
+
+    #[test]
+    fn plain_format_includes_file_when_present() {
+        let input = "Error at MyComponent.tsx:42";
+        let error_type = detect_error_type(input).unwrap_or(ErrorType::RuntimeError);
+        let result = ToonifiedError::new(input, error_type);
+        let output = result.format_plain();
+        assert!(output.contains("file: MyComponent.tsx:42"));
+    }
+
+    #[test]
+    fn plain_format_includes_compression_stats() {
+        let input = "TypeError: test error with some extra content to make it longer";
+        let result = ToonifiedError::new(input, ErrorType::TypeError);
+        let output = result.format_plain();
+        assert!(output.contains("compressed:"));
+        assert!(output.contains("saved)"));
+    }
+
+    #[test]
+    fn plain_stats_report_exact_output_length() {
+        let input = "TypeError: Cannot read properties of undefined (reading 'map')\n    at ProductList (src/ProductList.tsx:42:17)\n    at renderWithHooks (node_modules/react-dom/cjs/react-dom.development.js:14985:18)";
+        let output = ToonifiedError::new(input, ErrorType::TypeError).format_plain();
+        let expected = format!("→ {}c (", output.chars().count());
+        assert!(output.contains(&expected), "{}", output);
+        assert!(output.contains(&format!("compressed: {}c", input.chars().count())));
+    }
+
+    #[test]
+    fn multi_plain_stats_report_exact_output_length() {
+        let errors = vec![
+            ToonifiedError::new("TypeError: foo\n    at a.tsx:1", ErrorType::TypeError),
+            ToonifiedError::new("ReferenceError: bar", ErrorType::RefError),
+        ];
+        let output = format_multi_plain(&errors, 10_000);
+        let chars = output.chars().count();
+        assert!(output.ends_with(&format!("10000c → {}c ({}% saved, 2 errors)", chars, (10_000 - chars) * 100 / 10_000)));
+    }
+
+    #[test]
+    fn plain_footer_reports_tokens_when_counted() {
+        let input = "TypeError: foo is not a function\n    at App (src/App.tsx:12:5)";
+        let tokenizer = Tokenizer::with_merges(&[]);
+        let error = ToonifiedError::new(input, ErrorType::TypeError);
+
+        let output = error.format_plain_against(Original::measure(input, Some(&tokenizer)));
+        let tokens = format!(" · {} → {} tokens (", input.len(), output.len());
+        assert!(output.contains(&tokens), "{}", output);
+        assert!(!error.format_plain().contains("tokens"));
+    }
+
+    #[test]
+    fn files_plain_labels_sections_with_per_file_and_total_stats() {
+        let files = vec![
+            FileErrors {
+                name: "logs/app.log".to_string(),
+                errors: vec![ToonifiedError::new("TypeError: foo", ErrorType::TypeError)],
+                original: Original::from(3000),
+            },
+            FileErrors {
+                name: "build.txt".to_string(),
+                errors: vec![
+                    ToonifiedError::new("ReferenceError: bar", ErrorType::RefError),
+                    ToonifiedError::new("SyntaxError: baz", ErrorType::SyntaxError),
+                ],
+                original: Original::from(2000),
+            },
+        ];
+        let output = format_files_plain(&files);
+
+        let app = plain_section(&files[0]);
+        let build = plain_section(&files[1]);
+        assert!(app.starts_with("== logs/app.log ==
+type: TYPE_ERROR
+"));
+        assert!(app.ends_with(&format!("3000c → {}c (96% saved)", app.chars().count())), "{}", app);
+        assert!(build.ends_with(&format!("2000c → {}c (90% saved, 2 errors)", build.chars().count())), "{}", build);
+        assert!(output.starts_with(&format!("{}\n\n{}\n\n===\n", app, build)));
+        let total = format!("total: 5000c → {}c (", output.chars().count());
+        assert!(output.contains(&total) && output.ends_with(", 3 errors in 2 files)"), "{}", output);
+    }
+
+    #[test]
+    fn plain_format_lists_details_after_issue() {
+        let error = ToonifiedError::new("TypeError: boom", ErrorType::TypeError)
+            .with_detail("test", "Login › signs in")
+            .with_detail("retry", "1");
+        let output = error.format_plain();
+        assert!(output.contains("issue: TypeError: boom\ntest: Login › signs in\nretry: 1\n"), "{}", output);
+    }
+
+    #[test]
+    fn plain_format_omits_file_when_none() {
+        let input = "TypeError: test error";
+        let result = ToonifiedError::new(input, ErrorType::TypeError);
+        let output = result.format_plain();
+        assert!(!output.contains("file:"));
+    }
+
+    #[test]
+    fn multi_error_plain_format() {
+        let input1 = "TypeError: foo\n    at a.tsx:1";
+        let input2 = "ReferenceError: bar\n    at b.tsx:2";
+        let errors = vec![
+            ToonifiedError::new(input1, ErrorType::TypeError),
+            ToonifiedError::new(input2, ErrorType::RefError),
+        ];
+        let output = format_multi_plain(&errors, 100);
+        assert!(output.contains("type: TYPE_ERROR"));
+        assert!(output.contains("type: REF_ERROR"));
+        assert!(output.contains("==="));
+        assert!(output.contains("2 errors"));
+    }
+}
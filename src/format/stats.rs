@@ -0,0 +1,218 @@
+//! Exact compression statistics.
+//!
+//! Every formatter reports the size of the text it actually emits, stats line
+//! included. Because that size appears inside the text itself, [`render_exact`]
+//! re-renders until the numbers in the output match the output.
+
+use crate::tokenizer::Tokenizer;
+
+/// Size of the original input that stats compare against.
+#[derive(Clone, Copy)]
+pub struct Original<'a> {
+    /// Characters in the original input.
+    pub chars: usize,
+    /// Tokens in the original input and the tokenizer that counted them; when
+    /// set, stats also report compressed token counts.
+    pub tokens: Option<(usize, &'a Tokenizer)>,
+}
+
+impl<'a> Original<'a> {
+    /// Measure `text`, counting tokens too if a tokenizer is given.
+    pub fn measure(text: &str, tokenizer: Option<&'a Tokenizer>) -> Self {
+        Self {
+            chars: text.chars().count(),
+            tokens: tokenizer.map(|t| (t.count(text), t)),
+        }
+    }
+
+    fn tokenizer(&self) -> Option<&'a Tokenizer> {
+        self.tokens.map(|(_, t)| t)
+    }
+}
+
+/// Combined size of several inputs. Tokens are kept only if every input counted them.
+pub(crate) fn total<'a>(originals: impl IntoIterator<Item = Original<'a>>) -> Original<'a> {
+    let mut originals = originals.into_iter();
+    let Some(first) = originals.next() else {
+        return Original::from(0);
+    };
+    originals.fold(first, |sum, next| Original {
+        chars: sum.chars + next.chars,
+        tokens: sum.tokens.zip(next.tokens).map(|((a, tokenizer), (b, _))| (a + b, tokenizer)),
+    })
+}
+
+/// Character count only.
+impl From<usize> for Original<'_> {
+    fn from(chars: usize) -> Self {
+        Self { chars, tokens: None }
+    }
+}
+
+/// Sizes behind a "N% saved" figure. Original sizes are in characters, like the
+/// `orig` values in every output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompressionStats {
+    /// Characters in the original input.
+    pub original_chars: usize,
+    /// Characters in the emitted text, stats included.
+    pub compressed_chars: usize,
+    /// UTF-8 bytes in the emitted text, stats included.
+    pub compressed_bytes: usize,
+    /// Tokens in the original input, if a tokenizer was used.
+    pub original_tokens: Option<usize>,
+    /// Tokens in the emitted text, stats included, if a tokenizer was used.
+    pub compressed_tokens: Option<usize>,
+}
+
+impl CompressionStats {
+    /// Stats for `output`, a complete rendering of `original`.
+    pub fn measure<'a>(original: impl Into<Original<'a>>, output: &str) -> Self {
+        let original = original.into();
+        let mut stats = Self::empty(original);
+        stats.add_output(output, original.tokenizer());
+        stats
+    }
+
+    /// Percentage of characters saved, rounded down; 0 if the output is not smaller.
+    pub fn savings_pct(&self) -> usize {
+        savings(self.original_chars, self.compressed_chars)
+    }
+
+    /// Percentage of tokens saved, if tokens were counted.
+    pub fn token_savings_pct(&self) -> Option<usize> {
+        Some(savings(self.original_tokens?, self.compressed_tokens?))
+    }
+
+    /// `(original, compressed, pct)` token counts, if tokens were counted.
+    pub fn tokens(&self) -> Option<(usize, usize, usize)> {
+        Some((self.original_tokens?, self.compressed_tokens?, self.token_savings_pct()?))
+    }
+
+    fn empty(original: Original) -> Self {
+        Self {
+            original_chars: original.chars,
+            original_tokens: original.tokens.map(|(n, _)| n),
+            compressed_tokens: original.tokens.map(|_| 0),
+            ..Self::default()
+        }
+    }
+
+    /// Count `text` as emitted.
+    pub(crate) fn add_output(&mut self, text: &str, tokenizer: Option<&Tokenizer>) {
+        self.compressed_chars += text.chars().count();
+        self.compressed_bytes += text.len();
+        if let (Some(tokens), Some(tokenizer)) = (self.compressed_tokens.as_mut(), tokenizer) {
+            *tokens += tokenizer.count(text);
+        }
+    }
+}
+
+/// `1210 → 61 tokens (94% saved)`, if tokens were counted.
+pub(crate) fn token_summary(stats: &CompressionStats) -> Option<String> {
+    let (original, compressed, pct) = stats.tokens()?;
+    Some(format!("{} → {} tokens ({}% saved)", original, compressed, pct))
+}
+
+fn savings(original: usize, compressed: usize) -> usize {
+    if original > compressed {
+        ((original - compressed) * 100) / original
+    } else {
+        0
+    }
+}
+
+/// Size changes only move digit counts, so this settles within two or three rounds.
+const MAX_ROUNDS: usize = 8;
+
+/// Render text that reports its own size relative to `original`.
+pub(crate) fn render_exact<'a>(original: impl Into<Original<'a>>, render: impl Fn(&CompressionStats) -> String) -> String {
+    let original = original.into();
+    settle(CompressionStats::empty(original), original.tokenizer(), render)
+}
+
+/// Like [`render_exact`], for text written after output already counted in `emitted` (streaming).
+pub(crate) fn render_exact_after(emitted: CompressionStats, render: impl Fn(&CompressionStats) -> String) -> String {
+    settle(emitted, None, render)
+}
+
+fn settle(emitted: CompressionStats, tokenizer: Option<&Tokenizer>, render: impl Fn(&CompressionStats) -> String) -> String {
+    let mut stats = emitted;
+    for _ in 0..MAX_ROUNDS {
+        let text = render(&stats);
+        let mut measured = emitted;
+        measured.add_output(&text, tokenizer);
+        if measured == stats {
+            return text;
+        }
+        stats = measured;
+    }
+    render(&stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Tokenizer;
+
+    /// Every byte is its own token, so token counts equal byte counts.
+    fn byte_tokenizer() -> Tokenizer {
+        Tokenizer::with_merges(&[])
+    }
+
+    #[test]
+    fn measure_counts_chars_and_bytes() {
+        let stats = CompressionStats::measure(100, "→ ok");
+        assert_eq!(stats.compressed_chars, 4);
+        assert_eq!(stats.compressed_bytes, 6);
+        assert_eq!(stats.savings_pct(), 96);
+        assert_eq!(stats.tokens(), None);
+    }
+
+    #[test]
+    fn savings_are_zero_when_output_is_larger() {
+        assert_eq!(CompressionStats::measure(3, "abcd").savings_pct(), 0);
+        assert_eq!(CompressionStats::measure(0, "").savings_pct(), 0);
+    }
+
+    #[test]
+    fn measure_counts_tokens_with_tokenizer() {
+        let tokenizer = byte_tokenizer();
+        let original = Original::measure(&"x".repeat(200), Some(&tokenizer));
+        let stats = CompressionStats::measure(original, "→ ok");
+        assert_eq!(stats.tokens(), Some((200, 6, 97)));
+    }
+
+    #[test]
+    fn total_sums_chars_and_keeps_tokens_only_if_all_counted() {
+        let tokenizer = byte_tokenizer();
+        let counted = [Original::measure("abc", Some(&tokenizer)), Original::measure("de", Some(&tokenizer))];
+        let sum = total(counted);
+        assert_eq!((sum.chars, sum.tokens.map(|(n, _)| n)), (5, Some(5)));
+
+        let mixed = total([Original::measure("abc", Some(&tokenizer)), Original::from(2)]);
+        assert_eq!((mixed.chars, mixed.tokens.is_none()), (5, true));
+        assert_eq!(total([]).chars, 0);
+    }
+
+    #[test]
+    fn render_exact_reports_its_own_length() {
+        let text = render_exact(1000, |s| format!("body\nsize: {}", s.compressed_chars));
+        assert_eq!(text, format!("body\nsize: {}", text.chars().count()));
+    }
+
+    #[test]
+    fn render_exact_reports_its_own_token_count() {
+        let tokenizer = byte_tokenizer();
+        let original = Original { chars: 1000, tokens: Some((1000, &tokenizer)) };
+        let text = render_exact(original, |s| format!("tokens: {}", s.compressed_tokens.unwrap()));
+        assert_eq!(text, format!("tokens: {}", tokenizer.count(&text)));
+    }
+
+    #[test]
+    fn render_exact_after_includes_already_emitted_output() {
+        let emitted = CompressionStats::measure(1000, "first line\n");
+        let text = render_exact_after(emitted, |s| format!("size: {}\n", s.compressed_chars));
+        assert_eq!(text, format!("size: {}\n", 11 + text.chars().count()));
+    }
+}
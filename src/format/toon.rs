@@ -0,0 +1,601 @@
+//! TOON formatter (Token-Oriented Object Notation).
+
+use crate::error_type::ErrorType;
+use crate::format::stats::{render_exact, total, CompressionStats, Original};
+use crate::frame::parse_frame;
+use crate::model::{FileErrors, ToonifiedError};
+use crate::toon::{self, DecodeError, Delimiter, Value};
+
+impl ToonifiedError {
+    /// Render as TOON, with frames as a tabular array and stats as a nested object.
+    pub fn format_toon(&self) -> String {
+        self.format_toon_with(Delimiter::Comma)
+    }
+
+    /// Render as TOON using `delimiter` for the frames table.
+    pub fn format_toon_with(&self, delimiter: Delimiter) -> String {
+        self.format_toon_against(delimiter, self.original_len)
+    }
+
+    /// Render as TOON with stats measured against `original`, including a
+    /// `tokens` object when `original` carries token counts.
+    pub fn format_toon_against<'a>(&self, delimiter: Delimiter, original: impl Into<Original<'a>>) -> String {
+        let fields = self.toon_fields();
+
+        render_exact(original, |stats| {
+            let mut fields = fields.clone();
+            fields.push(field("stats", stats_object(stats, None)));
+            toon::encode(&Value::Object(fields), delimiter)
+        })
+    }
+
+    /// TOON fields for this error (type, file, issue, frames table), without stats.
+    pub(crate) fn toon_fields(&self) -> Vec<(String, Value)> {
+        let mut fields = vec![field("type", self.error_type.name().into())];
+
+        if !self.tags.is_empty() {
+            let tags = self.tag_names().into_iter().map(Value::from).collect();
+            fields.push(field("tags", Value::Array(tags)));
+        }
+
+        if let Some(confidence) = self.confidence {
+            fields.push(field("confidence", Value::Float(confidence)));
+        }
+
+        if let Some(ref loc) = self.file_location {
+            fields.push(field("file", loc.as_str().into()));
+        }
+
+        if let Some(ref issue) = self.issue {
+            fields.push(field("issue", issue.as_str().into()));
+        }
+
+        for (key, value) in &self.details {
+            fields.push(field(key, detail_value(value)));
+        }
+
+        // TOON tabular array format for frames
+        if !self.frames.is_empty() {
+            let rows = self.frames
+                .iter()
+                .map(|f| {
+                    let (func, loc) = parse_frame(f);
+                    Value::Object(vec![field("fn", func.into()), field("loc", loc.into())])
+                })
+                .collect();
+            fields.push(field("frames", Value::Array(rows)));
+        }
+
+        fields
+    }
+}
+
+/// Format multiple errors in TOON format
+pub fn format_multi_toon<'a>(errors: &[ToonifiedError], original: impl Into<Original<'a>>) -> String {
+    format_multi_toon_with(errors, original, Delimiter::Comma)
+}
+
+/// Format multiple errors in TOON format using `delimiter` for arrays and tables
+pub fn format_multi_toon_with<'a>(
+    errors: &[ToonifiedError],
+    original: impl Into<Original<'a>>,
+    delimiter: Delimiter,
+) -> String {
+    let items: Vec<Value> = errors.iter().map(|e| Value::Object(e.toon_fields())).collect();
+
+    render_exact(original, |stats| {
+        let fields = vec![
+            field("errors", Value::Array(items.clone())),
+            field("stats", stats_object(stats, Some(errors.len()))),
+        ];
+        toon::encode(&Value::Object(fields), delimiter)
+    })
+}
+
+/// Format errors from several files as a `files[N]:` list. Each item has the
+/// file's `source` name, its `errors`, and `stats` for those errors rendered on
+/// their own; the top-level `stats` cover the whole document.
+pub fn format_files_toon(files: &[FileErrors], delimiter: Delimiter) -> String {
+    let items: Vec<Value> = files
+        .iter()
+        .map(|file| {
+            let standalone = format_multi_toon_with(&file.errors, file.original, delimiter);
+            let stats = CompressionStats::measure(file.original, &standalone);
+            let errors = file.errors.iter().map(|e| Value::Object(e.toon_fields())).collect();
+            Value::Object(vec![
+                field("source", file.name.as_str().into()),
+                field("errors", Value::Array(errors)),
+                field("stats", stats_object(&stats, Some(file.errors.len()))),
+            ])
+        })
+        .collect();
+    let count = files.iter().map(|file| file.errors.len()).sum();
+
+    render_exact(total(files.iter().map(|file| file.original)), |stats| {
+        let mut totals = stats_fields(stats, Some(count));
+        totals.push(field("files", files.len().into()));
+        let fields = vec![field("files", Value::Array(items.clone())), field("stats", Value::Object(totals))];
+        toon::encode(&Value::Object(fields), delimiter)
+    })
+}
+
+/// `stats:` object with `orig`, `comp`, `pct`, for multi-error output `count`,
+/// and a nested `tokens:` object when tokens were counted.
+fn stats_object(stats: &CompressionStats, count: Option<usize>) -> Value {
+    Value::Object(stats_fields(stats, count))
+}
+
+fn stats_fields(stats: &CompressionStats, count: Option<usize>) -> Vec<(String, Value)> {
+    let mut fields = vec![
+        field("orig", stats.original_chars.into()),
+        field("comp", stats.compressed_chars.into()),
+        field("pct", stats.savings_pct().into()),
+    ];
+    if let Some(count) = count {
+        fields.push(field("count", count.into()));
+    }
+    if let Some((orig, comp, pct)) = stats.tokens() {
+        fields.push(field("tokens", Value::Object(vec![
+            field("orig", orig.into()),
+            field("comp", comp.into()),
+            field("pct", pct.into()),
+        ])));
+    }
+    fields
+}
+
+/// Integer-looking details (`retry: 1`) stay numbers; everything else is a string.
+fn detail_value(value: &str) -> Value {
+    match value.parse::<i64>() {
+        Ok(n) if n.to_string() == value => Value::Int(n),
+        _ => value.into(),
+    }
+}
+
+/// Fields every error may have; any other scalar field of an error is a detail.
+const ERROR_FIELDS: &[&str] = &["type", "tags", "confidence", "file", "issue", "frames", "stats"];
+
+fn field(key: &str, value: Value) -> (String, Value) {
+    (key.to_string(), value)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// TOON Reader
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors read back from `format_toon`/`format_multi_toon` output.
+#[derive(Debug, Clone)]
+pub struct DecodedToon {
+    /// Decoded errors. Single-error documents carry `original_len` (characters)
+    /// from their stats; errors in a multi-error document have `original_len` 0.
+    pub errors: Vec<ToonifiedError>,
+    /// `orig` from the `stats` object, if present.
+    pub original_len: Option<usize>,
+}
+
+/// Parse TOON output (current or error-toon 1.2 layout) back into errors.
+/// Multi-file documents decode to the errors of every file, in order.
+///
+/// Frames decode to `at fn (loc)` strings so they re-render like extracted frames.
+pub fn decode_toon(input: &str) -> Result<DecodedToon, DecodeError> {
+    // error-toon <= 1.2 escaped commas as `\,` and separated errors with `---`/`===`
+    let legacy = input.lines().any(|l| l.starts_with("stats{"));
+    if legacy && input.lines().any(|l| l == "---") {
+        return decode_legacy_multi(input);
+    }
+
+    let Value::Object(fields) = toon::decode(input)? else {
+        return Err(DecodeError::document("expected a TOON object"));
+    };
+    let original_len = stats_orig(&fields);
+
+    let errors = match (get(&fields, "files"), get(&fields, "errors")) {
+        (Some(Value::Array(files)), _) => {
+            let mut errors = Vec::new();
+            for file in files {
+                match file {
+                    Value::Object(fields) => errors.extend(errors_from_list(get(fields, "errors"), legacy)?),
+                    _ => return Err(DecodeError::document("files[] items must be objects")),
+                }
+            }
+            errors
+        }
+        (Some(_), _) => return Err(DecodeError::document("files must be an array")),
+        (None, Some(list)) => errors_from_list(Some(list), legacy)?,
+        (None, None) => {
+            let mut error = error_from_fields(&fields, legacy)?;
+            error.original_len = original_len.unwrap_or_default();
+            vec![error]
+        }
+    };
+
+    Ok(DecodedToon { errors, original_len })
+}
+
+/// Errors from an `errors[N]:` list; a missing list has no errors.
+fn errors_from_list(list: Option<&Value>, legacy: bool) -> Result<Vec<ToonifiedError>, DecodeError> {
+    match list {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::Object(fields) => error_from_fields(fields, legacy),
+                _ => Err(DecodeError::document("errors[] items must be objects")),
+            })
+            .collect(),
+        Some(_) => Err(DecodeError::document("errors must be an array")),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Old multi-error layout: `errors[N]:`, then `---` before each error, then `===` and a stats line.
+fn decode_legacy_multi(input: &str) -> Result<DecodedToon, DecodeError> {
+    let mut chunks: Vec<Vec<&str>> = vec![Vec::new()];
+    let mut stats = String::new();
+    let mut in_stats = false;
+
+    for line in input.lines() {
+        match line {
+            "---" => chunks.push(Vec::new()),
+            "===" => in_stats = true,
+            _ if in_stats => stats.push_str(line),
+            _ => chunks.last_mut().expect("chunks is never empty").push(line),
+        }
+    }
+
+    let errors = chunks[1..]
+        .iter()
+        .map(|chunk| match toon::decode(&chunk.join("\n"))? {
+            Value::Object(fields) => error_from_fields(&fields, true),
+            _ => Err(DecodeError::document("expected an error object")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let original_len = match toon::decode(&stats)? {
+        Value::Object(fields) => stats_orig(&fields),
+        _ => None,
+    };
+
+    Ok(DecodedToon { errors, original_len })
+}
+
+fn error_from_fields(fields: &[(String, Value)], legacy: bool) -> Result<ToonifiedError, DecodeError> {
+    let name = match get(fields, "type") {
+        Some(Value::Str(name)) => name,
+        _ => return Err(DecodeError::document("error is missing a `type` field")),
+    };
+    let error_type = ErrorType::from_name(name)
+        .ok_or_else(|| DecodeError::document(format!("unknown error type `{}`", name)))?;
+
+    let issue = text_field(fields, "issue").map(|issue| if legacy { issue.replace("\\,", ",") } else { issue });
+
+    let tags = match get(fields, "tags") {
+        Some(Value::Array(names)) => names
+            .iter()
+            .map(|name| {
+                let name = scalar_text(name).unwrap_or_default();
+                ErrorType::from_name(&name)
+                    .ok_or_else(|| DecodeError::document(format!("unknown error type `{}` in tags", name)))
+            })
+            .collect::<Result<_, _>>()?,
+        _ => Vec::new(),
+    };
+
+    let confidence = match get(fields, "confidence") {
+        Some(Value::Float(f)) => Some(*f),
+        Some(Value::Int(n)) => Some(*n as f64),
+        _ => None,
+    };
+
+    let frames = match get(fields, "frames") {
+        Some(Value::Array(rows)) => rows.iter().map(frame_from_row).collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(ToonifiedError {
+        error_type,
+        tags,
+        confidence,
+        file_location: text_field(fields, "file"),
+        issue,
+        details: fields
+            .iter()
+            .filter(|(key, _)| !ERROR_FIELDS.contains(&key.as_str()))
+            .filter_map(|(key, value)| Some((key.clone(), scalar_text(value)?)))
+            .collect(),
+        frames,
+        original_len: 0,
+    })
+}
+
+fn frame_from_row(row: &Value) -> String {
+    match row {
+        Value::Object(cells) => {
+            let func = text_field(cells, "fn").unwrap_or_default();
+            match text_field(cells, "loc").filter(|loc| !loc.is_empty()) {
+                Some(loc) => format!("at {} ({})", func, loc),
+                // `parse_frame` leaves unparseable frames whole in `fn`
+                None => func,
+            }
+        }
+        other => scalar_text(other).unwrap_or_default(),
+    }
+}
+
+fn stats_orig(fields: &[(String, Value)]) -> Option<usize> {
+    match get(fields, "stats") {
+        Some(Value::Object(stats)) => match get(stats, "orig") {
+            Some(Value::Int(n)) => usize::try_from(*n).ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn get<'a>(fields: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// A field's value as text; numbers and booleans decoded from unquoted TOON become strings again.
+fn text_field(fields: &[(String, Value)], key: &str) -> Option<String> {
+    get(fields, key).and_then(scalar_text)
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Str(s) => Some(s.clone()),
+        Value::Int(n) => Some(n.to_string()),
+        Value::Float(f) => Some(f.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+    use crate::tokenizer::Tokenizer;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // TOON Format Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn toon_format_includes_type() {
+        let result = ToonifiedError::new("TypeError: foo is not a function", ErrorType::TypeError);
+        let output = result.format_toon();
+        assert!(output.contains("type: TYPE_ERROR"));
+    }
+
+    #[test]
+    fn toon_format_includes_stats_object() {
+        let result = ToonifiedError::new("TypeError: test", ErrorType::TypeError);
+        let output = result.format_toon();
+        assert!(output.contains("\nstats:\n  orig: 15\n  comp: "));
+        assert!(output.contains("\n  pct: "));
+    }
+
+    #[test]
+    fn toon_stats_report_exact_output_length() {
+        let result = ToonifiedError::new("TypeError: foo is not a function\n    at App (src/App.tsx:12:5)", ErrorType::TypeError);
+        for delimiter in [Delimiter::Comma, Delimiter::Tab] {
+            let output = result.format_toon_with(delimiter);
+            assert!(output.contains(&format!("\n  comp: {}\n", output.chars().count())), "{}", output);
+        }
+
+        let output = format_multi_toon(&[result.clone(), result], 9000);
+        assert!(output.contains(&format!("\n  comp: {}\n", output.chars().count())), "{}", output);
+    }
+
+    #[test]
+    fn toon_stats_include_tokens_when_counted() {
+        let input = "TypeError: foo is not a function";
+        let tokenizer = Tokenizer::with_merges(&[]);
+        let result = ToonifiedError::new(input, ErrorType::TypeError);
+
+        let output = result.format_toon_against(Delimiter::Comma, Original::measure(input, Some(&tokenizer)));
+        let tokens = format!("\n  tokens:\n    orig: {}\n    comp: {}\n    pct: 0", input.len(), output.len());
+        assert!(output.ends_with(&tokens), "{}", output);
+        assert!(!result.format_toon().contains("tokens:"));
+    }
+
+    #[test]
+    fn toon_format_frames_use_tabular_syntax() {
+        let input = "Error: test\n    at FunctionA (file.tsx:10:5)\n    at FunctionB (other.tsx:20:3)";
+        let result = ToonifiedError::new(input, ErrorType::RuntimeError);
+        let output = result.format_toon();
+        // Should have tabular array declaration
+        assert!(output.contains("frames[") && output.contains("]{fn,loc}:"));
+    }
+
+    #[test]
+    fn toon_format_no_separator_line() {
+        let result = ToonifiedError::new("TypeError: test", ErrorType::TypeError);
+        let output = result.format_toon();
+        // TOON format should NOT have the "---" separator
+        assert!(!output.contains("\n---\n"));
+    }
+
+    #[test]
+    fn toon_format_quotes_issue_instead_of_escaping_commas() {
+        let input = "TypeError: foo, bar, baz are undefined";
+        let result = ToonifiedError::new(input, ErrorType::TypeError);
+        let output = result.format_toon();
+        assert!(output.contains("issue: \"TypeError: foo, bar, baz are undefined\""));
+        assert!(!output.contains("\\,"));
+    }
+
+    #[test]
+    fn toon_format_quotes_frame_cells_with_colons() {
+        let input = "Error: test\n    at FunctionA (http://localhost:3000/src/file.tsx:10:5)";
+        let result = ToonifiedError::new(input, ErrorType::RuntimeError);
+        let output = result.format_toon();
+        assert!(output.contains("file: \"file.tsx:10\""));
+        assert!(output.contains("frames[1]{fn,loc}:\n  FunctionA,\"file.tsx:10\""));
+    }
+
+    #[test]
+    fn toon_format_with_pipe_delimiter() {
+        let input = "Error: test\n    at FunctionA (file.tsx:10:5)\n    at FunctionB (other.tsx:20:3)";
+        let result = ToonifiedError::new(input, ErrorType::RuntimeError);
+        let output = result.format_toon_with(Delimiter::Pipe);
+        assert!(output.contains("frames[2|]{fn|loc}:\n  FunctionA|\"file.tsx:10\"\n  FunctionB|\"other.tsx:20\""));
+    }
+
+    #[test]
+    fn toon_format_is_more_compact_than_plain() {
+        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.\n    at p\n    at MDXContent (http://localhost:6006/Guide.mdx:79:10)\n    at Component (http://localhost:6006/App.tsx:18:5)";
+        let result = ToonifiedError::new(input, ErrorType::DomNesting);
+        let toon = result.format_toon();
+        let plain = result.format_plain();
+        // TOON format should generally be similar or smaller
+        // (may vary based on frame parsing overhead)
+        assert!(toon.len() <= plain.len() + 50, "TOON: {} chars, Plain: {} chars", toon.len(), plain.len());
+    }
+
+    #[test]
+    fn multi_error_toon_format() {
+        let input1 = "TypeError: foo\n    at a.tsx:1";
+        let input2 = "ReferenceError: bar\n    at b.tsx:2";
+        let errors = vec![
+            ToonifiedError::new(input1, ErrorType::TypeError),
+            ToonifiedError::new(input2, ErrorType::RefError),
+        ];
+        let output = format_multi_toon(&errors, 100);
+        assert!(output.contains("errors[2]:"));
+        assert!(output.contains("  - type: TYPE_ERROR"));
+        assert!(output.contains("  - type: REF_ERROR"));
+        assert!(output.contains("\nstats:\n  orig: 100\n"));
+        assert!(output.contains("\n  count: 2"));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // TOON Reader Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn decode_toon_round_trips_single_error() {
+        let input = "TypeError: foo, bar\n    at App (http://localhost:3000/src/App.tsx:12:5)";
+        let original = ToonifiedError::new(input, ErrorType::TypeError);
+        let decoded = decode_toon(&original.format_toon()).unwrap();
+
+        assert_eq!(decoded.original_len, Some(input.len()));
+        let error = &decoded.errors[0];
+        assert_eq!(error.error_type, ErrorType::TypeError);
+        assert_eq!(error.file_location.as_deref(), Some("App.tsx:12"));
+        assert_eq!(error.issue.as_deref(), Some("TypeError: foo, bar"));
+        assert_eq!(error.frames, vec!["at App (App.tsx:12)".to_string()]);
+        assert_eq!(error.original_len, input.len());
+    }
+
+    #[test]
+    fn decode_toon_round_trips_multi_error_with_any_delimiter() {
+        let errors = vec![
+            ToonifiedError::new("TypeError: foo\n    at A (a.tsx:1:1)", ErrorType::TypeError),
+            ToonifiedError::new("ReferenceError: bar|baz", ErrorType::RefError),
+        ];
+        for delimiter in [Delimiter::Comma, Delimiter::Tab, Delimiter::Pipe] {
+            let decoded = decode_toon(&format_multi_toon_with(&errors, 500, delimiter)).unwrap();
+            assert_eq!(decoded.original_len, Some(500));
+            assert_eq!(decoded.errors.len(), 2);
+            assert_eq!(decoded.errors[0].frames, vec!["at A (a.tsx:1)".to_string()]);
+            assert_eq!(decoded.errors[1].error_type, ErrorType::RefError);
+            assert_eq!(decoded.errors[1].issue.as_deref(), Some("ReferenceError: bar|baz"));
+        }
+    }
+
+    #[test]
+    fn files_toon_lists_sources_and_round_trips() {
+        let files = vec![
+            FileErrors {
+                name: "a.log".to_string(),
+                errors: vec![ToonifiedError::new("TypeError: foo\n    at A (a.tsx:1:1)", ErrorType::TypeError)],
+                original: Original::from(800),
+            },
+            FileErrors {
+                name: "logs/b, c.log".to_string(),
+                errors: vec![ToonifiedError::new("ReferenceError: bar", ErrorType::RefError)],
+                original: Original::from(700),
+            },
+        ];
+        let output = format_files_toon(&files, Delimiter::Comma);
+
+        assert!(output.starts_with("files[2]:\n  - source: a.log\n    errors[1]:\n      - type: TYPE_ERROR\n"));
+        assert!(output.contains("\n  - source: \"logs/b, c.log\"\n"));
+        let standalone = format_multi_toon(&files[0].errors, 800).chars().count();
+        assert!(output.contains(&format!("\n    stats:\n      orig: 800\n      comp: {}\n", standalone)));
+        let totals = format!("\nstats:\n  orig: 1500\n  comp: {}\n", output.chars().count());
+        assert!(output.contains(&totals) && output.ends_with("\n  count: 2\n  files: 2"), "{}", output);
+
+        let decoded = decode_toon(&output).unwrap();
+        assert_eq!(decoded.original_len, Some(1500));
+        assert_eq!(decoded.errors.len(), 2);
+        assert_eq!(decoded.errors[1].error_type, ErrorType::RefError);
+    }
+
+    #[test]
+    fn decode_toon_round_trips_details() {
+        let error = ToonifiedError::new("TypeError: boom", ErrorType::TypeError)
+            .with_detail("test", "Login › signs in")
+            .with_detail("retry", "1");
+        let output = error.format_toon();
+        assert!(output.contains("\ntest: Login › signs in\nretry: 1\n"), "{}", output);
+
+        let decoded = decode_toon(&output).unwrap();
+        assert_eq!(decoded.errors[0].details, error.details);
+    }
+
+    #[test]
+    fn toon_format_includes_tags_and_confidence() {
+        let input = "Unhandled Promise Rejection:\nTypeError: Failed to fetch";
+        let result = ToonifiedError::new(input, ErrorType::NetworkError);
+        let output = result.format_toon();
+        assert!(output.contains("tags[2]: UNHANDLED_REJECTION,TYPE_ERROR\nconfidence: 0.25"));
+    }
+
+    #[test]
+    fn decode_toon_round_trips_tags_and_confidence() {
+        let input = "Unhandled Promise Rejection:\nTypeError: Failed to fetch";
+        let original = ToonifiedError::new(input, ErrorType::NetworkError);
+        let decoded = decode_toon(&original.format_toon()).unwrap();
+        assert_eq!(decoded.errors[0].tags, original.tags);
+        assert_eq!(decoded.errors[0].confidence, Some(0.25));
+    }
+
+    #[test]
+    fn decode_toon_keeps_unparsed_frames_whole() {
+        let original = ToonifiedError::new("TypeError: foo\n    at tests/login.spec.ts:12:5", ErrorType::TypeError);
+        let decoded = decode_toon(&original.format_toon()).unwrap();
+        assert_eq!(decoded.errors[0].frames, vec!["at tests/login.spec.ts:12:5".to_string()]);
+    }
+
+    #[test]
+    fn decode_toon_reads_legacy_single_error() {
+        let input = "type: DOM_NESTING\nfile: Guide.mdx:79\nissue: a\\, b\nframes[1]{fn,loc}:\n  MDXContent,Guide.mdx:79\nstats{orig,comp,pct}: 4521,198,95";
+        let decoded = decode_toon(input).unwrap();
+        let error = &decoded.errors[0];
+        assert_eq!(error.error_type, ErrorType::DomNesting);
+        assert_eq!(error.file_location.as_deref(), Some("Guide.mdx:79"));
+        assert_eq!(error.issue.as_deref(), Some("a, b"));
+        assert_eq!(error.frames, vec!["at MDXContent (Guide.mdx:79)".to_string()]);
+        assert_eq!(error.original_len, 4521);
+        assert!(error.tags.is_empty());
+        assert_eq!(error.confidence, None);
+    }
+
+    #[test]
+    fn decode_toon_reads_legacy_multi_error() {
+        let input = "errors[2]:\n---\ntype: REACT_MINIFIED\nissue: Minified React error #130\n---\ntype: REACT_KEY\nfile: bundle.js:1234\nframes[1]{fn,loc}:\n  ProductItem,bundle.js:1234\n===\nstats{orig,comp,pct,count}: 2499,650,74,2";
+        let decoded = decode_toon(input).unwrap();
+        assert_eq!(decoded.original_len, Some(2499));
+        assert_eq!(decoded.errors.len(), 2);
+        assert_eq!(decoded.errors[0].error_type, ErrorType::ReactMinified);
+        assert_eq!(decoded.errors[1].frames, vec!["at ProductItem (bundle.js:1234)".to_string()]);
+    }
+
+    #[test]
+    fn decode_toon_rejects_unknown_type() {
+        let err = decode_toon("type: NOT_A_TYPE").unwrap_err();
+        assert!(err.to_string().contains("NOT_A_TYPE"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Stack frame parsing shared by the formatters.
+
+use crate::patterns::PATTERNS;
+
+/// Parse a stack frame string into (function_name, location)
+pub fn parse_frame(frame: &str) -> (String, String) {
+    // Common patterns:
+    // "at FunctionName (file.tsx:42:10)"
+    // "at FunctionName @ file.tsx:42"
+    // "@ FunctionName (file.tsx:42)"
+    // "FunctionName@file.tsx:42"
+
+    let frame = frame.trim();
+
+    // Try "at Name (loc)" pattern
+    if let Some(captures) = PATTERNS.frame_at_name_loc.captures(frame) {
+        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
+        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+        return (func.to_string(), simplify_location(loc));
+    }
+
+    // Try "@ Name (loc)" pattern
+    if let Some(captures) = PATTERNS.frame_at_symbol_loc.captures(frame) {
+        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
+        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+        return (func.to_string(), simplify_location(loc));
+    }
+
+    // Try "Name @ loc" or "Name@loc" pattern
+    if let Some(captures) = PATTERNS.frame_name_at_loc.captures(frame) {
+        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
+        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+        return (func.to_string(), simplify_location(loc));
+    }
+
+    // Fallback: return as-is
+    (frame.to_string(), String::new())
+}
+
+/// Simplify a location path (extract filename:line from full URL/path)
+pub fn simplify_location(loc: &str) -> String {
+    // Extract just filename:line from paths like:
+    // "http://localhost:6006/path/to/file.tsx:42:10" -> "file.tsx:42"
+    // "/absolute/path/to/file.tsx:42:10" -> "file.tsx:42"
+
+    let loc = loc.trim();
+
+    // Try to extract filename:line:col or filename:line
+    if let Some(captures) = PATTERNS.location_file_line.captures(loc) {
+        let file = captures.get(1).map(|m| m.as_str()).unwrap_or(loc);
+        let line = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+        return format!("{}:{}", file, line);
+    }
+
+    loc.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_frame_handles_at_pattern() {
+        let (func, loc) = parse_frame("at MyComponent (http://localhost:3000/src/App.tsx:42:10)");
+        assert_eq!(func, "MyComponent");
+        assert_eq!(loc, "App.tsx:42");
+    }
+
+    #[test]
+    fn parse_frame_handles_at_symbol_pattern() {
+        let (func, loc) = parse_frame("@ MDXContent (Guide.mdx:79:5)");
+        assert_eq!(func, "MDXContent");
+        assert_eq!(loc, "Guide.mdx:79");
+    }
+
+    #[test]
+    fn simplify_location_extracts_filename_and_line() {
+        let loc = simplify_location("http://localhost:6006/node_modules/.cache/App.tsx:42:10");
+        assert_eq!(loc, "App.tsx:42");
+    }
+
+    #[test]
+    fn simplify_location_handles_simple_path() {
+        let loc = simplify_location("/Users/dev/project/src/Component.tsx:100:5");
+        assert_eq!(loc, "Component.tsx:100");
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Frame Parsing Tests (Pre-compiled Regex)
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn parse_frame_webpack_path() {
+        let (func, loc) = parse_frame("at render (webpack://my-app/src/Component.tsx:42:10)");
+        assert_eq!(func, "render");
+        assert_eq!(loc, "Component.tsx:42");
+    }
+
+    #[test]
+    fn parse_frame_vite_path() {
+        let (func, loc) = parse_frame("at onClick (http://localhost:5173/src/App.tsx?t=123:15:3)");
+        assert_eq!(func, "onClick");
+        // Should extract the file and line, ignoring query params in some cases
+        assert!(loc.contains("App.tsx") || loc.contains("15"));
+    }
+
+    #[test]
+    fn parse_frame_name_at_symbol_format() {
+        let (func, loc) = parse_frame("MyFunction@/path/to/file.js:100:5");
+        assert_eq!(func, "MyFunction");
+        assert_eq!(loc, "file.js:100");
+    }
+
+    #[test]
+    fn parse_frame_keeps_dotted_and_anonymous_names() {
+        assert_eq!(parse_frame("at Object.fetchUser (http://localhost:3000/src/users.mjs:7:3)").0, "Object.fetchUser");
+        assert_eq!(parse_frame("at <anonymous> (app.js:10:1)"), ("<anonymous>".to_string(), "app.js:10".to_string()));
+        assert_eq!(parse_frame("at async loadUsers (src/api.ts:42:5)").0, "loadUsers");
+    }
+
+    #[test]
+    fn parse_frame_anonymous_function() {
+        let (func, loc) = parse_frame("at anonymous (app.js:10:1)");
+        assert_eq!(func, "anonymous");
+        assert_eq!(loc, "app.js:10");
+    }
+
+    #[test]
+    fn parse_frame_no_match_returns_original() {
+        let (func, loc) = parse_frame("some random text without pattern");
+        assert_eq!(func, "some random text without pattern");
+        assert_eq!(loc, "");
+    }
+
+    #[test]
+    fn parse_frame_empty_string() {
+        let (func, loc) = parse_frame("");
+        assert_eq!(func, "");
+        assert_eq!(loc, "");
+    }
+
+    #[test]
+    fn parse_frame_whitespace_only() {
+        let (func, loc) = parse_frame("   ");
+        assert_eq!(func, "");
+        assert_eq!(loc, "");
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Location Simplification Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn simplify_location_webpack_chunk() {
+        let loc = simplify_location("webpack://app/./src/components/Button.tsx:25:8");
+        assert_eq!(loc, "Button.tsx:25");
+    }
+
+    #[test]
+    fn simplify_location_with_query_string() {
+        let loc = simplify_location("http://localhost:3000/src/App.tsx?v=123:42:10");
+        // May or may not handle query strings perfectly, but shouldn't panic
+        assert!(loc.contains("42") || loc.contains("App"));
+    }
+
+    #[test]
+    fn simplify_location_windows_path() {
+        // Windows paths use backslashes, but the regex looks for forward slashes
+        // So it returns the full path since pattern doesn't match cleanly
+        let loc = simplify_location("C:\\Users\\dev\\project\\src\\App.tsx:50:1");
+        // The regex captures from the last / or start, so with backslashes it gets more
+        assert!(loc.contains("App.tsx") && loc.contains("50"));
+    }
+
+    #[test]
+    fn simplify_location_no_column() {
+        let loc = simplify_location("/path/to/file.js:100");
+        assert_eq!(loc, "file.js:100");
+    }
+
+    #[test]
+    fn simplify_location_no_line_number() {
+        let loc = simplify_location("/path/to/file.js");
+        // Should return as-is since pattern doesn't match
+        assert_eq!(loc, "/path/to/file.js");
+    }
+
+    #[test]
+    fn simplify_location_empty_string() {
+        let loc = simplify_location("");
+        assert_eq!(loc, "");
+    }
+
+    #[test]
+    fn simplify_location_various_extensions() {
+        assert_eq!(simplify_location("/a/b.tsx:1:1"), "b.tsx:1");
+        assert_eq!(simplify_location("/a/b.jsx:2:2"), "b.jsx:2");
+        assert_eq!(simplify_location("/a/b.ts:3:3"), "b.ts:3");
+        assert_eq!(simplify_location("/a/b.js:4:4"), "b.js:4");
+        assert_eq!(simplify_location("/a/b.vue:5:5"), "b.vue:5");
+        assert_eq!(simplify_location("/a/b.svelte:6:6"), "b.svelte:6");
+    }
+}
@@ -0,0 +1,228 @@
+//! File name patterns for positional input.
+//!
+//! Shells usually expand `logs/*.log` before error-toon sees it; this covers
+//! quoted patterns and shells that don't (Windows `cmd`). Supports `*`, `?`,
+//! `[abc]`/`[!abc]` within a path component and `**` for any number of directories.
+
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Whether `pattern` contains glob syntax.
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Files matching `pattern`, sorted. A pattern without glob syntax, or one that
+/// names an existing file (`build[1].log`), is returned as-is, whether or not it
+/// exists, so the caller reports a missing file. Fails with
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) for a malformed pattern such as `[z-a]`.
+pub fn expand_glob(pattern: &str) -> io::Result<Vec<PathBuf>> {
+    if !is_glob(pattern) || Path::new(pattern).exists() {
+        return Ok(vec![PathBuf::from(pattern)]);
+    }
+
+    let mut paths = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        paths = match component {
+            Component::Normal(_) if part == "**" => paths.iter().flat_map(|dir| subdirectories(dir)).collect(),
+            Component::Normal(_) if is_glob(&part) => {
+                let matcher = component_regex(&part).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("invalid character class in {}", part))
+                })?;
+                let mut matched = Vec::new();
+                for dir in &paths {
+                    matched.extend(matching_entries(dir, &matcher, part.starts_with('.')));
+                }
+                matched
+            }
+            _ => paths.into_iter().map(|dir| dir.join(component)).collect(),
+        };
+    }
+
+    let mut files: Vec<PathBuf> = paths.into_iter().filter(|path| path.is_file()).collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// `dir` itself plus every directory below it, skipping hidden ones. Symlinked
+/// directories aren't descended into (like bash's `globstar`), so a link cycle
+/// can't expand forever.
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let mut found = vec![dir.to_path_buf()];
+    let mut i = 0;
+    while i < found.len() {
+        let children: Vec<PathBuf> = entries(&found[i])
+            .filter(|(name, path)| !name.starts_with('.') && fs::symlink_metadata(path).is_ok_and(|m| m.is_dir()))
+            .map(|(_, path)| path)
+            .collect();
+        found.extend(children);
+        i += 1;
+    }
+    found
+}
+
+/// Entries of `dir` whose name matches; hidden entries only if the pattern starts with `.`.
+fn matching_entries(dir: &Path, matcher: &Regex, include_hidden: bool) -> Vec<PathBuf> {
+    entries(dir)
+        .filter(|(name, _)| (include_hidden || !name.starts_with('.')) && matcher.is_match(name))
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// `(name, path)` of every readable entry in `dir`; the empty path means the current directory.
+fn entries(dir: &Path) -> impl Iterator<Item = (String, PathBuf)> + '_ {
+    let listing = if dir.as_os_str().is_empty() { fs::read_dir(".") } else { fs::read_dir(dir) };
+    listing.into_iter().flatten().flatten().map(move |entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = dir.join(&name);
+        (name, path)
+    })
+}
+
+/// Anchored regex for one path component of a glob.
+fn component_regex(part: &str) -> Result<Regex, regex::Error> {
+    let mut regex = String::from("^");
+    let mut chars = part.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                let mut class = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    // A `]` first in the class (after any `!`) is a member, not the end
+                    if c == ']' && !class.is_empty() && class != "!" {
+                        closed = true;
+                        break;
+                    }
+                    class.push(c);
+                }
+                if closed {
+                    let negated = class.starts_with('!');
+                    let body = if negated { &class[1..] } else { &class[..] };
+                    regex.push('[');
+                    if negated {
+                        regex.push('^');
+                    }
+                    regex.push_str(&class_body(body));
+                    regex.push(']');
+                } else {
+                    regex.push_str(&regex::escape(&format!("[{}", class)));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex)
+}
+
+/// Members of a `[...]` class as a regex class body: every character escaped
+/// except a `-` between two others, so only `a-z` ranges keep their meaning and
+/// regex class operators (`&&`, `--`, `~~`) are plain characters.
+fn class_body(body: &str) -> String {
+    let chars: Vec<char> = body.chars().collect();
+    let mut escaped = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let range = c == '-' && i > 0 && i + 1 < chars.len() && chars[i - 1] != '-' && chars[i + 1] != '-';
+        if range {
+            escaped.push('-');
+        } else {
+            escaped.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        component_regex(pattern).unwrap().is_match(name)
+    }
+
+    /// Fresh directory tree under the system temp dir.
+    fn tree(name: &str, files: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("error-toon-glob-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn component_wildcards() {
+        assert!(matches("*.log", "app.log"));
+        assert!(!matches("*.log", "app.log.1"));
+        assert!(matches("app-?.txt", "app-1.txt"));
+        assert!(matches("[ab]*.log", "b.log"));
+        assert!(!matches("[!ab]*.log", "b.log"));
+        assert!(matches("a+b(1).log", "a+b(1).log"));
+        assert!(matches("[x", "[x"));
+    }
+
+    #[test]
+    fn class_members_are_literal_except_ranges() {
+        assert!(matches("[a&&b].log", "&.log"));
+        assert!(matches("[a--b].log", "-.log"));
+        assert!(!matches("[a--b].log", "c.log"));
+        assert!(matches("[-x].log", "-.log"));
+        assert!(matches("[!].log", "[!].log"));
+        assert!(matches("[]a].log", "].log"));
+    }
+
+    #[test]
+    fn malformed_classes_are_errors() {
+        let error = expand_glob("[z-a].log").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn existing_file_with_glob_characters_is_taken_literally() {
+        let root = tree("literal", &["build[1].log", "build1.log"]);
+        let path = root.join("build[1].log");
+        assert_eq!(expand_glob(&path.display().to_string()).unwrap(), vec![path]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn plain_paths_pass_through() {
+        assert_eq!(expand_glob("missing.log").unwrap(), vec![PathBuf::from("missing.log")]);
+        assert!(!is_glob("logs/app.log"));
+    }
+
+    #[test]
+    fn expands_sorted_file_matches() {
+        let root = tree("star", &["b.log", "a.log", "c.txt", ".hidden.log", "sub/d.log"]);
+        let found = expand_glob(&format!("{}/*.log", root.display())).unwrap();
+        assert_eq!(found, vec![root.join("a.log"), root.join("b.log")]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn double_star_matches_any_depth() {
+        let root = tree("globstar", &["a.log", "x/b.log", "x/y/c.log", "x/y/c.txt"]);
+        let found = expand_glob(&format!("{}/**/*.log", root.display())).unwrap();
+        assert_eq!(found, vec![root.join("a.log"), root.join("x/b.log"), root.join("x/y/c.log")]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn double_star_does_not_follow_symlinked_directories() {
+        let root = tree("symlink-loop", &["x/a.log"]);
+        std::os::unix::fs::symlink(&root, root.join("x/loop")).unwrap();
+        let found = expand_glob(&format!("{}/**/*.log", root.display())).unwrap();
+        assert_eq!(found, vec![root.join("x/a.log")]);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+//! Chrome DevTools Protocol event dumps (Puppeteer, Playwright CDP sessions).
+
+use super::{call_frames, classify_message, json_values, short_url, str_field, structured_classified, CallFrame, IngestError};
+use crate::detect::Classification;
+use crate::error_type::ErrorType;
+use crate::model::ToonifiedError;
+use crate::util::truncate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+/// Chrome's network log text: "the server responded with a status of 404 (Not Found)".
+static RESPONSE_STATUS: Lazy<Regex> = Lazy::new(|| Regex::new(r"status of ([45]\d{2})").unwrap());
+
+/// One error per `Runtime.exceptionThrown` event and per error-level
+/// `Log.entryAdded` event, in order. Other events are ignored.
+///
+/// Accepts a JSON array of events, JSON lines, or `{"events": [...]}`. Each
+/// event is either `{"method", "params"}` as sent over the wire or its bare
+/// `params` (`{"exceptionDetails"}` / `{"entry"}`). Frames and their location
+/// come from the fields of `stackTrace.callFrames`, not from the stack text.
+pub fn parse_cdp(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let mut values = json_values(input)?;
+    if let [Value::Object(wrapper)] = values.as_slice() {
+        if let Some(Value::Array(events)) = wrapper.get("events") {
+            values = events.clone();
+        }
+    }
+
+    Ok(values.iter().filter_map(event_error).collect())
+}
+
+fn event_error(event: &Value) -> Option<ToonifiedError> {
+    let params = event.get("params").unwrap_or(event);
+    let original_len = event.to_string().chars().count();
+    match str_field(event, "method") {
+        Some("Runtime.exceptionThrown") => exception(&params["exceptionDetails"], original_len),
+        Some("Log.entryAdded") => log_entry(&params["entry"], original_len),
+        Some(_) => None,
+        None if params.get("exceptionDetails").is_some() => exception(&params["exceptionDetails"], original_len),
+        None if params.get("entry").is_some() => log_entry(&params["entry"], original_len),
+        None => None,
+    }
+}
+
+/// `Runtime.ExceptionDetails`: the message is the first line of the thrown
+/// error's `description`, else `text` plus the thrown primitive value.
+fn exception(details: &Value, original_len: usize) -> Option<ToonifiedError> {
+    let thrown = &details["exception"];
+    let message = str_field(thrown, "description")
+        .and_then(|d| d.lines().next())
+        .map(str::to_string)
+        .or_else(|| {
+            let text = str_field(details, "text")?;
+            Some(match thrown.get("value").filter(|v| !v.is_null()) {
+                Some(Value::String(value)) => format!("{} {}", text, value),
+                Some(value) => format!("{} {}", text, value),
+                None => text.to_string(),
+            })
+        })?;
+
+    let frames = call_frames(&details["stackTrace"]);
+    let file_location = frames.first().map(CallFrame::location).or_else(|| script_location(details));
+    let classification = classify_message(&message, str_field(thrown, "className"));
+    let frames = frames.iter().map(ToString::to_string).collect();
+    Some(structured_classified(classification, file_location, Some(truncate(&message, 200)), frames, original_len))
+}
+
+/// `Log.LogEntry` at level `error`. Network entries are located by URL and
+/// typed `HTTP_ERROR` when the text reports a 4xx/5xx status, else `NETWORK_ERROR`.
+fn log_entry(entry: &Value, original_len: usize) -> Option<ToonifiedError> {
+    if str_field(entry, "level") != Some("error") {
+        return None;
+    }
+    let message = str_field(entry, "text")?.lines().next()?.to_string();
+
+    let frames = call_frames(&entry["stackTrace"]);
+    let (file_location, classification) = if str_field(entry, "source") == Some("network") {
+        let error_type =
+            if RESPONSE_STATUS.is_match(&message) { ErrorType::HttpError } else { ErrorType::NetworkError };
+        let classification = Classification { primary: error_type, tags: Vec::new(), confidence: 1.0 };
+        (str_field(entry, "url").map(short_url), classification)
+    } else {
+        (frames.first().map(CallFrame::location).or_else(|| script_location(entry)), classify_message(&message, None))
+    };
+    let frames = frames.iter().map(ToString::to_string).collect();
+    Some(structured_classified(classification, file_location, Some(truncate(&message, 200)), frames, original_len))
+}
+
+/// `file:line` from an event's own `url` and zero-based `lineNumber`.
+fn script_location(event: &Value) -> Option<String> {
+    let url = str_field(event, "url").filter(|url| !url.is_empty())?;
+    let file = short_url(url).rsplit('/').next()?.to_string();
+    match event["lineNumber"].as_u64() {
+        Some(line) => Some(format!("{}:{}", file, line + 1)),
+        None => Some(file),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+    use serde_json::json;
+
+    fn exception_event() -> Value {
+        json!({
+            "method": "Runtime.exceptionThrown",
+            "params": {
+                "timestamp": 1717000000000.0,
+                "exceptionDetails": {
+                    "exceptionId": 1,
+                    "text": "Uncaught",
+                    "lineNumber": 41,
+                    "columnNumber": 16,
+                    "url": "http://localhost:5173/src/components/ProductList.tsx",
+                    "stackTrace": {"callFrames": [
+                        {"functionName": "ProductList", "url": "http://localhost:5173/src/components/ProductList.tsx", "lineNumber": 41, "columnNumber": 16},
+                        {"functionName": "renderWithHooks", "url": "http://localhost:5173/node_modules/.vite/deps/react-dom.js", "lineNumber": 14984, "columnNumber": 17}
+                    ]},
+                    "exception": {
+                        "type": "object",
+                        "subtype": "error",
+                        "className": "TypeError",
+                        "description": "TypeError: Cannot read properties of undefined (reading 'map')\n    at ProductList (http://localhost:5173/src/components/ProductList.tsx:42:17)"
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn exception_thrown_uses_class_message_and_structured_frames() {
+        let errors = parse_cdp(&json!([exception_event()]).to_string()).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::TypeError);
+        assert_eq!(error.confidence, Some(1.0));
+        assert_eq!(error.issue.as_deref(), Some("TypeError: Cannot read properties of undefined (reading 'map')"));
+        assert_eq!(error.file_location.as_deref(), Some("ProductList.tsx:42"));
+        assert_eq!(error.frames, vec!["at ProductList (http://localhost:5173/src/components/ProductList.tsx:42:17)".to_string()]);
+    }
+
+    #[test]
+    fn thrown_primitives_use_text_and_value() {
+        let event = json!({"exceptionDetails": {"text": "Uncaught", "url": "http://x.test/app.js", "lineNumber": 9, "exception": {"type": "string", "value": "boom"}}});
+        let errors = parse_cdp(&event.to_string()).unwrap();
+        assert_eq!(errors[0].issue.as_deref(), Some("Uncaught boom"));
+        assert_eq!(errors[0].file_location.as_deref(), Some("app.js:10"));
+    }
+
+    #[test]
+    fn log_entries_keep_errors_only() {
+        let network = json!({"method": "Log.entryAdded", "params": {"entry": {
+            "source": "network", "level": "error",
+            "text": "Failed to load resource: the server responded with a status of 404 (Not Found)",
+            "url": "https://api.example.com/v1/users/7?x=1", "timestamp": 1.0
+        }}});
+        let warning = json!({"method": "Log.entryAdded", "params": {"entry": {"source": "javascript", "level": "warning", "text": "deprecated"}}});
+        let other = json!({"method": "Page.loadEventFired", "params": {}});
+        let input = [network, warning, other, exception_event()].iter().map(Value::to_string).collect::<Vec<_>>().join("\n");
+        let errors = parse_cdp(&input).unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_type, ErrorType::HttpError);
+        assert_eq!(errors[0].file_location.as_deref(), Some("api.example.com/v1/users/7"));
+        assert_eq!(errors[1].error_type, ErrorType::TypeError);
+    }
+
+    #[test]
+    fn accepts_events_wrapper() {
+        let input = json!({"events": [exception_event(), exception_event()]}).to_string();
+        assert_eq!(parse_cdp(&input).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,160 @@
+//! HAR (HTTP Archive) files exported from the DevTools Network panel.
+
+use super::{body_message, call_frames, short_url, str_field, structured, IngestError};
+use crate::error_type::ErrorType;
+use crate::model::ToonifiedError;
+use serde_json::Value;
+
+/// One error per failed request in a HAR document, in request order.
+///
+/// Responses with status 400 and above become `HTTP_ERROR`; requests that got no
+/// response become `CORS_ERROR` when the browser blocked them for CORS and
+/// `NETWORK_ERROR` otherwise. Requests the page cancelled itself
+/// (`net::ERR_ABORTED`) are skipped. The file is the shortened URL; the issue
+/// has the method, status or network error, timing, and the response body's
+/// error message; frames come from the request initiator's stack.
+pub fn parse_har(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let har: Value = serde_json::from_str(input)?;
+    let entries = har["log"]["entries"]
+        .as_array()
+        .ok_or_else(|| IngestError::new("not a HAR file: missing log.entries"))?;
+
+    Ok(entries.iter().filter_map(failed_request).collect())
+}
+
+fn failed_request(entry: &Value) -> Option<ToonifiedError> {
+    let request = &entry["request"];
+    let response = &entry["response"];
+    let status = response["status"].as_u64().unwrap_or(0);
+    let network_error = str_field(response, "_error").or_else(|| str_field(entry, "_error")).filter(|e| !e.is_empty());
+
+    let (error_type, outcome) = if status >= 400 {
+        let status_text = str_field(response, "statusText").unwrap_or_default();
+        (ErrorType::HttpError, format!("{} {}", status, status_text).trim_end().to_string())
+    } else if status == 0 {
+        if network_error.is_some_and(|e| e.contains("ERR_ABORTED")) {
+            return None;
+        }
+        match cors_reason(entry) {
+            Some(reason) => (ErrorType::CorsError, format!("blocked by CORS policy ({})", reason)),
+            None => (ErrorType::NetworkError, network_error.unwrap_or("no response").to_string()),
+        }
+    } else {
+        return None;
+    };
+
+    let method = str_field(request, "method").unwrap_or("GET");
+    let mut issue = format!("{} {}", method, outcome);
+    if let Some(time) = entry["time"].as_f64().filter(|t| *t >= 0.0) {
+        issue.push_str(&format!(" ({}ms)", time.round()));
+    }
+    let content = &response["content"];
+    if str_field(content, "encoding") != Some("base64") {
+        if let Some(message) = str_field(content, "text").and_then(body_message) {
+            issue.push_str(&format!(": {}", message));
+        }
+    }
+
+    Some(structured(
+        error_type,
+        str_field(request, "url").map(short_url),
+        Some(issue),
+        call_frames(&entry["_initiator"]["stack"]).iter().map(ToString::to_string).collect(),
+        entry.to_string().chars().count(),
+    ))
+}
+
+/// Why the browser blocked a request for CORS, if it did.
+fn cors_reason(entry: &Value) -> Option<String> {
+    let response = &entry["response"];
+    if let Some(status) = response.get("_corsErrorStatus").or_else(|| entry.get("_corsErrorStatus")) {
+        let reason = str_field(status, "corsError").unwrap_or("CORS error");
+        return Some(reason.to_string());
+    }
+    [str_field(response, "_blockedReason"), str_field(response, "_error"), str_field(entry, "_error")]
+        .into_iter()
+        .flatten()
+        .find(|reason| reason.to_ascii_lowercase().contains("cors"))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn har(entries: Vec<Value>) -> String {
+        json!({"log": {"version": "1.2", "entries": entries}}).to_string()
+    }
+
+    fn entry(method: &str, url: &str, status: u64, status_text: &str, extra: Value) -> Value {
+        let mut entry = json!({
+            "time": 842.37,
+            "request": {"method": method, "url": url},
+            "response": {"status": status, "statusText": status_text, "content": {"mimeType": "application/json"}}
+        });
+        if let (Value::Object(fields), Value::Object(extra)) = (&mut entry, extra) {
+            for (key, value) in extra {
+                match (fields.get_mut(&key), value) {
+                    (Some(Value::Object(existing)), Value::Object(more)) => existing.extend(more),
+                    (_, value) => {
+                        fields.insert(key, value);
+                    }
+                }
+            }
+        }
+        entry
+    }
+
+    #[test]
+    fn http_errors_carry_method_url_status_timing_and_body_message() {
+        let failed = entry(
+            "POST",
+            "https://api.example.com/v1/users?page=2",
+            500,
+            "Internal Server Error",
+            json!({
+                "response": {"content": {"mimeType": "application/json", "text": "{\"error\":{\"message\":\"Database timeout\"}}"}},
+                "_initiator": {"type": "script", "stack": {"callFrames": [
+                    {"functionName": "createUser", "url": "http://localhost:5173/src/api/users.ts", "lineNumber": 11, "columnNumber": 8}
+                ]}}
+            }),
+        );
+        let ok = entry("GET", "https://api.example.com/v1/me", 200, "OK", json!({}));
+        let errors = parse_har(&har(vec![ok, failed])).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::HttpError);
+        assert_eq!(error.confidence, Some(1.0));
+        assert_eq!(error.file_location.as_deref(), Some("api.example.com/v1/users"));
+        assert_eq!(error.issue.as_deref(), Some("POST 500 Internal Server Error (842ms): Database timeout"));
+        assert_eq!(error.frames, vec!["at createUser (http://localhost:5173/src/api/users.ts:12:9)".to_string()]);
+    }
+
+    #[test]
+    fn failed_requests_are_cors_or_network_errors() {
+        let cors = entry(
+            "GET",
+            "https://cdn.other.com/data.json",
+            0,
+            "",
+            json!({"response": {"_error": "net::ERR_FAILED", "_corsErrorStatus": {"corsError": "MissingAllowOriginHeader"}}}),
+        );
+        let refused = entry("GET", "http://localhost:4000/graphql", 0, "", json!({"response": {"_error": "net::ERR_CONNECTION_REFUSED"}}));
+        let aborted = entry("GET", "http://localhost:3000/hmr", 0, "", json!({"response": {"_error": "net::ERR_ABORTED"}}));
+        let errors = parse_har(&har(vec![cors, refused, aborted])).unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_type, ErrorType::CorsError);
+        assert_eq!(errors[0].issue.as_deref(), Some("GET blocked by CORS policy (MissingAllowOriginHeader) (842ms)"));
+        assert_eq!(errors[1].error_type, ErrorType::NetworkError);
+        assert_eq!(errors[1].issue.as_deref(), Some("GET net::ERR_CONNECTION_REFUSED (842ms)"));
+    }
+
+    #[test]
+    fn rejects_documents_without_entries() {
+        assert!(parse_har("{\"log\": {}}").unwrap_err().message.contains("log.entries"));
+        assert!(parse_har("not json").unwrap_err().message.starts_with("invalid JSON"));
+    }
+}
@@ -0,0 +1,192 @@
+//! Structured JSON-lines logs (pino, winston, bunyan).
+
+use super::{classify_message, str_field, IngestError};
+use crate::model::ToonifiedError;
+use serde_json::{Map, Value};
+
+/// Keys that hold a serialized error object.
+const ERROR_KEYS: [&str; 3] = ["err", "error", "exception"];
+
+/// One error per log record that carries an error object or is logged at
+/// error level or above, in order. Lines that aren't JSON objects are skipped.
+///
+/// The error object is `err`/`error`/`exception`, or the record itself when it
+/// has a `stack` (winston's `format.errors({ stack: true })`). Its stack, with
+/// the escaped newlines decoded, goes through the usual detection and
+/// extraction; records without one use `type: message` or the log message.
+/// Details keep the level, the timestamp, and the log message when it differs
+/// from the error's.
+pub fn parse_json_logs(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let mut records = 0;
+    let mut errors = Vec::new();
+    for line in input.lines().map(str::trim).filter(|l| l.starts_with('{')) {
+        let Ok(Value::Object(record)) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        records += 1;
+        errors.extend(record_error(&record, line.chars().count()));
+    }
+
+    if records == 0 {
+        return Err(IngestError::new("no JSON log records found"));
+    }
+    Ok(errors)
+}
+
+fn record_error(record: &Map<String, Value>, original_len: usize) -> Option<ToonifiedError> {
+    let level = level(record);
+    let err = ERROR_KEYS.iter().find_map(|key| record.get(*key).filter(|v| v.is_object() || v.is_string()));
+    // winston's `format.errors({ stack: true })` puts the error's fields on the record itself
+    let top_level = err.is_none() && record.contains_key("stack");
+    if err.is_none() && !top_level && !level.as_deref().is_some_and(is_error_level) {
+        return None;
+    }
+
+    let fields = |key: &str| match err {
+        Some(value @ Value::Object(_)) => str_field(value, key),
+        None if top_level => record.get(key)?.as_str(),
+        _ => None,
+    };
+
+    let kind = fields("type").or_else(|| fields("name")).filter(|k| !k.is_empty());
+    let err_message = match err {
+        Some(Value::String(message)) => Some(message.as_str()),
+        _ => fields("message"),
+    };
+    let log_message = ["msg", "message"].iter().find_map(|key| record.get(*key)?.as_str()).filter(|m| !m.trim().is_empty());
+
+    let text = match (fields("stack"), err_message, kind) {
+        (Some(stack), _, _) if !stack.trim().is_empty() => stack.to_string(),
+        (_, Some(message), Some(kind)) if !message.starts_with(kind) => format!("{}: {}", kind, message),
+        (_, Some(message), _) => message.to_string(),
+        (_, None, _) => log_message?.to_string(),
+    };
+
+    let mut error = ToonifiedError::classified(text.trim(), classify_message(&text, kind));
+    error.original_len = original_len;
+
+    if let Some(level) = level {
+        error = error.with_detail("level", level);
+    }
+    if let Some(time) = timestamp(record) {
+        error = error.with_detail("time", time);
+    }
+    if let Some(message) = log_message.filter(|m| Some(*m) != err_message && !text.starts_with(m.trim())) {
+        error = error.with_detail("msg", message.trim());
+    }
+    Some(error)
+}
+
+/// Level name: strings as written, pino/bunyan numbers mapped to their names.
+fn level(record: &Map<String, Value>) -> Option<String> {
+    match record.get("level").or_else(|| record.get("severity"))? {
+        Value::String(name) => Some(name.to_ascii_lowercase()),
+        Value::Number(n) => Some(
+            match n.as_u64()? {
+                60.. => "fatal",
+                50..=59 => "error",
+                40..=49 => "warn",
+                30..=39 => "info",
+                20..=29 => "debug",
+                _ => "trace",
+            }
+            .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+fn is_error_level(level: &str) -> bool {
+    matches!(level, "error" | "err" | "fatal" | "crit" | "critical" | "alert" | "emerg" | "emergency")
+}
+
+/// `time`/`timestamp`/`@timestamp`; epoch milliseconds (pino) become UTC ISO 8601.
+fn timestamp(record: &Map<String, Value>) -> Option<String> {
+    match ["time", "timestamp", "@timestamp"].iter().find_map(|key| record.get(*key))? {
+        Value::String(time) => Some(time.clone()),
+        Value::Number(n) => n.as_i64().map(iso_from_millis),
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ` for milliseconds since the Unix epoch.
+fn iso_from_millis(millis: i64) -> String {
+    let (secs, ms) = (millis.div_euclid(1000), millis.rem_euclid(1000));
+    let (days, secs) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        ms
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+
+    const PINO: &str = r#"{"level":30,"time":1700000000000,"msg":"server listening"}
+{"level":50,"time":1700000000123,"pid":7,"msg":"request failed","err":{"type":"TypeError","message":"Cannot read properties of undefined (reading 'id')","stack":"TypeError: Cannot read properties of undefined (reading 'id')\n    at getUser (/srv/app/src/routes/users.ts:42:17)\n    at Layer.handle (/srv/app/node_modules/express/lib/router/layer.js:95:5)"}}
+not json at all"#;
+
+    #[test]
+    fn pino_error_stack_is_unescaped_and_extracted() {
+        let errors = parse_json_logs(PINO).unwrap();
+        assert_eq!(errors.len(), 1);
+
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::TypeError);
+        assert_eq!(error.issue.as_deref(), Some("TypeError: Cannot read properties of undefined (reading 'id')"));
+        assert_eq!(error.file_location.as_deref(), Some("users.ts:42"));
+        assert_eq!(error.frames, vec!["at getUser (/srv/app/src/routes/users.ts:42:17)".to_string()]);
+        assert_eq!(error.detail("level"), Some("error"));
+        assert_eq!(error.detail("time"), Some("2023-11-14T22:13:20.123Z"));
+        assert_eq!(error.detail("msg"), Some("request failed"));
+    }
+
+    #[test]
+    fn winston_top_level_stack_and_error_level_messages() {
+        let input = r#"{"level":"error","message":"connect ECONNREFUSED 127.0.0.1:5432","stack":"Error: connect ECONNREFUSED 127.0.0.1:5432\n    at TCPConnectWrap.afterConnect [as oncomplete] (node:net:1595:16)","timestamp":"2024-05-01T10:00:00.000Z"}
+{"level":"warn","message":"slow query"}
+{"level":"error","message":"Payment provider returned 502"}"#;
+        let errors = parse_json_logs(input).unwrap();
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!(errors[0].error_type, ErrorType::SystemError);
+        assert_eq!(errors[0].detail("time"), Some("2024-05-01T10:00:00.000Z"));
+        assert_eq!(errors[0].detail("msg"), None);
+        assert_eq!(errors[1].issue.as_deref(), Some("Payment provider returned 502"));
+    }
+
+    #[test]
+    fn bunyan_err_name_and_message_without_stack() {
+        let input = r#"{"name":"api","level":60,"time":"2024-05-01T10:00:00.000Z","msg":"crashed","err":{"name":"RangeError","message":"Invalid array length"}}"#;
+        let error = &parse_json_logs(input).unwrap()[0];
+        assert_eq!(error.error_type, ErrorType::RangeError);
+        assert_eq!(error.issue.as_deref(), Some("RangeError: Invalid array length"));
+        assert_eq!(error.detail("level"), Some("fatal"));
+    }
+
+    #[test]
+    fn iso_dates() {
+        assert_eq!(iso_from_millis(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso_from_millis(951_782_400_000), "2000-02-29T00:00:00.000Z");
+    }
+}
@@ -0,0 +1,128 @@
+//! JUnit XML test reports (Jest, Vitest, pytest, Gradle, ...).
+
+use super::{classify_message, IngestError};
+use crate::model::ToonifiedError;
+use crate::util::strip_ansi;
+use roxmltree::{Document, Node};
+
+/// One error per `<testcase>` with a `<failure>` or `<error>` child, in report order.
+///
+/// The failure text goes through the usual detection and extraction, with the
+/// `message` attribute as its first line when the body doesn't already start
+/// with it. Details carry the enclosing `<testsuite>` name and the test name;
+/// a test whose failure text names no file is located by its `file`/`line`
+/// attributes.
+pub fn parse_junit(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let document = Document::parse(input).map_err(|e| IngestError::new(format!("invalid XML: {}", e)))?;
+    let root = document.root_element();
+    if !matches!(root.tag_name().name(), "testsuites" | "testsuite") {
+        return Err(IngestError::new(format!("not a JUnit report: root element is <{}>", root.tag_name().name())));
+    }
+
+    Ok(root
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+        .filter_map(|testcase| testcase_error(input, testcase))
+        .collect())
+}
+
+fn testcase_error(input: &str, testcase: Node) -> Option<ToonifiedError> {
+    let failure = testcase.children().find(|n| n.has_tag_name("failure") || n.has_tag_name("error"))?;
+    let text = failure_text(failure);
+    let kind = failure.attribute("type").filter(|t| !t.is_empty());
+
+    let mut error = ToonifiedError::classified(&text, classify_message(&text, kind));
+    error.original_len = input[testcase.range()].chars().count();
+    if error.file_location.is_none() {
+        error.file_location = testcase.attribute("file").map(|file| match testcase.attribute("line") {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.to_string(),
+        });
+    }
+
+    let suite = testcase.ancestors().find(|n| n.has_tag_name("testsuite")).and_then(|s| s.attribute("name"));
+    if let Some(suite) = suite.filter(|s| !s.is_empty()) {
+        error = error.with_detail("suite", suite);
+    }
+    Some(error.with_detail("test", test_name(testcase, suite)))
+}
+
+/// The `message` attribute followed by the element's text, trimmed and without
+/// ANSI escapes. The message is left out when the text already starts with it.
+fn failure_text(failure: Node) -> String {
+    let body: String = failure.descendants().filter(|n| n.is_text()).filter_map(|n| n.text()).collect();
+    let body = strip_ansi(body.trim()).into_owned();
+    let message = strip_ansi(failure.attribute("message").unwrap_or_default().trim()).into_owned();
+
+    let first_line = message.lines().next().unwrap_or_default();
+    if message.is_empty() || body.starts_with(first_line) {
+        body
+    } else if body.is_empty() {
+        message
+    } else {
+        format!("{}\n{}", message, body)
+    }
+}
+
+/// The test's `name`, prefixed by its `classname` when that adds something
+/// beyond the suite name (pytest's `tests.test_api.TestUsers`).
+fn test_name(testcase: Node, suite: Option<&str>) -> String {
+    let name = testcase.attribute("name").unwrap_or("<unnamed>");
+    match testcase.attribute("classname") {
+        Some(class) if !class.is_empty() && Some(class) != suite && !name.starts_with(class) => {
+            format!("{}.{}", class, name)
+        }
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+
+    const JEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="jest tests" tests="3" failures="1" errors="1">
+  <testsuite name="ProductList" tests="3" failures="1">
+    <testcase classname="ProductList renders items" name="ProductList renders items" time="0.01">
+    </testcase>
+    <testcase classname="ProductList maps products" name="ProductList maps products" time="0.02">
+      <failure message="TypeError: Cannot read properties of undefined (reading &apos;map&apos;)"><![CDATA[TypeError: Cannot read properties of undefined (reading 'map')
+    at ProductList (/app/src/components/ProductList.tsx:42:17)
+    at Object.<anonymous> (/app/node_modules/jest-circus/build/utils.js:298:28)]]></failure>
+    </testcase>
+    <testcase classname="ProductList skipped" name="ProductList skipped"><skipped/></testcase>
+  </testsuite>
+</testsuites>"#;
+
+    const PYTEST: &str = r#"<testsuites><testsuite name="pytest" errors="1">
+  <testcase classname="tests.test_api.TestUsers" name="test_fetch" file="tests/test_api.py" line="14">
+    <error message="failed on setup with &quot;ConnectionRefusedError&quot;">def setup():
+E   ConnectionRefusedError: [Errno 111] Connection refused</error>
+  </testcase>
+</testsuite></testsuites>"#;
+
+    #[test]
+    fn failures_run_through_detection_and_extraction() {
+        let errors = parse_junit(JEST).unwrap();
+        assert_eq!(errors.len(), 1);
+
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::TypeError);
+        assert_eq!(error.file_location.as_deref(), Some("ProductList.tsx:42"));
+        assert_eq!(error.issue.as_deref(), Some("TypeError: Cannot read properties of undefined (reading 'map')"));
+        assert_eq!(error.frames.len(), 1);
+        assert_eq!(error.detail("suite"), Some("ProductList"));
+        assert_eq!(error.detail("test"), Some("ProductList maps products"));
+    }
+
+    #[test]
+    fn errors_use_message_and_testcase_location() {
+        let errors = parse_junit(PYTEST).unwrap();
+        let error = &errors[0];
+        assert_eq!(error.issue.as_deref(), Some("failed on setup with \"ConnectionRefusedError\""));
+        assert_eq!(error.file_location.as_deref(), Some("tests/test_api.py:14"));
+        assert_eq!(error.detail("suite"), Some("pytest"));
+        assert_eq!(error.detail("test"), Some("tests.test_api.TestUsers.test_fetch"));
+    }
+}
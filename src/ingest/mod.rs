@@ -0,0 +1,336 @@
+//! Structured inputs: error reports that arrive as data instead of console text.
+//!
+//! Each submodule parses one format and builds [`ToonifiedError`] values
+//! directly from its fields, so they render through the same formatters as
+//! errors extracted from text. When the data names the error type (an HTTP
+//! status, a JavaScript error class) the error has confidence 1; free-text
+//! messages are classified like console text.
+
+mod cdp;
+mod har;
+mod json_logs;
+mod junit;
+mod playwright;
+mod sentry;
+
+pub use cdp::parse_cdp;
+pub use har::parse_har;
+pub use json_logs::parse_json_logs;
+pub use junit::parse_junit;
+pub use playwright::parse_playwright;
+pub use sentry::parse_sentry;
+
+use crate::detect::{classify, classify_as, Classification};
+use crate::error_type::ErrorType;
+use crate::extract::extract_file_location;
+use crate::patterns::PATTERNS;
+use crate::model::ToonifiedError;
+use crate::util::truncate;
+use serde_json::Value;
+use std::fmt;
+
+/// Longest issue text kept from a response body or message field.
+const MAX_MESSAGE_LEN: usize = 120;
+
+/// Error reading a structured input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestError {
+    pub message: String,
+}
+
+impl IngestError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl fmt::Display for IngestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for IngestError {}
+
+impl From<serde_json::Error> for IngestError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::new(format!("invalid JSON: {}", e))
+    }
+}
+
+/// An error whose fields, type included, come straight from structured data.
+fn structured(
+    error_type: ErrorType,
+    file_location: Option<String>,
+    issue: Option<String>,
+    frames: Vec<String>,
+    original_len: usize,
+) -> ToonifiedError {
+    let classification = Classification { primary: error_type, tags: Vec::new(), confidence: 1.0 };
+    structured_classified(classification, file_location, issue, frames, original_len)
+}
+
+/// An error built from structured fields with a type classified from its message.
+fn structured_classified(
+    classification: Classification,
+    file_location: Option<String>,
+    issue: Option<String>,
+    frames: Vec<String>,
+    original_len: usize,
+) -> ToonifiedError {
+    ToonifiedError {
+        error_type: classification.primary,
+        tags: classification.tags,
+        confidence: Some(classification.confidence),
+        file_location,
+        issue,
+        details: Vec::new(),
+        frames,
+        original_len,
+    }
+}
+
+/// Classify an error message. A JavaScript error `class` that maps to an
+/// [`ErrorType`] decides the primary type with confidence 1; otherwise the
+/// message is classified like console text, falling back to `RUNTIME_ERROR`.
+fn classify_message(message: &str, class: Option<&str>) -> Classification {
+    if let Some(error_type) = class.and_then(js_error_type) {
+        return Classification { confidence: 1.0, ..classify_as(message, error_type) };
+    }
+    classify(message).unwrap_or_else(|| classify_as(message, ErrorType::RuntimeError))
+}
+
+/// Error type for a built-in JavaScript error class name.
+fn js_error_type(class: &str) -> Option<ErrorType> {
+    match class {
+        "TypeError" => Some(ErrorType::TypeError),
+        "ReferenceError" => Some(ErrorType::RefError),
+        "SyntaxError" => Some(ErrorType::SyntaxError),
+        "RangeError" => Some(ErrorType::RangeError),
+        "URIError" => Some(ErrorType::UriError),
+        "EvalError" => Some(ErrorType::EvalError),
+        _ => None,
+    }
+}
+
+/// Frames kept per error, like [`extract_user_frames`](crate::extract_user_frames).
+const MAX_FRAMES: usize = 3;
+
+/// One frame of a DevTools `Runtime.StackTrace`, with one-based line and column.
+#[derive(Debug, Clone, PartialEq)]
+struct CallFrame<'a> {
+    function: &'a str,
+    url: &'a str,
+    line: u64,
+    column: u64,
+}
+
+impl CallFrame<'_> {
+    /// `file:line`, the file being the last segment of the URL's path.
+    fn location(&self) -> String {
+        let path = short_url(self.url);
+        format!("{}:{}", path.rsplit('/').next().unwrap_or(&path), self.line)
+    }
+}
+
+impl fmt::Display for CallFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {} ({}:{}:{})", self.function, self.url, self.line, self.column)
+    }
+}
+
+/// User frames of a DevTools `Runtime.StackTrace`: its `callFrames` (zero-based
+/// `lineNumber`/`columnNumber`), then those of each async `parent`. Frames are
+/// kept or dropped by their `url` field: frames without one, and `node_modules`
+/// and framework URLs, are left out.
+fn call_frames(stack: &Value) -> Vec<CallFrame<'_>> {
+    let mut frames = Vec::new();
+    let mut stack = stack;
+    while let Some(calls) = stack["callFrames"].as_array() {
+        frames.extend(calls.iter().filter_map(|frame| {
+            let url = str_field(frame, "url").filter(|url| !url.is_empty() && !PATTERNS.framework_noise.is_match(url))?;
+            Some(CallFrame {
+                function: str_field(frame, "functionName").filter(|n| !n.is_empty()).unwrap_or("<anonymous>"),
+                url,
+                line: frame["lineNumber"].as_u64().unwrap_or(0) + 1,
+                column: frame["columnNumber"].as_u64().unwrap_or(0) + 1,
+            })
+        }));
+        stack = &stack["parent"];
+    }
+    frames.truncate(MAX_FRAMES);
+    frames
+}
+
+/// The error message in a response or payload body: a `message`/`error`/`detail`
+/// field of a JSON body, otherwise the first line of text with markup removed.
+fn body_message(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    if let Ok(json) = serde_json::from_str::<Value>(body) {
+        return json_message(&json).map(|m| truncate(m.trim(), MAX_MESSAGE_LEN));
+    }
+
+    let text = strip_tags(body);
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| truncate(line, MAX_MESSAGE_LEN))
+}
+
+/// First message-like string in a JSON error payload.
+fn json_message(json: &Value) -> Option<&str> {
+    match json {
+        Value::String(s) if !s.trim().is_empty() => Some(s),
+        Value::Object(fields) => ["message", "error", "detail", "title", "error_description", "errors"]
+            .iter()
+            .filter_map(|key| fields.get(*key))
+            .find_map(json_message),
+        Value::Array(items) => items.iter().find_map(json_message),
+        _ => None,
+    }
+}
+
+/// Text content of an HTML body: tags dropped, `<head>`/`<script>`/`<style>` skipped.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let lower = after.get(..8).unwrap_or(after).to_ascii_lowercase();
+        let skip_until = ["<head", "<script", "<style"]
+            .iter()
+            .find(|tag| lower.starts_with(*tag))
+            .map(|tag| format!("</{}>", &tag[1..]));
+        let end = match skip_until {
+            Some(close) => after.to_ascii_lowercase().find(&close).map(|i| i + close.len()),
+            None => after.find('>').map(|i| i + 1),
+        };
+        match end {
+            Some(end) => {
+                text.push('\n');
+                rest = &after[end..];
+            }
+            None => {
+                rest = "";
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// `file:line` of the first frame, as [`extract_file_location`] reads it.
+fn frame_location(frames: &[String]) -> Option<String> {
+    frames.first().and_then(|frame| extract_file_location(frame))
+}
+
+/// Parse a JSON document, a JSON array, or JSON lines into a list of values.
+fn json_values(input: &str) -> Result<Vec<Value>, IngestError> {
+    match serde_json::from_str::<Value>(input) {
+        Ok(Value::Array(items)) => Ok(items),
+        Ok(value) => Ok(vec![value]),
+        Err(whole) => {
+            let lines: Result<Vec<Value>, _> =
+                input.lines().filter(|line| !line.trim().is_empty()).map(serde_json::from_str).collect();
+            lines.map_err(|_| IngestError::from(whole))
+        }
+    }
+}
+
+/// `host/path` without scheme, query, or fragment, truncated for display.
+fn short_url(url: &str) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    truncate(url, 60)
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn body_message_reads_json_error_fields() {
+        assert_eq!(body_message(r#"{"error":{"message":"User not found"}}"#).as_deref(), Some("User not found"));
+        assert_eq!(body_message(r#"{"errors":[{"message":"Bad id"}]}"#).as_deref(), Some("Bad id"));
+        assert_eq!(body_message(r#"{"detail":"Not authenticated"}"#).as_deref(), Some("Not authenticated"));
+        assert_eq!(body_message(r#"{"ok":false}"#), None);
+    }
+
+    #[test]
+    fn body_message_strips_html() {
+        let html = "<html><head><title>x</title><style>h1{}</style></head><body><h1>502 Bad Gateway</h1></body></html>";
+        assert_eq!(body_message(html).as_deref(), Some("502 Bad Gateway"));
+        assert_eq!(body_message("  \n plain failure\nmore").as_deref(), Some("plain failure"));
+    }
+
+    #[test]
+    fn js_error_class_decides_type() {
+        let classification = classify_message("Error: x is not a function", Some("TypeError"));
+        assert_eq!((classification.primary, classification.confidence), (ErrorType::TypeError, 1.0));
+        assert_eq!(classify_message("Uncaught boom", Some("Error")).primary, ErrorType::RuntimeError);
+        assert_eq!(classify_message("ReferenceError: x is not defined", None).primary, ErrorType::RefError);
+    }
+
+    #[test]
+    fn json_values_accepts_arrays_objects_and_lines() {
+        assert_eq!(json_values("[1, 2]").unwrap().len(), 2);
+        assert_eq!(json_values("{\"a\": 1}").unwrap().len(), 1);
+        assert_eq!(json_values("{\"a\": 1}\n\n{\"b\": 2}\n").unwrap().len(), 2);
+        assert!(json_values("{oops").is_err());
+    }
+
+    #[test]
+    fn parsers_reject_other_documents() {
+        assert!(parse_playwright("{\"stats\": {}}").is_err());
+        assert!(parse_junit("<html></html>").is_err());
+        assert!(parse_junit("<testsuite>").is_err());
+        assert!(parse_sentry("{\"log\": {}}").is_err());
+        assert!(parse_json_logs("TypeError: boom\n    at x (a.js:1:1)").is_err());
+    }
+
+    #[test]
+    fn short_url_drops_scheme_and_query() {
+        assert_eq!(short_url("https://api.example.com/v1/users?id=1#top"), "api.example.com/v1/users");
+    }
+
+    #[test]
+    fn call_frames_are_one_based_and_filtered_by_url() {
+        let stack = json!({"callFrames": [
+            {"functionName": "loadUsers", "url": "http://localhost:3000/src/api.ts", "lineNumber": 41, "columnNumber": 4},
+            {"functionName": "", "url": "http://localhost:3000/node_modules/.vite/deps/react-dom.js", "lineNumber": 9},
+            {"functionName": "eval", "url": "", "lineNumber": 0}
+        ]});
+        let frames = call_frames(&stack);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].to_string(), "at loadUsers (http://localhost:3000/src/api.ts:42:5)");
+        assert_eq!(frames[0].location(), "api.ts:42");
+    }
+
+    #[test]
+    fn call_frames_keep_module_and_extensionless_urls_and_async_parents() {
+        let stack = json!({
+            "callFrames": [{"functionName": "Object.fetchUser", "url": "http://localhost:3000/src/users.mjs", "lineNumber": 6, "columnNumber": 2}],
+            "parent": {"callFrames": [
+                {"functionName": "boot", "url": "https://app.example.com/assets/main", "lineNumber": 0, "columnNumber": 130}
+            ]}
+        });
+        let frames: Vec<String> = call_frames(&stack).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            frames,
+            vec![
+                "at Object.fetchUser (http://localhost:3000/src/users.mjs:7:3)".to_string(),
+                "at boot (https://app.example.com/assets/main:1:131)".to_string(),
+            ]
+        );
+    }
+}
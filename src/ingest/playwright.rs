@@ -0,0 +1,183 @@
+//! Playwright JSON reporter output (`--reporter=json`).
+
+use super::{str_field, structured_classified, IngestError};
+use crate::detect::{classify_as, Classification};
+use crate::error_type::ErrorType;
+use crate::extract::extract_user_frames;
+use crate::model::ToonifiedError;
+use crate::util::{strip_ansi, truncate};
+use serde_json::Value;
+
+/// One error per failed test, then one per flaky test (failed, then passed on
+/// retry), each in report order.
+///
+/// The file is the spec's `file:line`. Details carry the title path
+/// (`describe › test`), the project, the retry that failed, the locator the
+/// failing call waited for, and for flaky tests the retry they passed on.
+/// Frames are the user frames of the reported stack.
+pub fn parse_playwright(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let report: Value = serde_json::from_str(input)?;
+    let suites = report["suites"]
+        .as_array()
+        .ok_or_else(|| IngestError::new("not a Playwright JSON report: missing suites"))?;
+
+    let mut failed = Vec::new();
+    let mut flaky = Vec::new();
+    for suite in suites {
+        walk_suite(suite, &[], &mut failed, &mut flaky);
+    }
+    failed.extend(flaky);
+    Ok(failed)
+}
+
+/// Collect failures from `suite`'s specs and nested suites. `titles` are the
+/// enclosing `describe` titles; the top-level suite is the file and adds none.
+fn walk_suite(suite: &Value, titles: &[&str], failed: &mut Vec<ToonifiedError>, flaky: &mut Vec<ToonifiedError>) {
+    for spec in suite["specs"].as_array().into_iter().flatten() {
+        let mut path = titles.to_vec();
+        path.extend(str_field(spec, "title"));
+        for test in spec["tests"].as_array().into_iter().flatten() {
+            match str_field(test, "status") {
+                Some("unexpected") => failed.extend(test_error(spec, test, &path, false)),
+                Some("flaky") => flaky.extend(test_error(spec, test, &path, true)),
+                _ => {}
+            }
+        }
+    }
+
+    for child in suite["suites"].as_array().into_iter().flatten() {
+        let mut path = titles.to_vec();
+        path.extend(str_field(child, "title").filter(|t| !t.is_empty()));
+        walk_suite(child, &path, failed, flaky);
+    }
+}
+
+fn test_error(spec: &Value, test: &Value, path: &[&str], is_flaky: bool) -> Option<ToonifiedError> {
+    let results = test["results"].as_array()?;
+    let mut failures = results.iter().filter(|r| !matches!(str_field(r, "status"), Some("passed" | "skipped")));
+    // A failed test's last attempt is the one that counts; a flaky test's first failure is what went wrong
+    let result = if is_flaky { failures.next()? } else { failures.next_back()? };
+
+    let error = result.get("error").or_else(|| result["errors"].get(0))?;
+    let message = strip_ansi(str_field(error, "message").unwrap_or_default()).into_owned();
+    let stack = strip_ansi(str_field(error, "stack").unwrap_or_default()).into_owned();
+    let header = message.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("Test failed").to_string();
+
+    let file_location = str_field(spec, "file").map(|file| match spec["line"].as_u64() {
+        Some(line) => format!("{}:{}", file, line),
+        None => file.to_string(),
+    });
+
+    let classification = Classification { confidence: 1.0, ..classify_as(&message, ErrorType::Playwright) };
+    let mut error = structured_classified(
+        classification,
+        file_location,
+        Some(truncate(&header, 200)),
+        extract_user_frames(&stack),
+        test.to_string().chars().count(),
+    )
+    .with_detail("test", path.join(" › "));
+
+    if let Some(project) = str_field(test, "projectName").filter(|p| !p.is_empty()) {
+        error = error.with_detail("project", project);
+    }
+    error = error.with_detail("retry", result["retry"].as_u64().unwrap_or(0).to_string());
+    if let Some(locator) = locator(&message) {
+        error = error.with_detail("locator", locator);
+    }
+    if is_flaky {
+        let passed_on = results.iter().find(|r| str_field(r, "status") == Some("passed")).and_then(|r| r["retry"].as_u64());
+        let status = match passed_on {
+            Some(retry) => format!("flaky (passed on retry {})", retry),
+            None => "flaky".to_string(),
+        };
+        error = error.with_detail("status", status);
+    }
+    Some(error)
+}
+
+/// The locator a failing assertion or action was about: `Locator: ...` in
+/// assertion messages, else the target of the call log's `waiting for ...`.
+fn locator(message: &str) -> Option<String> {
+    let lines = message.lines().map(str::trim);
+    let mut found = None;
+    for line in lines {
+        if let Some(locator) = line.strip_prefix("Locator:") {
+            return Some(truncate(locator.trim(), 120));
+        }
+        if let Some((_, target)) = line.split_once("waiting for ") {
+            found = found.or_else(|| Some(truncate(target.trim(), 120)));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ASSERTION: &str = "\u{1b}[31mError: expect(locator).toBeVisible() failed\u{1b}[39m\n\nLocator: getByRole('button', { name: 'Sign in' })\nExpected: visible\nReceived: <element(s) not found>\nTimeout: 5000ms\n\nCall log:\n  - Expect \"toBeVisible\" with timeout 5000ms\n  - waiting for getByRole('button', { name: 'Sign in' })\n";
+    const STACK: &str = "Error: expect(locator).toBeVisible() failed\n    at /home/me/app/tests/auth/login.spec.ts:15:33\n    at /home/me/app/node_modules/playwright/lib/worker/workerMain.js:335:9";
+
+    fn result(status: &str, retry: u64, message: Option<&str>) -> Value {
+        match message {
+            Some(message) => json!({"status": status, "retry": retry, "error": {"message": message, "stack": STACK}}),
+            None => json!({"status": status, "retry": retry}),
+        }
+    }
+
+    fn report() -> String {
+        let failed = json!({"projectName": "chromium", "status": "unexpected", "results": [
+            result("failed", 0, Some("Error: first attempt")),
+            result("failed", 1, Some(ASSERTION)),
+        ]});
+        let flaky = json!({"projectName": "chromium", "status": "flaky", "results": [
+            result("timedOut", 0, Some("Test timeout of 30000ms exceeded.\n  - waiting for locator('#menu')")),
+            result("passed", 1, None),
+        ]});
+        let passed = json!({"projectName": "chromium", "status": "expected", "results": [result("passed", 0, None)]});
+
+        json!({"suites": [{
+            "title": "auth/login.spec.ts",
+            "file": "auth/login.spec.ts",
+            "specs": [{"title": "shows landing", "file": "auth/login.spec.ts", "line": 5, "tests": [passed]}],
+            "suites": [{
+                "title": "Login",
+                "file": "auth/login.spec.ts",
+                "specs": [
+                    {"title": "opens menu", "file": "auth/login.spec.ts", "line": 20, "tests": [flaky]},
+                    {"title": "signs in", "file": "auth/login.spec.ts", "line": 12, "tests": [failed]}
+                ]
+            }]
+        }]})
+        .to_string()
+    }
+
+    #[test]
+    fn failed_tests_come_first_with_title_path_retry_and_locator() {
+        let errors = parse_playwright(&report()).unwrap();
+        assert_eq!(errors.len(), 2);
+
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::Playwright);
+        assert_eq!(error.file_location.as_deref(), Some("auth/login.spec.ts:12"));
+        assert_eq!(error.issue.as_deref(), Some("Error: expect(locator).toBeVisible() failed"));
+        assert_eq!(error.detail("test"), Some("Login › signs in"));
+        assert_eq!(error.detail("project"), Some("chromium"));
+        assert_eq!(error.detail("retry"), Some("1"));
+        assert_eq!(error.detail("locator"), Some("getByRole('button', { name: 'Sign in' })"));
+        assert_eq!(error.detail("status"), None);
+        assert_eq!(error.frames, vec!["at /home/me/app/tests/auth/login.spec.ts:15:33".to_string()]);
+    }
+
+    #[test]
+    fn flaky_tests_are_marked_and_listed_last() {
+        let errors = parse_playwright(&report()).unwrap();
+        let error = &errors[1];
+        assert_eq!(error.detail("test"), Some("Login › opens menu"));
+        assert_eq!(error.detail("retry"), Some("0"));
+        assert_eq!(error.detail("locator"), Some("locator('#menu')"));
+        assert_eq!(error.detail("status"), Some("flaky (passed on retry 1)"));
+    }
+}
@@ -0,0 +1,181 @@
+//! Sentry event JSON (the "JSON" download on an issue, or events from the API).
+
+use super::{classify_message, frame_location, json_values, str_field, structured_classified, IngestError, MAX_FRAMES};
+use crate::model::ToonifiedError;
+use crate::util::truncate;
+use serde_json::Value;
+
+/// One error per event with an exception, in order.
+///
+/// Accepts a single event, an array of events, or JSON lines, with the
+/// exception either at `exception.values` (event payloads) or in an
+/// `entries[]` item of type `exception` (API responses). The last value is the
+/// exception that was reported; earlier values in a chain become `cause`
+/// details. `type`/`value` decide the error type and issue, user frames are
+/// the frames Sentry marked `in_app` (innermost first), and `release` and
+/// `environment` are kept as `release`/`env` details.
+pub fn parse_sentry(input: &str) -> Result<Vec<ToonifiedError>, IngestError> {
+    let events = json_values(input)?;
+    if !events.iter().any(|e| e.get("exception").is_some() || e.get("entries").is_some() || e.get("event_id").is_some()) {
+        return Err(IngestError::new("not a Sentry event: missing exception"));
+    }
+    Ok(events.iter().filter_map(event_error).collect())
+}
+
+fn event_error(event: &Value) -> Option<ToonifiedError> {
+    let values = exception_values(event)?;
+    let (reported, causes) = values.split_last()?;
+
+    let message = exception_message(reported);
+    let frames = in_app_frames(&reported["stacktrace"]["frames"]);
+    let classification = classify_message(&message, str_field(reported, "type"));
+    let mut error = structured_classified(
+        classification,
+        frame_location(&frames),
+        Some(truncate(&message, 200)),
+        frames,
+        event.to_string().chars().count(),
+    );
+
+    for cause in causes.iter().rev() {
+        error = error.with_detail("cause", truncate(&exception_message(cause), 120));
+    }
+    if let Some(release) = tag(event, "release") {
+        error = error.with_detail("release", release);
+    }
+    if let Some(environment) = tag(event, "environment") {
+        error = error.with_detail("env", environment);
+    }
+    Some(error)
+}
+
+/// `exception.values`, or the values of the `exception` entry in an API event.
+fn exception_values(event: &Value) -> Option<&Vec<Value>> {
+    event["exception"]["values"].as_array().filter(|v| !v.is_empty()).or_else(|| {
+        event["entries"]
+            .as_array()?
+            .iter()
+            .find(|entry| str_field(entry, "type") == Some("exception"))?["data"]["values"]
+            .as_array()
+    })
+}
+
+/// `Type: value`, or whichever of the two is present.
+fn exception_message(exception: &Value) -> String {
+    match (str_field(exception, "type"), str_field(exception, "value")) {
+        (Some(kind), Some(value)) => format!("{}: {}", kind, value.lines().next().unwrap_or_default()),
+        (Some(kind), None) => kind.to_string(),
+        (None, Some(value)) => value.lines().next().unwrap_or_default().to_string(),
+        (None, None) => "Error".to_string(),
+    }
+}
+
+/// `in_app` frames, innermost first. Sentry lists frames oldest call first.
+fn in_app_frames(frames: &Value) -> Vec<String> {
+    let Some(frames) = frames.as_array() else {
+        return Vec::new();
+    };
+    frames
+        .iter()
+        .rev()
+        .filter(|frame| frame["in_app"].as_bool() == Some(true))
+        .take(MAX_FRAMES)
+        .map(|frame| {
+            let function = str_field(frame, "function").filter(|f| !f.is_empty()).unwrap_or("<anonymous>");
+            let file = ["filename", "abs_path", "module"]
+                .iter()
+                .find_map(|key| str_field(frame, key))
+                .unwrap_or("<unknown>");
+            match (frame["lineno"].as_u64(), frame["colno"].as_u64()) {
+                (Some(line), Some(column)) => format!("at {} ({}:{}:{})", function, file, line, column),
+                (Some(line), None) => format!("at {} ({}:{})", function, file, line),
+                _ => format!("at {} ({})", function, file),
+            }
+        })
+        .collect()
+}
+
+/// A top-level field such as `release`, else the same key in `tags`, which
+/// Sentry writes as `[[key, value]]`, `[{"key", "value"}]`, or an object.
+fn tag(event: &Value, key: &str) -> Option<String> {
+    let value = match &event[key] {
+        Value::String(s) => Some(s.as_str()),
+        // API events nest the release: {"release": {"version": "..."}}
+        Value::Object(_) => str_field(&event[key], "shortVersion").or_else(|| str_field(&event[key], "version")),
+        _ => None,
+    };
+    let value = value.or_else(|| match &event["tags"] {
+        Value::Array(tags) => tags.iter().find_map(|tag| match tag {
+            Value::Array(pair) if pair.first().and_then(Value::as_str) == Some(key) => pair.get(1)?.as_str(),
+            Value::Object(_) if str_field(tag, "key") == Some(key) => str_field(tag, "value"),
+            _ => None,
+        }),
+        Value::Object(tags) => tags.get(key)?.as_str(),
+        _ => None,
+    });
+    value.filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_type::ErrorType;
+    use serde_json::json;
+
+    fn frame(function: &str, filename: &str, line: u64, in_app: bool) -> Value {
+        json!({"function": function, "filename": filename, "lineno": line, "colno": 17, "in_app": in_app})
+    }
+
+    fn event() -> Value {
+        json!({
+            "event_id": "9f2c",
+            "release": "web@1.4.2",
+            "tags": [["environment", "production"], ["browser", "Chrome"]],
+            "exception": {"values": [
+                {"type": "ChunkLoadError", "value": "Loading chunk 7 failed."},
+                {"type": "TypeError", "value": "Cannot read properties of undefined (reading 'map')", "stacktrace": {"frames": [
+                    frame("renderWithHooks", "./node_modules/react-dom/cjs/react-dom.development.js", 14985, false),
+                    frame("App", "./src/App.tsx", 18, true),
+                    frame("ProductList", "./src/components/ProductList.tsx", 42, true),
+                    frame("Array.map", "<anonymous>", 1, false),
+                ]}}
+            ]}
+        })
+    }
+
+    #[test]
+    fn reads_reported_exception_with_in_app_frames() {
+        let errors = parse_sentry(&event().to_string()).unwrap();
+        assert_eq!(errors.len(), 1);
+
+        let error = &errors[0];
+        assert_eq!(error.error_type, ErrorType::TypeError);
+        assert_eq!(error.confidence, Some(1.0));
+        assert_eq!(error.issue.as_deref(), Some("TypeError: Cannot read properties of undefined (reading 'map')"));
+        assert_eq!(error.file_location.as_deref(), Some("ProductList.tsx:42"));
+        assert_eq!(
+            error.frames,
+            vec![
+                "at ProductList (./src/components/ProductList.tsx:42:17)".to_string(),
+                "at App (./src/App.tsx:18:17)".to_string(),
+            ]
+        );
+        assert_eq!(error.detail("cause"), Some("ChunkLoadError: Loading chunk 7 failed."));
+        assert_eq!(error.detail("release"), Some("web@1.4.2"));
+        assert_eq!(error.detail("env"), Some("production"));
+    }
+
+    #[test]
+    fn reads_api_entries_and_object_tags() {
+        let api = json!({
+            "eventID": "9f2c",
+            "release": {"version": "web@1.4.2", "shortVersion": "1.4.2"},
+            "tags": [{"key": "environment", "value": "staging"}],
+            "entries": [{"type": "exception", "data": {"values": [{"type": "Error", "value": "Failed to fetch"}]}}]
+        });
+        let errors = parse_sentry(&api.to_string()).unwrap();
+        assert_eq!(errors[0].error_type, ErrorType::NetworkError);
+        assert_eq!(errors[0].detail("release"), Some("1.4.2"));
+        assert_eq!(errors[0].detail("env"), Some("staging"));
+    }
+}
@@ -0,0 +1,154 @@
+//! Compress verbose browser errors for LLM consumption.
+//!
+//! The pipeline has four stages, each exposed on its own so tools can embed
+//! as much of it as they need:
+//!
+//! 1. **Split** mixed console output into one block per error
+//!    ([`split_into_error_blocks`]).
+//! 2. **Detect** the [`ErrorType`] of each block ([`detect_error_type`]), or
+//!    score it against every detector for a primary type, secondary tags, and
+//!    a confidence ([`classify`]).
+//! 3. **Extract** the file location, issue, and user frames
+//!    ([`ToonifiedError::new`]).
+//! 4. **Format** as plain text, TOON, JSON, or colored output
+//!    ([`ToonifiedError::format_plain`], [`format_multi_toon`], [`format_json`], ...).
+//!
+//! [`BlockStream`] runs the split stage incrementally over unbounded input such
+//! as a [`Follow`]ed log file, and [`run_tee`] captures a command's output while
+//! passing it through.
+//!
+//! [`decode_toon`] reads TOON output back into [`ToonifiedError`] values.
+//!
+//! [`FileErrors`] and the `format_files_*` formatters label errors by the file
+//! they came from; [`expand_glob`] resolves file patterns.
+//!
+//! [`toonify`] runs stages 1–3 in one call:
+//!
+//! ```
+//! let errors = error_toon::toonify("TypeError: foo is not a function\n    at App (src/App.tsx:12:5)");
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].error_type.name(), "TYPE_ERROR");
+//! assert_eq!(errors[0].file_location.as_deref(), Some("App.tsx:12"));
+//! ```
+
+mod detect;
+mod error_type;
+mod extract;
+mod follow;
+pub mod format;
+mod frame;
+mod glob;
+pub mod ingest;
+mod model;
+mod patterns;
+mod run;
+mod split;
+mod stream;
+pub mod tokenizer;
+pub mod toon;
+mod util;
+mod watch;
+
+pub use detect::{classify, detect_candidates, detect_error_type, Classification};
+pub use error_type::ErrorType;
+pub use extract::{extract_details, extract_file_location, extract_issue, extract_user_frames};
+pub use follow::{Follow, DEFAULT_POLL_INTERVAL};
+pub use format::{
+    decode_toon, format_files_colored_for, format_files_json, format_files_plain, format_files_toon, format_json,
+    format_multi_colored, format_multi_colored_for, format_multi_plain, format_multi_toon, format_multi_toon_with,
+    CompressionStats, DecodedToon, NdjsonWriter, Original,
+};
+pub use frame::{parse_frame, simplify_location};
+pub use glob::{expand_glob, is_glob};
+pub use model::{FileErrors, ToonifiedError};
+pub use run::{run_tee, Captured};
+pub use split::{is_error_boundary, is_stack_frame_line, split_into_error_blocks, BlockSplitter};
+pub use stream::{BlockStream, DEFAULT_IDLE_TIMEOUT};
+pub use tokenizer::Tokenizer;
+pub use watch::{ClipboardWatcher, Replacement, WatchFormat};
+
+/// Split `input` into error blocks and compress every block with a recognizable error type.
+///
+/// Blocks that match no [`ErrorType`] are dropped; the returned errors keep the
+/// order of [`split_into_error_blocks`].
+pub fn toonify(input: &str) -> Vec<ToonifiedError> {
+    split_into_error_blocks(input)
+        .iter()
+        .filter_map(|block| classify(block).map(|c| ToonifiedError::classified(block, c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Integration Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn full_dom_nesting_error_processing() {
+        let input = r#"Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.
+    at p
+    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
+    at Dashboard (webpack-internal:///./src/pages/Dashboard.tsx:45:23)
+    at App (webpack-internal:///./src/App.tsx:18:42)"#;
+
+        let error_type = detect_error_type(input).unwrap();
+        assert!(matches!(error_type, ErrorType::DomNesting));
+
+        let result = ToonifiedError::new(input, error_type);
+        assert_eq!(result.file_location, Some("Dashboard.tsx:45".to_string()));
+        assert_eq!(result.issue, Some("<p> cannot appear as a descendant of <p>".to_string()));
+
+        let output = result.format_plain();
+        assert!(output.contains("type: DOM_NESTING"));
+        assert!(output.contains("file: Dashboard.tsx:45"));
+        assert!(output.contains("<p> cannot appear as a descendant of <p>"));
+    }
+
+    #[test]
+    fn full_hydration_error_processing() {
+        let input = r#"Uncaught Error: Hydration failed because the initial UI does not match what was rendered on the server.
+    at throwOnHydrationMismatch (webpack-internal:///./node_modules/react-dom/index.js:12507:9)
+    at BlogPost (webpack-internal:///./src/components/BlogPost.tsx:23:18)
+    at Layout (webpack-internal:///./src/components/Layout.tsx:45:12)"#;
+
+        let error_type = detect_error_type(input).unwrap();
+        assert!(matches!(error_type, ErrorType::Hydration));
+
+        let result = ToonifiedError::new(input, error_type);
+        assert_eq!(result.file_location, Some("BlogPost.tsx:23".to_string()));
+        assert!(result.issue.as_ref().unwrap().contains("Hydration failed"));
+    }
+
+    #[test]
+    fn compression_ratio_calculated_correctly() {
+        // Use a realistic verbose error (like from browser console)
+        let long_input = format!(
+            "TypeError: Cannot read properties of undefined\n{}",
+            (0..50).map(|i| format!("    at function{} (webpack-internal:///./node_modules/react/index.js:{}:10)", i, i * 100))
+                   .collect::<Vec<_>>()
+                   .join("\n")
+        );
+
+        let result = ToonifiedError::new(&long_input, ErrorType::TypeError);
+        let output = result.format_plain();
+
+        // Output should be much shorter than input for verbose stack traces
+        assert!(output.len() < long_input.len(), "Output ({}) should be shorter than input ({})", output.len(), long_input.len());
+        assert!(output.contains("saved)"));
+    }
+
+    #[test]
+    fn multi_error_detection() {
+        let input = "TypeError: a\n    at x.tsx:1\n\nReferenceError: b\n    at y.tsx:2";
+        let blocks = split_into_error_blocks(input);
+        let errors: Vec<_> = blocks.iter()
+            .filter_map(|b| detect_error_type(b).map(|t| ToonifiedError::new(b, t)))
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_type, ErrorType::TypeError);
+        assert_eq!(errors[1].error_type, ErrorType::RefError);
+    }
+}
@@ -1,24 +1,21 @@
 use arboard::Clipboard;
-use clap::Parser;
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use colored::*;
-use once_cell::sync::Lazy;
-use regex::Regex;
-use std::io::{self, IsTerminal, Read};
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Constants
-// ─────────────────────────────────────────────────────────────────────────────
-
-const BOX_WIDTH: usize = 43;
-const CONTENT_WIDTH: usize = BOX_WIDTH - 4; // Account for "│ " and " │"
-const TRUNCATE_WIDTH: usize = CONTENT_WIDTH - 3; // Account for "..."
-const SOURCE_EXTENSIONS: &str = r"mdx|tsx|jsx|ts|js|vue|svelte";
-
-// Stats line overhead estimates for multi-error output
-// Format: "compressed: Nc → Nc (N% saved, N errors)" ≈ 50 chars max
-const MULTI_ERROR_PLAIN_STATS_OVERHEAD: usize = 50;
-// Format: "stats{orig,comp,pct,count}: N,N,N,N" ≈ 60 chars max
-const MULTI_ERROR_TOON_STATS_OVERHEAD: usize = 60;
+use error_toon::{
+    classify, decode_toon, ClipboardWatcher, WatchFormat, expand_glob, format_files_colored_for, format_files_json, format_files_plain,
+    format_files_toon, format_json, format_multi_colored_for, format_multi_plain, format_multi_toon_with,
+    ingest::{self, IngestError},
+    run_tee, toon::Delimiter, toonify, BlockStream, FileErrors, Follow, NdjsonWriter, Original, Tokenizer, ToonifiedError,
+    DEFAULT_IDLE_TIMEOUT,
+};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Cursor, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command as Process};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // CLI
@@ -26,2247 +23,586 @@ const MULTI_ERROR_TOON_STATS_OVERHEAD: usize = 60;
 
 #[derive(Parser)]
 #[command(name = "error-toon", version, about = "Compress verbose browser errors for LLM consumption")]
+#[command(group(ArgGroup::new("structured").args(["har", "cdp", "playwright_report", "junit", "sentry", "json_logs"]).multiple(false).conflicts_with_all(["files", "follow", "stream"])))]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Files or glob patterns to read instead of stdin/clipboard; output is labeled per file
+    #[arg(value_name = "FILES", conflicts_with_all = ["stream", "ndjson", "follow"])]
+    files: Vec<String>,
+
     /// Don't copy result to clipboard (copies by default)
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_copy: bool,
 
     /// Plain output (no colors)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     plain: bool,
 
     /// Output in TOON format (Token-Oriented Object Notation)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     toon: bool,
-}
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Error Types
-// ─────────────────────────────────────────────────────────────────────────────
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum ErrorType {
-    // DOM/React errors
-    DomNesting,
-    Hydration,
-    ReactMinified,
-    InvalidHook,
-    ReactKey,
-    // JavaScript errors
-    TypeError,
-    RefError,
-    SyntaxError,
-    RangeError,
-    UriError,
-    EvalError,
-    // Network errors
-    CorsError,
-    NetworkError,
-    HttpError,
-    WebSocketError,
-    // Security errors
-    CspError,
-    SecurityError,
-    MixedContent,
-    // Build tool / Testing errors
-    Storybook,
-    NextJs,
-    ModuleNotFound,
-    Playwright,
-    // System/Node errors
-    SystemError,
-    // Promise errors
-    UnhandledRejection,
-    // Browser API errors
-    MediaError,
-    IndexedDbError,
-    ServiceWorker,
-    // Deprecation/warnings
-    Deprecation,
-    // Catch-all
-    RuntimeError,
-}
+    /// Delimiter for TOON arrays and tables
+    #[arg(long, value_enum, default_value_t = DelimiterArg::Comma, global = true)]
+    delimiter: DelimiterArg,
 
-impl ErrorType {
-    // Order matters! More specific patterns first, RuntimeError (catch-all) last
-    const ALL: &'static [ErrorType] = &[
-        // DOM/React (most specific first)
-        Self::DomNesting,
-        Self::Hydration,
-        Self::ReactMinified,
-        Self::InvalidHook,
-        Self::ReactKey,
-        // Security errors (MixedContent before SecurityError - more specific)
-        Self::CorsError,
-        Self::CspError,
-        Self::MixedContent,      // Must come before SecurityError (contains "insecure")
-        Self::SecurityError,
-        // Browser APIs (ServiceWorker before HttpError - may contain status codes)
-        Self::ServiceWorker,
-        Self::MediaError,
-        Self::IndexedDbError,
-        // Network errors
-        Self::NetworkError,
-        Self::WebSocketError,
-        Self::HttpError,         // After ServiceWorker (SW errors may contain HTTP codes)
-        // Build tools / Testing
-        Self::Playwright,        // Before Storybook (more specific patterns)
-        Self::Storybook,
-        Self::NextJs,
-        Self::ModuleNotFound,
-        // Promise errors (before JS errors - may contain TypeError text)
-        Self::UnhandledRejection,
-        // JavaScript errors
-        Self::TypeError,
-        Self::RefError,
-        Self::SyntaxError,
-        Self::RangeError,
-        Self::UriError,
-        Self::EvalError,
-        // System errors
-        Self::SystemError,
-        // Warnings
-        Self::Deprecation,
-        // Catch-all (must be last)
-        Self::RuntimeError,
-    ];
-
-    fn name(&self) -> &'static str {
-        match self {
-            Self::DomNesting => "DOM_NESTING",
-            Self::Hydration => "HYDRATION",
-            Self::ReactMinified => "REACT_MINIFIED",
-            Self::InvalidHook => "INVALID_HOOK",
-            Self::ReactKey => "REACT_KEY",
-            Self::TypeError => "TYPE_ERROR",
-            Self::RefError => "REF_ERROR",
-            Self::SyntaxError => "SYNTAX_ERROR",
-            Self::RangeError => "RANGE_ERROR",
-            Self::UriError => "URI_ERROR",
-            Self::EvalError => "EVAL_ERROR",
-            Self::CorsError => "CORS_ERROR",
-            Self::NetworkError => "NETWORK_ERROR",
-            Self::HttpError => "HTTP_ERROR",
-            Self::WebSocketError => "WEBSOCKET_ERROR",
-            Self::CspError => "CSP_ERROR",
-            Self::SecurityError => "SECURITY_ERROR",
-            Self::MixedContent => "MIXED_CONTENT",
-            Self::Storybook => "STORYBOOK",
-            Self::NextJs => "NEXTJS",
-            Self::ModuleNotFound => "MODULE_NOT_FOUND",
-            Self::Playwright => "PLAYWRIGHT",
-            Self::SystemError => "SYSTEM_ERROR",
-            Self::UnhandledRejection => "UNHANDLED_REJECTION",
-            Self::MediaError => "MEDIA_ERROR",
-            Self::IndexedDbError => "INDEXEDDB_ERROR",
-            Self::ServiceWorker => "SERVICE_WORKER",
-            Self::Deprecation => "DEPRECATION",
-            Self::RuntimeError => "RUNTIME_ERROR",
-        }
-    }
+    /// Output as JSON (array of errors followed by a stats object)
+    #[arg(short, long, conflicts_with = "toon", global = true)]
+    json: bool,
 
-    fn color(&self) -> Color {
-        match self {
-            // Warnings (yellow)
-            Self::DomNesting | Self::Deprecation | Self::ReactKey => Color::Yellow,
-            // React/Hydration (magenta)
-            Self::Hydration | Self::ReactMinified | Self::InvalidHook => Color::Magenta,
-            // Build tools / Testing (cyan)
-            Self::Storybook | Self::NextJs | Self::ModuleNotFound | Self::Playwright => Color::Cyan,
-            // Network (blue)
-            Self::NetworkError | Self::HttpError | Self::WebSocketError => Color::Blue,
-            // Security (bright red)
-            Self::CorsError | Self::CspError | Self::SecurityError | Self::MixedContent => Color::BrightRed,
-            // All other errors (red)
-            _ => Color::Red,
-        }
-    }
+    /// Stream newline-delimited JSON: one error per line as soon as it is complete, stats last
+    #[arg(long, conflicts_with_all = ["toon", "json"], global = true)]
+    ndjson: bool,
 
-    fn icon(&self) -> &'static str {
-        match self {
-            Self::DomNesting => "󰅖",
-            Self::Hydration | Self::ReactMinified | Self::InvalidHook | Self::ReactKey => "󰜈",
-            Self::Storybook => "󰂺",
-            Self::NextJs => "󰔶",
-            Self::Playwright => "󰙨",
-            Self::CorsError | Self::CspError | Self::SecurityError | Self::MixedContent => "󰒃",
-            Self::NetworkError | Self::HttpError => "󰖟",
-            Self::WebSocketError => "󱄙",
-            Self::ModuleNotFound => "󰏗",
-            Self::SystemError => "",
-            Self::UnhandledRejection => "󰜺",
-            Self::MediaError => "󰎁",
-            Self::IndexedDbError => "󰆼",
-            Self::ServiceWorker => "󰖟",
-            Self::Deprecation => "󰀦",
-            _ => "",
-        }
-    }
+    /// Print each error as soon as its block is complete (e.g. `tail -f app.log | error-toon -s`)
+    #[arg(short, long, conflicts_with = "json", global = true)]
+    stream: bool,
 
-    fn pattern(&self) -> &Regex {
-        match self {
-            Self::DomNesting => &PATTERNS.dom_nesting,
-            Self::Hydration => &PATTERNS.hydration,
-            Self::ReactMinified => &PATTERNS.react_minified,
-            Self::InvalidHook => &PATTERNS.invalid_hook,
-            Self::ReactKey => &PATTERNS.react_key,
-            Self::TypeError => &PATTERNS.type_error,
-            Self::RefError => &PATTERNS.ref_error,
-            Self::SyntaxError => &PATTERNS.syntax_error,
-            Self::RangeError => &PATTERNS.range_error,
-            Self::UriError => &PATTERNS.uri_error,
-            Self::EvalError => &PATTERNS.eval_error,
-            Self::CorsError => &PATTERNS.cors_error,
-            Self::NetworkError => &PATTERNS.network_error,
-            Self::HttpError => &PATTERNS.http_error,
-            Self::WebSocketError => &PATTERNS.websocket_error,
-            Self::CspError => &PATTERNS.csp_error,
-            Self::SecurityError => &PATTERNS.security_error,
-            Self::MixedContent => &PATTERNS.mixed_content,
-            Self::Storybook => &PATTERNS.storybook,
-            Self::NextJs => &PATTERNS.nextjs,
-            Self::ModuleNotFound => &PATTERNS.module_not_found,
-            Self::Playwright => &PATTERNS.playwright,
-            Self::SystemError => &PATTERNS.system_error,
-            Self::UnhandledRejection => &PATTERNS.unhandled_rejection,
-            Self::MediaError => &PATTERNS.media_error,
-            Self::IndexedDbError => &PATTERNS.indexeddb_error,
-            Self::ServiceWorker => &PATTERNS.service_worker,
-            Self::Deprecation => &PATTERNS.deprecation,
-            Self::RuntimeError => &PATTERNS.stack_trace,
-        }
-    }
-}
+    /// Follow a growing log file like `tail -F` (rotation and truncation included) and print each new error
+    #[arg(short, long, value_name = "FILE", conflicts_with = "json", global = true)]
+    follow: Option<PathBuf>,
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Patterns (compiled once at startup)
-// ─────────────────────────────────────────────────────────────────────────────
+    /// Read failed requests from a HAR file exported from the DevTools Network panel
+    #[arg(long, value_name = "FILE")]
+    har: Option<PathBuf>,
 
-static PATTERNS: Lazy<Patterns> = Lazy::new(Patterns::compile);
-
-struct Patterns {
-    // Detection - DOM/React
-    dom_nesting: Regex,
-    hydration: Regex,
-    react_minified: Regex,
-    invalid_hook: Regex,
-    react_key: Regex,
-    // Detection - JavaScript errors
-    type_error: Regex,
-    ref_error: Regex,
-    syntax_error: Regex,
-    range_error: Regex,
-    uri_error: Regex,
-    eval_error: Regex,
-    // Detection - Network
-    cors_error: Regex,
-    network_error: Regex,
-    http_error: Regex,
-    websocket_error: Regex,
-    // Detection - Security
-    csp_error: Regex,
-    security_error: Regex,
-    mixed_content: Regex,
-    // Detection - Build tools / Testing
-    storybook: Regex,
-    nextjs: Regex,
-    module_not_found: Regex,
-    playwright: Regex,
-    // Detection - System
-    system_error: Regex,
-    // Detection - Promise
-    unhandled_rejection: Regex,
-    // Detection - Browser APIs
-    media_error: Regex,
-    indexeddb_error: Regex,
-    service_worker: Regex,
-    // Detection - Warnings
-    deprecation: Regex,
-    // Detection - Catch-all
-    stack_trace: Regex,
-    // Extraction patterns
-    file_location: Regex,
-    dom_issue: Regex,
-    system_code: Regex,
-    storybook_code: Regex,
-    nextjs_code: Regex,
-    http_status: Regex,
-    user_frame: Regex,
-    framework_noise: Regex,
-    // TOON frame parsing patterns (pre-compiled for performance)
-    frame_at_name_loc: Regex,
-    frame_at_symbol_loc: Regex,
-    frame_name_at_loc: Regex,
-    location_file_line: Regex,
-}
+    /// Read Chrome DevTools Protocol events (`Runtime.exceptionThrown`, `Log.entryAdded`) from a JSON dump
+    #[arg(long, value_name = "FILE")]
+    cdp: Option<PathBuf>,
 
-impl Patterns {
-    fn compile() -> Self {
-        let ext = SOURCE_EXTENSIONS;
-        Self {
-            // Detection - DOM/React
-            dom_nesting: re(r"(?i)validateDOMNesting"),
-            hydration: re(r"(?i)hydrat(ion|e|ing).*(?:failed|mismatch|error)"),
-            react_minified: re(r"Minified React error #\d+|react\.production\.min\.js"),
-            invalid_hook: re(r"(?i)Invalid hook call|Rules of Hooks|rendered more hooks"),
-            react_key: re(r"(?i)Encountered two children with the same key|Keys should be unique|Non-unique keys may cause|unique .?key.? prop"),
-
-            // Detection - JavaScript errors (allow optional file:line prefix from browser console)
-            type_error: re(r"(?m)^(?:\S+:\d+\s+)?TypeError:|Uncaught TypeError"),
-            ref_error: re(r"(?m)^(?:\S+:\d+\s+)?ReferenceError:|Uncaught ReferenceError"),
-            syntax_error: re(r"(?m)^(?:\S+:\d+\s+)?SyntaxError:|Uncaught SyntaxError"),
-            range_error: re(r"(?m)^(?:\S+:\d+\s+)?RangeError:|Uncaught RangeError"),
-            uri_error: re(r"(?m)^(?:\S+:\d+\s+)?URIError:|Uncaught URIError"),
-            eval_error: re(r"(?m)^(?:\S+:\d+\s+)?EvalError:|Uncaught EvalError"),
-
-            // Detection - Network
-            cors_error: re(r"(?i)CORS|Access-Control-Allow-Origin|blocked by CORS|cross-origin"),
-            network_error: re(r"(?i)Failed to fetch|NetworkError|net::ERR_|NS_ERROR_|fetch.*failed"),
-            // HTTP errors: "GET /api 404" or "status: 500" but NOT "bundle.js:45892"
-            http_error: re(r"(?i)\b(GET|POST|PUT|DELETE|PATCH)\s+\S+\s+[45]\d{2}\b|status[:\s]+[45]\d{2}\b|\b[45]\d{2}\s+(Not Found|Internal Server|Bad Request|Unauthorized|Forbidden)"),
-            websocket_error: re(r"(?i)WebSocket.*(?:error|failed|closed)|ws://.*error|wss://.*error"),
-
-            // Detection - Security
-            csp_error: re(r"(?i)Content-Security-Policy|CSP|blocked.*policy|violat.*directive"),
-            security_error: re(r"(?i)SecurityError|security.*violation|insecure|blocked.*security"),
-            mixed_content: re(r"(?i)Mixed Content|blocked.*insecure|http://.*https://"),
-
-            // Detection - Build tools / Testing
-            storybook: re(r"SB_"),
-            nextjs: re(r"(?i)NEXT_|getServerSideProps|getStaticProps|NextJS|next/"),
-            module_not_found: re(r"(?i)Module not found|Cannot find module|Cannot resolve|ModuleNotFoundError"),
-            playwright: re(r"(?i)locator\.(click|fill|waitFor|check|press|type|hover)|page\.(goto|waitFor|click)|expect\(.*\)\.(toBeVisible|toHaveText|toBeEnabled|toBeChecked|toContainText)|TimeoutError.*locator|waiting for locator|strict mode violation|playwright|@playwright/test"),
-
-            // Detection - System
-            system_error: re(r"ENOENT|EACCES|ECONNREFUSED|ETIMEDOUT|EADDRINUSE|EPERM"),
-
-            // Detection - Promise
-            unhandled_rejection: re(r"(?i)Unhandled.*rejection|UnhandledPromiseRejection|promise.*reject"),
-
-            // Detection - Browser APIs
-            media_error: re(r"(?i)MediaError|NotSupportedError.*media|play\(\).*failed|autoplay.*blocked"),
-            indexeddb_error: re(r"(?i)IndexedDB|IDBDatabase|QuotaExceededError|VersionError"),
-            service_worker: re(r"(?i)ServiceWorker|service.*worker.*(?:error|failed)|SW.*(?:error|failed)"),
-
-            // Detection - Warnings
-            deprecation: re(r"(?i)deprecated|deprecation|will be removed|no longer supported"),
-
-            // Detection - Catch-all (must be very generic)
-            stack_trace: re(r"at .* \(.*:\d+:\d+\)|Error:.*\n.*at\s"),
-
-            // Extraction patterns
-            file_location: re(&format!(r"[A-Za-z0-9_.-]+\.({ext}):\d+")),
-            dom_issue: re(r"<[a-z]+> cannot (?:appear as a |be a )?descendant of <[a-z]+>"),
-            system_code: re(r"E[A-Z]+:[^\n]*"),
-            storybook_code: re(r"SB_[A-Z_]+[^\n]*"),
-            nextjs_code: re(r"NEXT_[A-Z_]+|(?:getServerSideProps|getStaticProps)[^\n]*error"),
-            http_status: re(r"\b[45]\d{2}\b"),
-            user_frame: re(&format!(r"(@|at ).+\.({ext}):\d+")),
-            framework_noise: re(r"chunk-|node_modules|storybook_internal|webpack|vite|/internal|react-dom"),
-
-            // TOON frame parsing (pre-compiled for hot path performance)
-            frame_at_name_loc: re(r"at\s+(\w+)\s*\(([^)]+)\)"),
-            frame_at_symbol_loc: re(r"@\s*(\w+)\s*\(([^)]+)\)"),
-            frame_name_at_loc: re(r"(\w+)\s*@\s*(.+)"),
-            location_file_line: re(r"([^/]+\.[a-z]+):(\d+)(?::\d+)?$"),
-        }
-    }
-}
+    /// Read failed and flaky tests from a Playwright JSON report (`--reporter=json`)
+    #[arg(long, value_name = "FILE")]
+    playwright_report: Option<PathBuf>,
 
-fn re(pattern: &str) -> Regex {
-    Regex::new(pattern).expect("Invalid regex pattern")
-}
+    /// Read failing test cases from a JUnit XML report (Jest, Vitest, pytest, ...)
+    #[arg(long, value_name = "FILE")]
+    junit: Option<PathBuf>,
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Detection
-// ─────────────────────────────────────────────────────────────────────────────
+    /// Read Sentry event JSON; user frames are the ones Sentry marked `in_app`
+    #[arg(long, value_name = "FILE")]
+    sentry: Option<PathBuf>,
 
-fn detect_error_type(input: &str) -> Option<ErrorType> {
-    ErrorType::ALL
-        .iter()
-        .find(|t| t.pattern().is_match(input))
-        .copied()
-}
+    /// Read errors from JSON-lines logs (pino, winston, bunyan), keeping level and timestamp
+    #[arg(long, value_name = "FILE")]
+    json_logs: Option<PathBuf>,
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Multi-Error Splitting
-// ─────────────────────────────────────────────────────────────────────────────
+    /// When streaming, flush a pending error after this many milliseconds without new input (0 = wait for the next error or EOF)
+    #[arg(long, value_name = "MS", default_value_t = DEFAULT_IDLE_TIMEOUT.as_millis() as u64, global = true)]
+    idle_timeout: u64,
 
-/// Check if a line is a stack frame (starts with whitespace + "at " or "@")
-fn is_stack_frame_line(line: &str) -> bool {
-    let trimmed = line.trim_start();
-    // Lines that start with whitespace and contain "at " or "@" are stack frames
-    (line.starts_with(' ') || line.starts_with('\t'))
-        && (trimmed.starts_with("at ") || trimmed.starts_with("@ ") || trimmed.contains(" @ "))
+    /// Also report token counts using a BPE vocabulary: `cl100k`, `o200k`, or a path to a `.tiktoken` file
+    #[arg(long, value_name = "NAME|PATH", global = true)]
+    tokenizer: Option<String>,
 }
 
-/// Check if a line starts a new error (matches any `ErrorType` pattern and is not a stack frame)
-fn is_error_boundary(line: &str) -> bool {
-    // Stack frames are never boundaries
-    if is_stack_frame_line(line) {
-        return false;
-    }
-
-    let trimmed = line.trim();
-
-    // Empty or whitespace-only lines are not boundaries
-    if trimmed.is_empty() {
-        return false;
+impl Args {
+    fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout > 0).then(|| Duration::from_millis(self.idle_timeout))
     }
 
-    // Reuse existing patterns - same logic as detect_error_type()
-    // Note: Some patterns (like RuntimeError's stack_trace) use multiline matching
-    // which won't work on single lines, so we also check for generic error starts
-    if ErrorType::ALL.iter().any(|t| t.pattern().is_match(line)) {
-        return true;
+    /// Load the `--tokenizer` vocabulary, exiting with an error if it can't be found.
+    fn tokenizer(&self) -> Option<Tokenizer> {
+        let spec = self.tokenizer.as_deref()?;
+        Some(Tokenizer::load(spec).unwrap_or_else(|e| exit_with_error(&e.message)))
     }
-
-    // Additional check for generic error starts that multiline patterns miss
-    // e.g., "Error: Cannot update a component..."
-    trimmed.starts_with("Error:") ||
-    trimmed.starts_with("Uncaught Error:") ||
-    trimmed.starts_with("Warning:")
 }
 
-/// Split input containing multiple errors into separate error blocks.
-/// Each block contains one error with its associated stack frames.
-fn split_into_error_blocks(input: &str) -> Vec<String> {
-    let mut blocks = Vec::new();
-    let mut current_block = String::new();
+#[derive(Subcommand)]
+enum Command {
+    /// Read TOON output back and re-render it in the selected format
+    Decode {
+        /// TOON file to read (defaults to stdin, then clipboard)
+        file: Option<PathBuf>,
+    },
+    /// Run a command, showing its output live; if it fails, compress the errors it printed
+    Run {
+        /// Command and arguments, after `--` (e.g. `error-toon run -- npm test`)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+    /// Watch the clipboard and replace copied errors with their compressed form (plain, or TOON with -t)
+    Watch {
+        /// Milliseconds between clipboard checks
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        interval: u64,
 
-    for line in input.lines() {
-        let is_error_start = is_error_boundary(line);
+        /// Start paused (press Enter to resume)
+        #[arg(long)]
+        paused: bool,
+    },
+}
 
-        if is_error_start && !current_block.trim().is_empty() {
-            // Save previous block, start new one
-            blocks.push(current_block.trim().to_string());
-            current_block = String::new();
-        }
+#[derive(Clone, Copy, ValueEnum)]
+enum DelimiterArg {
+    Comma,
+    Tab,
+    Pipe,
+}
 
-        if !current_block.is_empty() {
-            current_block.push('\n');
+impl From<DelimiterArg> for Delimiter {
+    fn from(arg: DelimiterArg) -> Self {
+        match arg {
+            DelimiterArg::Comma => Delimiter::Comma,
+            DelimiterArg::Tab => Delimiter::Tab,
+            DelimiterArg::Pipe => Delimiter::Pipe,
         }
-        current_block.push_str(line);
     }
-
-    // Don't forget last block
-    if !current_block.trim().is_empty() {
-        blocks.push(current_block.trim().to_string());
-    }
-
-    blocks
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Extraction
+// Input
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn extract_file_location(input: &str) -> Option<String> {
-    // Prefer user code over node_modules/framework files
-    let all_matches: Vec<_> = PATTERNS.file_location.find_iter(input).collect();
-
-    // First try to find a user file (not in node_modules)
-    for m in &all_matches {
-        // Get the full line containing this match
-        let line_start = input[..m.start()].rfind('\n').map(|i| i + 1).unwrap_or(0);
-        let line_end = input[m.end()..].find('\n').map(|i| m.end() + i).unwrap_or(input.len());
-        let full_line = &input[line_start..line_end];
-
-        // Skip node_modules - user code is in src/, pages/, components/, etc.
-        if !full_line.contains("node_modules") {
-            return Some(m.as_str().to_string());
-        }
-    }
-
-    // Fallback to first match
-    all_matches.first().map(|m| m.as_str().to_string())
-}
-
-fn extract_issue(input: &str, error_type: ErrorType) -> Option<String> {
-    match error_type {
-        // DOM/React errors
-        ErrorType::DomNesting => extract_by_pattern_or_contains(input, &PATTERNS.dom_issue, "descendant"),
-        ErrorType::Hydration => find_line_containing(input, &["hydration", "mismatch", "server", "client"]),
-        ErrorType::ReactMinified => find_line_containing(input, &["Minified React error", "react.production"]),
-        ErrorType::InvalidHook => find_line_containing(input, &["Invalid hook", "Rules of Hooks", "rendered more hooks"]),
-        ErrorType::ReactKey => find_line_starting_with(input, &["Encountered two children with the same key"]),
-
-        // JavaScript errors
-        ErrorType::TypeError => find_line_starting_with(input, &["TypeError:", "Uncaught TypeError"]),
-        ErrorType::RefError => find_line_starting_with(input, &["ReferenceError:", "Uncaught ReferenceError"]),
-        ErrorType::SyntaxError => find_line_starting_with(input, &["SyntaxError:", "Uncaught SyntaxError"]),
-        ErrorType::RangeError => find_line_starting_with(input, &["RangeError:", "Uncaught RangeError"]),
-        ErrorType::UriError => find_line_starting_with(input, &["URIError:", "Uncaught URIError"]),
-        ErrorType::EvalError => find_line_starting_with(input, &["EvalError:", "Uncaught EvalError"]),
-
-        // Network errors
-        ErrorType::CorsError => find_line_containing(input, &["CORS", "Access-Control", "cross-origin", "blocked"]),
-        ErrorType::NetworkError => find_line_containing(input, &["Failed to fetch", "NetworkError", "net::ERR_", "fetch"]),
-        ErrorType::HttpError => extract_first_match(input, &PATTERNS.http_status)
-            .and_then(|status| find_line_containing(input, &[&status])),
-        ErrorType::WebSocketError => find_line_containing(input, &["WebSocket", "ws://", "wss://"]),
-
-        // Security errors
-        ErrorType::CspError => find_line_containing(input, &["Content-Security-Policy", "CSP", "directive", "violated"]),
-        ErrorType::SecurityError => find_line_containing(input, &["SecurityError", "security", "blocked"]),
-        ErrorType::MixedContent => find_line_containing(input, &["Mixed Content", "insecure", "http://"]),
-
-        // Build tools / Testing
-        ErrorType::Storybook => extract_first_match_truncated(input, &PATTERNS.storybook_code, 100),
-        ErrorType::NextJs => extract_first_match_truncated(input, &PATTERNS.nextjs_code, 100)
-            .or_else(|| find_line_containing(input, &["NEXT_", "getServerSideProps", "getStaticProps"])),
-        ErrorType::ModuleNotFound => find_line_containing(input, &["Module not found", "Cannot find module", "Cannot resolve"]),
-        ErrorType::Playwright => find_line_containing(input, &["TimeoutError", "locator", "expect(", "waiting for", "strict mode", "Timeout"]),
-
-        // System errors
-        ErrorType::SystemError => extract_first_match(input, &PATTERNS.system_code),
-
-        // Promise errors
-        ErrorType::UnhandledRejection => find_line_containing(input, &["Unhandled", "rejection", "promise"]),
-
-        // Browser API errors
-        ErrorType::MediaError => find_line_containing(input, &["MediaError", "play()", "autoplay", "media"]),
-        ErrorType::IndexedDbError => find_line_containing(input, &["IndexedDB", "IDBDatabase", "QuotaExceeded"]),
-        ErrorType::ServiceWorker => find_line_containing(input, &["ServiceWorker", "service worker", "SW"]),
-
-        // Warnings
-        ErrorType::Deprecation => find_line_containing(input, &["deprecated", "deprecation", "will be removed"]),
-
-        // Catch-all
-        ErrorType::RuntimeError => input.lines().next().map(str::to_string),
-    }
-}
-
-fn extract_user_frames(input: &str) -> Vec<String> {
-    input
-        .lines()
-        .filter(|line| PATTERNS.user_frame.is_match(line) && !PATTERNS.framework_noise.is_match(line))
-        .take(3)
-        .map(|s| s.trim().to_string())
-        .collect()
-}
-
-// Extraction helpers
-fn extract_by_pattern_or_contains(input: &str, pattern: &Regex, fallback_contains: &str) -> Option<String> {
-    pattern
-        .find(input)
-        .map(|m| m.as_str().to_string())
-        .or_else(|| find_line_containing(input, &[fallback_contains]))
-}
-
-fn extract_first_match(input: &str, pattern: &Regex) -> Option<String> {
-    pattern.find(input).map(|m| m.as_str().to_string())
-}
-
-fn extract_first_match_truncated(input: &str, pattern: &Regex, max_len: usize) -> Option<String> {
-    pattern.find(input).map(|m| truncate(m.as_str(), max_len))
-}
-
-fn find_line_containing(input: &str, needles: &[&str]) -> Option<String> {
-    input
-        .lines()
-        .find(|line| {
-            let lower = line.to_lowercase();
-            needles.iter().any(|n| lower.contains(&n.to_lowercase()))
-        })
-        .map(|s| s.trim().to_string())
-}
-
-fn find_line_starting_with(input: &str, prefixes: &[&str]) -> Option<String> {
-    // First try exact start-of-line match
-    if let Some(line) = input
-        .lines()
-        .find(|line| prefixes.iter().any(|p| line.starts_with(p)))
-    {
-        return Some(line.to_string());
+fn read_input() -> Result<String, &'static str> {
+    // If piped, read from stdin
+    if !io::stdin().is_terminal() {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|_| "Failed to read stdin")?;
+        return Ok(buf);
     }
 
-    // Fall back to finding pattern anywhere in line (browser console format: file.js:29 ErrorType:)
-    for line in input.lines() {
-        for prefix in prefixes {
-            if let Some(idx) = line.find(prefix) {
-                // Extract from the error type onwards
-                return Some(line[idx..].to_string());
+    // Try clipboard first
+    if let Ok(mut clipboard) = Clipboard::new() {
+        if let Ok(text) = clipboard.get_text() {
+            if !text.trim().is_empty() {
+                return Ok(text);
             }
         }
     }
-    None
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Output Model
-// ─────────────────────────────────────────────────────────────────────────────
-
-struct ToonifiedError {
-    error_type: ErrorType,
-    file_location: Option<String>,
-    issue: Option<String>,
-    frames: Vec<String>,
-    original_len: usize,
-}
 
-impl ToonifiedError {
-    fn new(input: &str, error_type: ErrorType) -> Self {
-        Self {
-            error_type,
-            file_location: extract_file_location(input),
-            issue: extract_issue(input, error_type),
-            frames: extract_user_frames(input),
-            original_len: input.len(),
-        }
-    }
+    // Clipboard empty - wait for user to paste
+    eprintln!("{}", "Clipboard empty. Paste error below, then press Ctrl+D:".yellow());
+    let mut buf = String::new();
+    io::stdin()
+        .read_to_string(&mut buf)
+        .map_err(|_| "Failed to read stdin")?;
+    Ok(buf)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Plain Formatter
+// Streaming
 // ─────────────────────────────────────────────────────────────────────────────
 
-impl ToonifiedError {
-    fn format_plain(&self) -> String {
-        let mut lines = vec![format!("type: {}", self.error_type.name())];
-
-        if let Some(ref loc) = self.file_location {
-            lines.push(format!("file: {}", loc));
-        }
-
-        if let Some(ref issue) = self.issue {
-            lines.push(format!("issue: {}", issue));
-        }
+/// Compress each error block of `reader` as soon as it completes and print it,
+/// as NDJSON or in the selected per-error format.
+fn stream(args: &Args, tokenizer: Option<&Tokenizer>, reader: impl BufRead + Send + 'static) -> io::Result<()> {
+    let mut blocks = BlockStream::new(reader).idle_timeout(args.idle_timeout());
+    let mut out = io::stdout().lock();
 
-        if !self.frames.is_empty() {
-            lines.push("frames:".to_string());
-            for frame in &self.frames {
-                lines.push(format!("  {}", frame));
+    if args.ndjson {
+        let mut writer = NdjsonWriter::new(out);
+        for block in blocks.by_ref() {
+            if let Some(error) = compress_block(&block?) {
+                writer.write_error(&error)?;
             }
         }
+        return writer.finish(blocks.chars_read()).map(drop);
+    }
 
-        // Calculate stats without recursion
-        let content = lines.join("\n");
-        let stats_overhead = 40; // Approximate size of stats line
-        let compressed_len = content.len() + stats_overhead;
-        let savings = if self.original_len > compressed_len {
-            ((self.original_len - compressed_len) * 100) / self.original_len
-        } else {
-            0
-        };
-
-        lines.push(String::new());
-        lines.push("---".to_string());
-        lines.push(format!("compressed: {}c → {}c ({}% saved)", self.original_len, compressed_len, savings));
-
-        lines.join("\n")
+    let plain = args.plain || !io::stdout().is_terminal();
+    for block in blocks {
+        let block = block?;
+        if let Some(error) = compress_block(&block) {
+            let original = Original::measure(&block, tokenizer);
+            let output = if args.toon {
+                error.format_toon_against(args.delimiter.into(), original)
+            } else if plain {
+                error.format_plain_against(original)
+            } else {
+                error.format_colored_against(&error.format_plain_against(original), original)
+            };
+            writeln!(out, "{}\n", output)?;
+            out.flush()?;
+        }
     }
+    Ok(())
+}
+
+fn compress_block(block: &str) -> Option<ToonifiedError> {
+    classify(block).map(|c| ToonifiedError::classified(block, c))
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// TOON Formatter (Token-Oriented Object Notation)
+// Main
 // ─────────────────────────────────────────────────────────────────────────────
 
-impl ToonifiedError {
-    fn format_toon(&self) -> String {
-        let mut lines = vec![format!("type: {}", self.error_type.name())];
-
-        if let Some(ref loc) = self.file_location {
-            lines.push(format!("file: {}", loc));
-        }
-
-        if let Some(ref issue) = self.issue {
-            // Escape commas in issue text for TOON compatibility
-            let escaped_issue = issue.replace(',', "\\,");
-            lines.push(format!("issue: {}", escaped_issue));
-        }
-
-        // TOON tabular array format for frames
-        if !self.frames.is_empty() {
-            let parsed_frames: Vec<(String, String)> = self.frames
-                .iter()
-                .map(|f| parse_frame(f))
-                .collect();
+fn main() {
+    let args = Args::parse();
 
-            lines.push(format!("frames[{}]{{fn,loc}}:", parsed_frames.len()));
-            for (func, loc) in parsed_frames {
-                lines.push(format!("  {},{}", func, loc));
-            }
-        }
+    if args.plain || args.json || args.ndjson || !io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
 
-        // Calculate compressed size using this format
-        let content = lines.join("\n");
-        let stats_line_len = 30; // Approximate stats line size
-        let compressed_len = content.len() + stats_line_len;
-        let savings = if self.original_len > compressed_len {
-            ((self.original_len - compressed_len) * 100) / self.original_len
-        } else {
-            0
-        };
+    if let Some(Command::Decode { file }) = &args.command {
+        decode(&args, file.as_ref());
+        return;
+    }
 
-        // TOON inline object format for stats
-        lines.push(format!("stats{{orig,comp,pct}}: {},{},{}", self.original_len, compressed_len, savings));
+    let tokenizer = args.tokenizer();
+    let tokenizer = tokenizer.as_ref();
 
-        lines.join("\n")
+    if let Some(Command::Run { command }) = &args.command {
+        process::exit(run(&args, tokenizer, command));
     }
-}
 
-/// Parse a stack frame string into (function_name, location)
-fn parse_frame(frame: &str) -> (String, String) {
-    // Common patterns:
-    // "at FunctionName (file.tsx:42:10)"
-    // "at FunctionName @ file.tsx:42"
-    // "@ FunctionName (file.tsx:42)"
-    // "FunctionName@file.tsx:42"
-
-    let frame = frame.trim();
-
-    // Try "at Name (loc)" pattern
-    if let Some(captures) = PATTERNS.frame_at_name_loc.captures(frame) {
-        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
-        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
-        return (func.to_string(), simplify_location(loc));
+    if let Some(Command::Watch { interval, paused }) = &args.command {
+        watch(&args, Duration::from_millis(*interval), *paused);
     }
 
-    // Try "@ Name (loc)" pattern
-    if let Some(captures) = PATTERNS.frame_at_symbol_loc.captures(frame) {
-        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
-        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
-        return (func.to_string(), simplify_location(loc));
+    if !args.files.is_empty() {
+        compress_files(&args, tokenizer);
+        return;
     }
 
-    // Try "Name @ loc" or "Name@loc" pattern
-    if let Some(captures) = PATTERNS.frame_name_at_loc.captures(frame) {
-        let func = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
-        let loc = captures.get(2).map(|m| m.as_str()).unwrap_or("");
-        return (func.to_string(), simplify_location(loc));
+    if let Some(path) = &args.har {
+        structured(&args, tokenizer, path, "failed requests", ingest::parse_har);
+        return;
     }
 
-    // Fallback: return as-is
-    (frame.to_string(), String::new())
-}
-
-/// Simplify a location path (extract filename:line from full URL/path)
-fn simplify_location(loc: &str) -> String {
-    // Extract just filename:line from paths like:
-    // "http://localhost:6006/path/to/file.tsx:42:10" -> "file.tsx:42"
-    // "/absolute/path/to/file.tsx:42:10" -> "file.tsx:42"
-
-    let loc = loc.trim();
-
-    // Try to extract filename:line:col or filename:line
-    if let Some(captures) = PATTERNS.location_file_line.captures(loc) {
-        let file = captures.get(1).map(|m| m.as_str()).unwrap_or(loc);
-        let line = captures.get(2).map(|m| m.as_str()).unwrap_or("");
-        return format!("{}:{}", file, line);
+    if let Some(path) = &args.cdp {
+        structured(&args, tokenizer, path, "exceptions or error log entries", ingest::parse_cdp);
+        return;
     }
 
-    loc.to_string()
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Colored Formatter
-// ─────────────────────────────────────────────────────────────────────────────
+    if let Some(path) = &args.playwright_report {
+        structured(&args, tokenizer, path, "failed or flaky tests", ingest::parse_playwright);
+        return;
+    }
 
-impl ToonifiedError {
-    fn format_colored(&self) -> String {
-        let color = self.error_type.color();
-        let mut box_lines = BoxBuilder::new(color);
+    if let Some(path) = &args.junit {
+        structured(&args, tokenizer, path, "failed tests", ingest::parse_junit);
+        return;
+    }
 
-        box_lines.header(&format!("{} {}", self.error_type.icon(), self.error_type.name()));
+    if let Some(path) = &args.sentry {
+        structured(&args, tokenizer, path, "exceptions", ingest::parse_sentry);
+        return;
+    }
 
-        if let Some(ref loc) = self.file_location {
-            box_lines.row(&format!(" {}", loc), Color::White);
-        }
+    if let Some(path) = &args.json_logs {
+        structured(&args, tokenizer, path, "error records", ingest::parse_json_logs);
+        return;
+    }
 
-        if let Some(ref issue) = self.issue {
-            box_lines.row(&format!(" {}", truncate(issue, TRUNCATE_WIDTH)), Color::Yellow);
+    if let Some(path) = &args.follow {
+        eprintln!("{}", format!("Following {} (Ctrl+C to stop)", path.display()).bright_black());
+        if let Err(e) = stream(&args, tokenizer, BufReader::new(Follow::new(path))) {
+            exit_with_error(&format!("Failed to follow {}: {}", path.display(), e));
         }
+        return;
+    }
 
-        if !self.frames.is_empty() {
-            box_lines.row("frames:", Color::BrightBlack);
-            for frame in &self.frames {
-                box_lines.row(&format!("  {}", truncate(frame, TRUNCATE_WIDTH - 2)), Color::Cyan);
+    if args.ndjson || args.stream {
+        // Piped stdin streams as it arrives; otherwise fall back to clipboard/paste
+        let result = if io::stdin().is_terminal() {
+            match read_input() {
+                Ok(s) => stream(&args, tokenizer, Cursor::new(s)),
+                Err(e) => exit_with_error(e),
             }
-        }
-
-        // Calculate stats (use plain format length)
-        let plain_len = self.format_plain().len();
-        let savings = if self.original_len > plain_len {
-            ((self.original_len - plain_len) * 100) / self.original_len
         } else {
-            0
+            stream(&args, tokenizer, BufReader::new(io::stdin()))
         };
-
-        box_lines.separator();
-        box_lines.row(&format!("📦 {}c → {}c ({}% saved)", self.original_len, plain_len, savings), Color::Green);
-
-        box_lines.build()
+        if let Err(e) = result {
+            exit_with_error(&format!("Failed to stream output: {}", e));
+        }
+        return;
     }
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Multi-Error Formatters
-// ─────────────────────────────────────────────────────────────────────────────
 
-/// Format multiple errors in plain format with separator between errors
-fn format_multi_plain(errors: &[ToonifiedError], total_original_len: usize) -> String {
-    let mut output_parts: Vec<String> = Vec::new();
+    let input = match read_input() {
+        Ok(s) if s.trim().is_empty() => exit_with_error("No input. Copy an error to clipboard or pipe it in."),
+        Ok(s) => s,
+        Err(e) => exit_with_error(e),
+    };
 
-    for (i, error) in errors.iter().enumerate() {
-        let mut lines = vec![format!("type: {}", error.error_type.name())];
+    // Split input into error blocks and compress each one
+    let results = toonify(&input);
 
-        if let Some(ref loc) = error.file_location {
-            lines.push(format!("file: {}", loc));
-        }
+    if results.is_empty() {
+        eprintln!("{}", "Not a recognizable error. Passing through.".yellow());
+        println!("{}", input);
+        return;
+    }
 
-        if let Some(ref issue) = error.issue {
-            lines.push(format!("issue: {}", issue));
-        }
+    emit(&args, &results, Original::measure(&input, tokenizer));
+}
 
-        if !error.frames.is_empty() {
-            lines.push("frames:".to_string());
-            for frame in &error.frames {
-                lines.push(format!("  {}", frame));
-            }
+/// Positional input: compress each file separately and print labeled sections
+fn compress_files(args: &Args, tokenizer: Option<&Tokenizer>) {
+    let mut paths = Vec::new();
+    for pattern in &args.files {
+        let matched = expand_glob(pattern)
+            .unwrap_or_else(|e| exit_with_error(&format!("Failed to expand {}: {}", pattern, e)));
+        if matched.is_empty() {
+            exit_with_error(&format!("No files match {}", pattern));
         }
+        paths.extend(matched);
+    }
 
-        if i < errors.len() - 1 {
-            lines.push(String::new());
+    let mut files = Vec::new();
+    for path in &paths {
+        let input = fs::read_to_string(path)
+            .unwrap_or_else(|e| exit_with_error(&format!("Failed to read {}: {}", path.display(), e)));
+        let errors = toonify(&input);
+        if errors.is_empty() {
+            eprintln!("{}", format!("No recognizable errors in {}. Skipping.", path.display()).yellow());
+            continue;
         }
+        files.push(FileErrors { name: path.display().to_string(), errors, original: Original::measure(&input, tokenizer) });
+    }
 
-        output_parts.push(lines.join("\n"));
+    if files.is_empty() {
+        exit_with_error("No recognizable errors in any file.");
     }
 
-    let content = output_parts.join("\n===\n");
-    let compressed_len = content.len() + MULTI_ERROR_PLAIN_STATS_OVERHEAD;
-    let savings = if total_original_len > compressed_len {
-        ((total_original_len - compressed_len) * 100) / total_original_len
+    let copyable_output = if args.json {
+        format_files_json(&files)
+    } else if args.toon {
+        format_files_toon(&files, args.delimiter.into())
     } else {
-        0
+        format_files_plain(&files)
     };
 
-    format!(
-        "{}\n\n---\ncompressed: {}c → {}c ({}% saved, {} errors)",
-        content, total_original_len, compressed_len, savings, errors.len()
-    )
+    let error_count = files.iter().map(|file| file.errors.len()).sum();
+    display_and_copy(args, &copyable_output, || format_files_colored_for(&files, &copyable_output), error_count);
 }
 
-/// Format multiple errors in TOON format
-fn format_multi_toon(errors: &[ToonifiedError], total_original_len: usize) -> String {
-    let mut lines = vec![format!("errors[{}]:", errors.len())];
+/// `run` subcommand: tee the command's output and compress it if the command fails,
+/// honoring `--ndjson`/`--stream` like piped input. Returns the child's exit code.
+fn run(args: &Args, tokenizer: Option<&Tokenizer>, command: &[String]) -> i32 {
+    // Keep stdout pure NDJSON by teeing the command's stdout to stderr
+    let tee_out: Box<dyn Write + Send> = if args.ndjson { Box::new(io::stderr()) } else { Box::new(io::stdout()) };
+    let captured = run_tee(Process::new(&command[0]).args(&command[1..]), tee_out, io::stderr())
+        .unwrap_or_else(|e| exit_with_error(&format!("Failed to run {}: {}", command[0], e)));
+    let code = captured.exit_code();
+    if captured.status.success() {
+        return code;
+    }
 
-    for error in errors {
-        lines.push("---".to_string());
-        lines.push(format!("type: {}", error.error_type.name()));
+    let results = toonify(&captured.output);
+    if results.is_empty() {
+        eprintln!("{}", format!("Command failed (exit {}); no recognizable errors in its output.", code).yellow());
+        return code;
+    }
 
-        if let Some(ref loc) = error.file_location {
-            lines.push(format!("file: {}", loc));
+    eprintln!("\n{}", format!("── error-toon: {} failed (exit {}) ──", command[0], code).red().bold());
+    if args.ndjson || args.stream {
+        if let Err(e) = stream(args, tokenizer, Cursor::new(captured.output)) {
+            exit_with_error(&format!("Failed to stream output: {}", e));
         }
+    } else {
+        emit(args, &results, Original::measure(&captured.output, tokenizer));
+    }
+    code
+}
 
-        if let Some(ref issue) = error.issue {
-            let escaped_issue = issue.replace(',', "\\,");
-            lines.push(format!("issue: {}", escaped_issue));
-        }
+/// `watch` subcommand: poll the clipboard and replace newly copied errors with
+/// their compressed form until interrupted. Enter toggles pause/resume.
+fn watch(args: &Args, interval: Duration, start_paused: bool) -> ! {
+    let mut clipboard = Clipboard::new().unwrap_or_else(|_| exit_with_error("Clipboard not available"));
+    let format = if args.toon { WatchFormat::Toon(args.delimiter.into()) } else { WatchFormat::Plain };
+    let mut watcher = ClipboardWatcher::new(format);
 
-        if !error.frames.is_empty() {
-            let parsed_frames: Vec<(String, String)> = error.frames
-                .iter()
-                .map(|f| parse_frame(f))
-                .collect();
+    // Only text copied from now on counts
+    if let Ok(text) = clipboard.get_text() {
+        watcher.skip(&text);
+    }
 
-            lines.push(format!("frames[{}]{{fn,loc}}:", parsed_frames.len()));
-            for (func, loc) in parsed_frames {
-                lines.push(format!("  {},{}", func, loc));
+    let paused = Arc::new(AtomicBool::new(start_paused));
+    {
+        let paused = Arc::clone(&paused);
+        thread::spawn(move || {
+            for _ in io::stdin().lock().lines() {
+                let now_paused = !paused.fetch_xor(true, Ordering::SeqCst);
+                let state = if now_paused { "Paused. Press Enter to resume." } else { "Resumed." };
+                eprintln!("{}", state.yellow());
             }
-        }
+        });
     }
 
-    // Add separator and stats placeholder to calculate final length
-    lines.push("===".to_string());
-
-    // Calculate compressed length (content + stats line)
-    let content_without_stats = lines.join("\n");
-    let compressed_len = content_without_stats.len() + MULTI_ERROR_TOON_STATS_OVERHEAD;
-    let savings = if total_original_len > compressed_len {
-        ((total_original_len - compressed_len) * 100) / total_original_len
-    } else {
-        0
-    };
-
-    lines.push(format!("stats{{orig,comp,pct,count}}: {},{},{},{}",
-        total_original_len, compressed_len, savings, errors.len()));
+    let state = if start_paused { "paused" } else { "watching" };
+    eprintln!("{}", format!("👀 Clipboard watch {} (Enter: pause/resume, Ctrl+C: quit)", state).bright_black());
 
-    lines.join("\n")
-}
-
-/// Format multiple errors in colored format (multiple boxes)
-fn format_multi_colored(errors: &[ToonifiedError], total_original_len: usize) -> String {
-    let mut boxes: Vec<String> = Vec::new();
-
-    for error in errors {
-        let color = error.error_type.color();
-        let mut box_lines = BoxBuilder::new(color);
-
-        box_lines.header(&format!("{} {}", error.error_type.icon(), error.error_type.name()));
-
-        if let Some(ref loc) = error.file_location {
-            box_lines.row(&format!(" {}", loc), Color::White);
-        }
+    let mut was_paused = start_paused;
+    loop {
+        thread::sleep(interval);
+        let is_paused = paused.load(Ordering::SeqCst);
+        let Ok(text) = clipboard.get_text() else {
+            continue;
+        };
 
-        if let Some(ref issue) = error.issue {
-            box_lines.row(&format!(" {}", truncate(issue, TRUNCATE_WIDTH)), Color::Yellow);
+        if is_paused || was_paused {
+            // Don't rewrite anything copied while paused
+            watcher.skip(&text);
+            was_paused = is_paused;
+            continue;
         }
 
-        if !error.frames.is_empty() {
-            box_lines.row("frames:", Color::BrightBlack);
-            for frame in &error.frames {
-                box_lines.row(&format!("  {}", truncate(frame, TRUNCATE_WIDTH - 2)), Color::Cyan);
+        if let Some(replacement) = watcher.check(&text) {
+            if clipboard.set_text(&replacement.text).is_err() {
+                eprintln!("{}", "⚠ Failed to write to clipboard".yellow());
+                continue;
             }
-        }
-
-        boxes.push(box_lines.build());
+            let types: Vec<&str> = replacement.errors.iter().map(|e| e.error_type.name()).collect();
+            let stats = replacement.stats;
+            eprintln!(
+                "{}",
+                format!(
+                    "📋 Compressed {}: {}c → {}c ({}% saved)",
+                    types.join(", "),
+                    stats.original_chars,
+                    stats.compressed_chars,
+                    stats.savings_pct()
+                )
+                .green()
+            );
+        }
+    }
+}
+
+/// Structured input (`--har`, ...): parse `path` with `parse` and print the errors
+fn structured(
+    args: &Args,
+    tokenizer: Option<&Tokenizer>,
+    path: &PathBuf,
+    what: &str,
+    parse: fn(&str) -> Result<Vec<ToonifiedError>, IngestError>,
+) {
+    let input = fs::read_to_string(path)
+        .unwrap_or_else(|e| exit_with_error(&format!("Failed to read {}: {}", path.display(), e)));
+    let errors = parse(&input).unwrap_or_else(|e| exit_with_error(&format!("Invalid {}: {}", path.display(), e)));
+    if errors.is_empty() {
+        eprintln!("{}", format!("No {} in {}.", what, path.display()).green());
+        return;
     }
 
-    // Calculate aggregate stats
-    let plain_output = format_multi_plain(errors, total_original_len);
-    let compressed_len = plain_output.len();
-    let savings = if total_original_len > compressed_len {
-        ((total_original_len - compressed_len) * 100) / total_original_len
-    } else {
-        0
-    };
-
-    let stats_line = format!(
-        "\n{} {} errors: {}c → {}c ({}% saved)",
-        "📦".green(),
-        errors.len(),
-        total_original_len,
-        compressed_len,
-        savings
-    ).green().to_string();
+    if args.ndjson {
+        let mut writer = NdjsonWriter::new(io::stdout().lock());
+        let result = errors
+            .iter()
+            .try_for_each(|error| writer.write_error(error))
+            .and_then(|_| writer.finish(input.chars().count()).map(drop));
+        if let Err(e) = result {
+            exit_with_error(&format!("Failed to write NDJSON: {}", e));
+        }
+        return;
+    }
 
-    format!("{}\n{}", boxes.join("\n\n"), stats_line)
+    emit(args, &errors, Original::measure(&input, tokenizer));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Box Drawing Helper
-// ─────────────────────────────────────────────────────────────────────────────
-
-struct BoxBuilder {
-    lines: Vec<String>,
-    color: Color,
-}
+/// `decode` subcommand: parse TOON and re-render the errors
+fn decode(args: &Args, file: Option<&PathBuf>) {
+    let input = match file {
+        Some(path) => fs::read_to_string(path)
+            .unwrap_or_else(|e| exit_with_error(&format!("Failed to read {}: {}", path.display(), e))),
+        None => read_input().unwrap_or_else(|e| exit_with_error(e)),
+    };
 
-impl BoxBuilder {
-    fn new(color: Color) -> Self {
-        Self { lines: vec![], color }
+    let decoded = decode_toon(&input).unwrap_or_else(|e| exit_with_error(&format!("Invalid TOON: {}", e)));
+    if decoded.errors.is_empty() {
+        exit_with_error("No errors in TOON input.");
     }
+    let total_original_len = decoded
+        .original_len
+        .unwrap_or_else(|| decoded.errors.iter().map(|e| e.original_len).sum());
 
-    fn header(&mut self, text: &str) {
-        self.lines.push(self.horizontal_line('╭', '╮'));
-        self.lines.push(self.content_line(text, self.color, true));
-        self.lines.push(self.horizontal_line('├', '┤'));
+    if args.ndjson {
+        let mut writer = NdjsonWriter::new(io::stdout().lock());
+        let result = decoded
+            .errors
+            .iter()
+            .try_for_each(|error| writer.write_error(error))
+            .and_then(|_| writer.finish(total_original_len).map(drop));
+        if let Err(e) = result {
+            exit_with_error(&format!("Failed to write NDJSON: {}", e));
+        }
+        return;
     }
 
-    fn row(&mut self, text: &str, content_color: Color) {
-        self.lines.push(self.content_line(text, content_color, false));
-    }
+    emit(args, &decoded.errors, Original::from(total_original_len));
+}
 
-    fn separator(&mut self) {
-        self.lines.push(self.horizontal_line('├', '┤'));
-    }
+/// Print results in the selected format and copy the copyable form to the clipboard
+fn emit(args: &Args, results: &[ToonifiedError], original: Original) {
+    // Select output format based on number of errors
+    let copyable_output = if args.json {
+        format_json(results, original)
+    } else if results.len() == 1 {
+        // Single error: use existing formatters for backward compatibility
+        if args.toon {
+            results[0].format_toon_against(args.delimiter.into(), original)
+        } else {
+            results[0].format_plain_against(original)
+        }
+    } else {
+        // Multiple errors: use multi-error formatters
+        if args.toon {
+            format_multi_toon_with(results, original, args.delimiter.into())
+        } else {
+            format_multi_plain(results, original)
+        }
+    };
 
-    fn build(mut self) -> String {
-        self.lines.push(self.horizontal_line('╰', '╯'));
-        self.lines.join("\n")
-    }
+    let colored = || {
+        if results.len() == 1 {
+            results[0].format_colored_against(&copyable_output, original)
+        } else {
+            format_multi_colored_for(results, original, &copyable_output)
+        }
+    };
+    display_and_copy(args, &copyable_output, colored, results.len());
+}
 
-    fn horizontal_line(&self, left: char, right: char) -> String {
-        format!(
-            "{}{}{}",
-            left.to_string().color(self.color),
-            "─".repeat(BOX_WIDTH - 2).color(self.color),
-            right.to_string().color(self.color)
-        )
+/// Print `copyable_output` (or the `colored` rendering on a terminal) and copy it to the clipboard
+fn display_and_copy(args: &Args, copyable_output: &str, colored: impl FnOnce() -> String, error_count: usize) {
+    // Display
+    if args.toon || args.json || args.plain || !io::stdout().is_terminal() {
+        println!("{}", copyable_output);
+    } else {
+        println!("{}", colored());
     }
 
-    fn content_line(&self, text: &str, content_color: Color, bold: bool) -> String {
-        let content = if bold {
-            text.color(content_color).bold().to_string()
+    // Copy to clipboard by default (unless --no-copy or piped output)
+    let should_copy = !args.no_copy && io::stdout().is_terminal();
+    if should_copy {
+        let format_name = if args.json {
+            "JSON"
+        } else if args.toon {
+            "TOON"
         } else {
-            text.color(content_color).to_string()
+            "plain"
         };
-
-        let visible_len = text.chars().count();
-        let padding = CONTENT_WIDTH.saturating_sub(visible_len);
-
-        format!(
-            "{} {}{} {}",
-            "│".color(self.color),
-            content,
-            " ".repeat(padding),
-            "│".color(self.color)
-        )
+        let count_info = if error_count > 1 {
+            format!(", {} errors", error_count)
+        } else {
+            String::new()
+        };
+        match Clipboard::new() {
+            Ok(mut clipboard) => match clipboard.set_text(copyable_output) {
+                Ok(_) => eprintln!("{}", format!("📋 Copied to clipboard ({}{})", format_name, count_info).green()),
+                Err(_) => eprintln!("{}", "⚠ Failed to write to clipboard".yellow()),
+            },
+            Err(_) => eprintln!("{}", "⚠ Clipboard not available".yellow()),
+        }
     }
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Utilities
-// ─────────────────────────────────────────────────────────────────────────────
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        return s.to_string();
-    }
-
-    let truncate_at = max_len.saturating_sub(3);
-
-    // Find nearest UTF-8 character boundary to avoid panic on multi-byte chars
-    let mut boundary = truncate_at.min(s.len());
-    while boundary > 0 && !s.is_char_boundary(boundary) {
-        boundary -= 1;
-    }
-
-    format!("{}...", &s[..boundary])
+fn exit_with_error(msg: &str) -> ! {
+    eprintln!("{} {}", "Error:".red().bold(), msg);
+    std::process::exit(1)
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Input
-// ─────────────────────────────────────────────────────────────────────────────
-
-fn read_input() -> Result<String, &'static str> {
-    // If piped, read from stdin
-    if !io::stdin().is_terminal() {
-        let mut buf = String::new();
-        io::stdin()
-            .read_to_string(&mut buf)
-            .map_err(|_| "Failed to read stdin")?;
-        return Ok(buf);
-    }
-
-    // Try clipboard first
-    if let Ok(mut clipboard) = Clipboard::new() {
-        if let Ok(text) = clipboard.get_text() {
-            if !text.trim().is_empty() {
-                return Ok(text);
-            }
-        }
-    }
-
-    // Clipboard empty - wait for user to paste
-    eprintln!("{}", "Clipboard empty. Paste error below, then press Ctrl+D:".yellow());
-    let mut buf = String::new();
-    io::stdin()
-        .read_to_string(&mut buf)
-        .map_err(|_| "Failed to read stdin")?;
-    Ok(buf)
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Main
-// ─────────────────────────────────────────────────────────────────────────────
-
-fn main() {
-    let args = Args::parse();
-
-    if args.plain || !io::stdout().is_terminal() {
-        colored::control::set_override(false);
-    }
-
-    let input = match read_input() {
-        Ok(s) if s.trim().is_empty() => exit_with_error("No input. Copy an error to clipboard or pipe it in."),
-        Ok(s) => s,
-        Err(e) => exit_with_error(e),
-    };
-
-    let total_original_len = input.len();
-
-    // Split input into separate error blocks
-    let blocks = split_into_error_blocks(&input);
-
-    // Process each block into a ToonifiedError
-    let results: Vec<ToonifiedError> = blocks
-        .iter()
-        .filter_map(|block| {
-            detect_error_type(block).map(|et| ToonifiedError::new(block, et))
-        })
-        .collect();
-
-    if results.is_empty() {
-        eprintln!("{}", "Not a recognizable error. Passing through.".yellow());
-        println!("{}", input);
-        return;
-    }
-
-    // Select output format based on number of errors
-    let copyable_output = if results.len() == 1 {
-        // Single error: use existing formatters for backward compatibility
-        if args.toon {
-            results[0].format_toon()
-        } else {
-            results[0].format_plain()
-        }
-    } else {
-        // Multiple errors: use multi-error formatters
-        if args.toon {
-            format_multi_toon(&results, total_original_len)
-        } else {
-            format_multi_plain(&results, total_original_len)
-        }
-    };
-
-    // Display
-    if args.toon || args.plain || !io::stdout().is_terminal() {
-        println!("{}", copyable_output);
-    } else if results.len() == 1 {
-        println!("{}", results[0].format_colored());
-    } else {
-        println!("{}", format_multi_colored(&results, total_original_len));
-    }
-
-    // Copy to clipboard by default (unless --no-copy or piped output)
-    let should_copy = !args.no_copy && io::stdout().is_terminal();
-    if should_copy {
-        let format_name = if args.toon { "TOON" } else { "plain" };
-        let count_info = if results.len() > 1 {
-            format!(", {} errors", results.len())
-        } else {
-            String::new()
-        };
-        match Clipboard::new() {
-            Ok(mut clipboard) => match clipboard.set_text(&copyable_output) {
-                Ok(_) => eprintln!("{}", format!("📋 Copied to clipboard ({}{})", format_name, count_info).green()),
-                Err(_) => eprintln!("{}", "⚠ Failed to write to clipboard".yellow()),
-            },
-            Err(_) => eprintln!("{}", "⚠ Clipboard not available".yellow()),
-        }
-    }
-}
-
-fn exit_with_error(msg: &str) -> ! {
-    eprintln!("{} {}", "Error:".red().bold(), msg);
-    std::process::exit(1)
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Tests
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Error Type Detection Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn detects_dom_nesting_error() {
-        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::DomNesting)));
-    }
-
-    #[test]
-    fn detects_dom_nesting_case_insensitive() {
-        let input = "Warning: VALIDATEDOMNESTING(...): error";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::DomNesting)));
-    }
-
-    #[test]
-    fn detects_hydration_error() {
-        let input = "Uncaught Error: Hydration failed because the initial UI does not match.";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Hydration)));
-    }
-
-    #[test]
-    fn detects_type_error() {
-        let input = "TypeError: Cannot read properties of undefined (reading 'map')";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::TypeError)));
-    }
-
-    #[test]
-    fn detects_reference_error() {
-        let input = "ReferenceError: myVariable is not defined";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::RefError)));
-    }
-
-    #[test]
-    fn detects_syntax_error() {
-        let input = "SyntaxError: Unexpected token '<'";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::SyntaxError)));
-    }
-
-    #[test]
-    fn detects_system_error_enoent() {
-        let input = "Error: ENOENT: no such file or directory, open '/path/to/file'";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::SystemError)));
-    }
-
-    #[test]
-    fn detects_system_error_econnrefused() {
-        let input = "Error: connect ECONNREFUSED 127.0.0.1:3000";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::SystemError)));
-    }
-
-    #[test]
-    fn detects_storybook_error() {
-        let input = "SB_PREVIEW_API_UNDEFINED: The preview API is not available.";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Storybook)));
-    }
-
-    #[test]
-    fn detects_runtime_error_with_stack_trace() {
-        let input = "Error: Something went wrong\n    at MyComponent (App.tsx:25:10)";
-        let result = detect_error_type(input);
-        // Should match RuntimeError due to stack trace pattern
-        assert!(result.is_some());
-    }
-
-    #[test]
-    fn returns_none_for_unrecognized_input() {
-        let input = "This is just some random text without any error patterns.";
-        let result = detect_error_type(input);
-        assert!(result.is_none());
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // New Error Type Detection Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn detects_react_minified_error() {
-        let input = "Minified React error #185; visit https://reactjs.org/docs/error-decoder.html";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::ReactMinified));
-    }
-
-    #[test]
-    fn detects_invalid_hook_error() {
-        let input = "Invalid hook call. Hooks can only be called inside of the body of a function component.";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::InvalidHook));
-    }
-
-    #[test]
-    fn detects_react_key_error() {
-        let input = "Encountered two children with the same key, `?path=/docs/foundation-related--docs`. Keys should be unique.";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::ReactKey));
-    }
-
-    #[test]
-    fn detects_react_key_error_verbose() {
-        let input = r#"DocPage.tsx:428 Encountered two children with the same key, `?path=/docs/foundation-related--docs`. Keys should be unique so that components maintain their identity across updates. Non-unique keys may cause children to be duplicated and/or omitted — the behavior is unsupported and could change in a future version.
-(anonymous) @ chunk-ZJ2MJDOW.js?v=9079ec11:4925
-runWithFiberInDEV @ chunk-ZJ2MJDOW.js?v=9079ec11:997"#;
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::ReactKey));
-    }
-
-    #[test]
-    fn extracts_react_key_issue() {
-        let input = "Encountered two children with the same key, `test-key`. Keys should be unique.";
-        let result = ToonifiedError::new(input, ErrorType::ReactKey);
-        assert!(result.issue.is_some());
-        assert!(result.issue.unwrap().contains("same key"));
-    }
-
-    #[test]
-    fn detects_cors_error() {
-        let input = "Access to XMLHttpRequest at 'https://api.example.com' from origin 'http://localhost:3000' has been blocked by CORS policy";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::CorsError));
-    }
-
-    #[test]
-    fn detects_network_error() {
-        let input = "TypeError: Failed to fetch";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::NetworkError));
-    }
-
-    #[test]
-    fn detects_network_error_chrome() {
-        let input = "net::ERR_CONNECTION_REFUSED";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::NetworkError));
-    }
-
-    #[test]
-    fn detects_http_error() {
-        let input = "GET https://api.example.com/users 404 (Not Found)";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::HttpError));
-    }
-
-    #[test]
-    fn detects_websocket_error() {
-        let input = "WebSocket connection to 'wss://example.com/socket' failed";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::WebSocketError));
-    }
-
-    #[test]
-    fn detects_csp_error() {
-        let input = "Refused to execute inline script because it violates the following Content-Security-Policy directive";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::CspError));
-    }
-
-    #[test]
-    fn detects_mixed_content_error() {
-        let input = "Mixed Content: The page at 'https://example.com' was loaded over HTTPS, but requested an insecure resource";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::MixedContent));
-    }
-
-    #[test]
-    fn detects_nextjs_error() {
-        let input = "Error: getServerSideProps should return an object";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::NextJs));
-    }
-
-    #[test]
-    fn detects_module_not_found_error() {
-        let input = "Module not found: Can't resolve './components/Button' in '/app/src'";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::ModuleNotFound));
-    }
-
-    #[test]
-    fn detects_range_error() {
-        let input = "RangeError: Maximum call stack size exceeded";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::RangeError));
-    }
-
-    #[test]
-    fn detects_unhandled_rejection() {
-        let input = "Unhandled Promise Rejection: TypeError: Cannot read property 'x' of undefined";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::UnhandledRejection));
-    }
-
-    #[test]
-    fn detects_media_error() {
-        let input = "DOMException: play() failed because the user didn't interact with the document first";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::MediaError));
-    }
-
-    #[test]
-    fn detects_indexeddb_error() {
-        let input = "QuotaExceededError: The IndexedDB quota has been exceeded";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::IndexedDbError));
-    }
-
-    #[test]
-    fn detects_service_worker_error() {
-        let input = "ServiceWorker registration failed: A bad HTTP response code (404) was received";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::ServiceWorker));
-    }
-
-    #[test]
-    fn detects_deprecation_warning() {
-        let input = "Warning: componentWillMount has been renamed, and is not recommended for use. This method will be deprecated in a future version.";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::Deprecation));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // JavaScript Errors with Browser Console Prefix
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn detects_syntax_error_with_file_prefix() {
-        // Browser console format: file.js:line ErrorType: message
-        let input = "vite-app.js:29 SyntaxError: The requested module '/src/stories/brand/BrandComponents.tsx' does not provide an export named 'NEUTRAL' (at 06-Iconography.mdx:7:49)";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::SyntaxError));
-    }
-
-    #[test]
-    fn detects_type_error_with_file_prefix() {
-        let input = "bundle.js:42 TypeError: Cannot read properties of undefined (reading 'map')";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::TypeError));
-    }
-
-    #[test]
-    fn detects_ref_error_with_file_prefix() {
-        let input = "app.js:100 ReferenceError: myVariable is not defined";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::RefError));
-    }
-
-    #[test]
-    fn detects_range_error_with_file_prefix() {
-        let input = "script.js:55 RangeError: Maximum call stack size exceeded";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::RangeError));
-    }
-
-    #[test]
-    fn detects_syntax_error_without_prefix() {
-        // Should still work without file prefix
-        let input = "SyntaxError: Unexpected token '<'";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::SyntaxError));
-    }
-
-    #[test]
-    fn detects_uncaught_type_error() {
-        // Uncaught variant should still work
-        let input = "Uncaught TypeError: foo is not a function";
-        let result = detect_error_type(input);
-        assert_eq!(result, Some(ErrorType::TypeError));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // File Location Extraction Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn extracts_simple_file_location() {
-        let input = "Error at MyComponent.tsx:42";
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("MyComponent.tsx:42".to_string()));
-    }
-
-    #[test]
-    fn prefers_user_code_over_node_modules() {
-        let input = r#"
-    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
-    at Dashboard (webpack-internal:///./src/pages/Dashboard.tsx:45:23)
-    at App (webpack-internal:///./node_modules/react-router/index.js:100:5)
-"#;
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("Dashboard.tsx:45".to_string()));
-    }
-
-    #[test]
-    fn falls_back_to_node_modules_if_no_user_code() {
-        let input = r#"
-    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
-    at Container (webpack-internal:///./node_modules/@mui/material/Container.js:55:12)
-"#;
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("CardContent.js:82".to_string()));
-    }
-
-    #[test]
-    fn extracts_mdx_file_location() {
-        let input = "Error in iOS-SafeArea-Guide.mdx:79";
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("iOS-SafeArea-Guide.mdx:79".to_string()));
-    }
-
-    #[test]
-    fn extracts_vue_file_location() {
-        let input = "Error at MyComponent.vue:123";
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("MyComponent.vue:123".to_string()));
-    }
-
-    #[test]
-    fn extracts_svelte_file_location() {
-        let input = "Error at App.svelte:42";
-        let result = extract_file_location(input);
-        assert_eq!(result, Some("App.svelte:42".to_string()));
-    }
-
-    #[test]
-    fn returns_none_when_no_file_location() {
-        let input = "Some error without file reference";
-        let result = extract_file_location(input);
-        assert!(result.is_none());
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Issue Extraction Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn extracts_dom_nesting_issue_appear_as() {
-        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.";
-        let result = extract_issue(input, ErrorType::DomNesting);
-        assert_eq!(result, Some("<p> cannot appear as a descendant of <p>".to_string()));
-    }
-
-    #[test]
-    fn extracts_dom_nesting_issue_be_a() {
-        let input = "In HTML, <div> cannot be a descendant of <p>.";
-        let result = extract_issue(input, ErrorType::DomNesting);
-        assert_eq!(result, Some("<div> cannot be a descendant of <p>".to_string()));
-    }
-
-    #[test]
-    fn extracts_hydration_issue() {
-        let input = "Uncaught Error: Hydration failed because the initial UI does not match.";
-        let result = extract_issue(input, ErrorType::Hydration);
-        assert_eq!(result, Some("Uncaught Error: Hydration failed because the initial UI does not match.".to_string()));
-    }
-
-    #[test]
-    fn extracts_type_error_issue() {
-        let input = "TypeError: Cannot read properties of undefined (reading 'map')\n    at Array.map";
-        let result = extract_issue(input, ErrorType::TypeError);
-        assert_eq!(result, Some("TypeError: Cannot read properties of undefined (reading 'map')".to_string()));
-    }
-
-    #[test]
-    fn extracts_system_error_code() {
-        let input = "Error: ENOENT: no such file or directory, open '/path/to/file'";
-        let result = extract_issue(input, ErrorType::SystemError);
-        assert_eq!(result, Some("ENOENT: no such file or directory, open '/path/to/file'".to_string()));
-    }
-
-    #[test]
-    fn extracts_storybook_code() {
-        let input = "SB_PREVIEW_API_UNDEFINED: The preview API is not available.";
-        let result = extract_issue(input, ErrorType::Storybook);
-        assert!(result.unwrap().starts_with("SB_PREVIEW_API_UNDEFINED"));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // User Frame Extraction Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn extracts_user_frames_filters_node_modules() {
-        let input = r#"
-    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
-    at Dashboard (./src/pages/Dashboard.tsx:45:23)
-    at App (./src/App.tsx:18:42)
-    at Router (webpack-internal:///./node_modules/react-router/index.js:100:5)
-"#;
-        let result = extract_user_frames(input);
-        assert_eq!(result.len(), 2);
-        assert!(result[0].contains("Dashboard.tsx"));
-        assert!(result[1].contains("App.tsx"));
-    }
-
-    #[test]
-    fn limits_user_frames_to_three() {
-        let input = r#"
-    at Component1 (./src/Component1.tsx:10:5)
-    at Component2 (./src/Component2.tsx:20:5)
-    at Component3 (./src/Component3.tsx:30:5)
-    at Component4 (./src/Component4.tsx:40:5)
-    at Component5 (./src/Component5.tsx:50:5)
-"#;
-        let result = extract_user_frames(input);
-        assert_eq!(result.len(), 3);
-    }
-
-    #[test]
-    fn returns_empty_when_no_user_frames() {
-        let input = "Some error without stack trace";
-        let result = extract_user_frames(input);
-        assert!(result.is_empty());
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Output Format Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn plain_format_includes_type() {
-        let input = "TypeError: test error";
-        let result = ToonifiedError::new(input, ErrorType::TypeError);
-        let output = result.format_plain();
-        assert!(output.contains("type: TYPE_ERROR"));
-    }
-
-    #[test]
-    fn plain_format_includes_file_when_present() {
-        let input = "Error at MyComponent.tsx:42";
-        let error_type = detect_error_type(input).unwrap_or(ErrorType::RuntimeError);
-        let result = ToonifiedError::new(input, error_type);
-        let output = result.format_plain();
-        assert!(output.contains("file: MyComponent.tsx:42"));
-    }
-
-    #[test]
-    fn plain_format_includes_compression_stats() {
-        let input = "TypeError: test error with some extra content to make it longer";
-        let result = ToonifiedError::new(input, ErrorType::TypeError);
-        let output = result.format_plain();
-        assert!(output.contains("compressed:"));
-        assert!(output.contains("saved)"));
-    }
-
-    #[test]
-    fn plain_format_omits_file_when_none() {
-        let input = "TypeError: test error";
-        let result = ToonifiedError::new(input, ErrorType::TypeError);
-        let output = result.format_plain();
-        assert!(!output.contains("file:"));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Utility Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn truncate_short_string_unchanged() {
-        let result = truncate("short", 10);
-        assert_eq!(result, "short");
-    }
-
-    #[test]
-    fn truncate_long_string_with_ellipsis() {
-        let result = truncate("this is a very long string", 10);
-        assert_eq!(result, "this is...");
-        assert_eq!(result.len(), 10);
-    }
-
-    #[test]
-    fn truncate_exact_length_unchanged() {
-        let result = truncate("exactly10!", 10);
-        assert_eq!(result, "exactly10!");
-    }
-
-    #[test]
-    fn truncate_handles_unicode_without_panic() {
-        // This would panic before the fix if truncation landed mid-character
-        let chinese = "错误：无法读取属性的值";
-        let result = truncate(chinese, 10);
-        assert!(result.ends_with("..."));
-        // Ensure we can iterate over chars (proves valid UTF-8)
-        assert!(result.chars().count() > 0);
-    }
-
-    #[test]
-    fn truncate_handles_emoji_without_panic() {
-        let emoji = "Error: 🔥🔥🔥🔥🔥 something failed";
-        let result = truncate(emoji, 15);
-        assert!(result.ends_with("..."));
-        assert!(result.chars().count() > 0);
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Integration Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn full_dom_nesting_error_processing() {
-        let input = r#"Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.
-    at p
-    at CardContent (webpack-internal:///./node_modules/@mui/material/CardContent.js:82:35)
-    at Dashboard (webpack-internal:///./src/pages/Dashboard.tsx:45:23)
-    at App (webpack-internal:///./src/App.tsx:18:42)"#;
-
-        let error_type = detect_error_type(input).unwrap();
-        assert!(matches!(error_type, ErrorType::DomNesting));
-
-        let result = ToonifiedError::new(input, error_type);
-        assert_eq!(result.file_location, Some("Dashboard.tsx:45".to_string()));
-        assert_eq!(result.issue, Some("<p> cannot appear as a descendant of <p>".to_string()));
-
-        let output = result.format_plain();
-        assert!(output.contains("type: DOM_NESTING"));
-        assert!(output.contains("file: Dashboard.tsx:45"));
-        assert!(output.contains("<p> cannot appear as a descendant of <p>"));
-    }
-
-    #[test]
-    fn full_hydration_error_processing() {
-        let input = r#"Uncaught Error: Hydration failed because the initial UI does not match what was rendered on the server.
-    at throwOnHydrationMismatch (webpack-internal:///./node_modules/react-dom/index.js:12507:9)
-    at BlogPost (webpack-internal:///./src/components/BlogPost.tsx:23:18)
-    at Layout (webpack-internal:///./src/components/Layout.tsx:45:12)"#;
-
-        let error_type = detect_error_type(input).unwrap();
-        assert!(matches!(error_type, ErrorType::Hydration));
-
-        let result = ToonifiedError::new(input, error_type);
-        assert_eq!(result.file_location, Some("BlogPost.tsx:23".to_string()));
-        assert!(result.issue.as_ref().unwrap().contains("Hydration failed"));
-    }
-
-    #[test]
-    fn compression_ratio_calculated_correctly() {
-        // Use a realistic verbose error (like from browser console)
-        let long_input = format!(
-            "TypeError: Cannot read properties of undefined\n{}",
-            (0..50).map(|i| format!("    at function{} (webpack-internal:///./node_modules/react/index.js:{}:10)", i, i * 100))
-                   .collect::<Vec<_>>()
-                   .join("\n")
-        );
-
-        let result = ToonifiedError::new(&long_input, ErrorType::TypeError);
-        let output = result.format_plain();
-
-        // Output should be much shorter than input for verbose stack traces
-        assert!(output.len() < long_input.len(), "Output ({}) should be shorter than input ({})", output.len(), long_input.len());
-        assert!(output.contains("saved)"));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Error Type Properties Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn error_type_names_are_uppercase() {
-        for error_type in ErrorType::ALL {
-            let name = error_type.name();
-            assert_eq!(name, name.to_uppercase(), "Error type name should be uppercase: {}", name);
-        }
-    }
-
-    #[test]
-    fn all_error_types_have_patterns() {
-        for error_type in ErrorType::ALL {
-            // Just ensure the pattern can be accessed without panic
-            let _ = error_type.pattern();
-        }
-    }
-
-    #[test]
-    fn error_types_icons_are_valid() {
-        // Some error types have icons, others use a default
-        // Just ensure the icon method doesn't panic
-        for error_type in ErrorType::ALL {
-            let _ = error_type.icon();
-        }
-
-        // Verify specific types have custom icons
-        assert!(!ErrorType::DomNesting.icon().is_empty());
-        assert!(!ErrorType::Hydration.icon().is_empty());
-        assert!(!ErrorType::Storybook.icon().is_empty());
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // TOON Format Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn toon_format_includes_type() {
-        let result = ToonifiedError::new("TypeError: foo is not a function", ErrorType::TypeError);
-        let output = result.format_toon();
-        assert!(output.contains("type: TYPE_ERROR"));
-    }
-
-    #[test]
-    fn toon_format_includes_stats_inline_object() {
-        let result = ToonifiedError::new("TypeError: test", ErrorType::TypeError);
-        let output = result.format_toon();
-        assert!(output.contains("stats{orig,comp,pct}:"));
-    }
-
-    #[test]
-    fn toon_format_frames_use_tabular_syntax() {
-        let input = "Error: test\n    at FunctionA (file.tsx:10:5)\n    at FunctionB (other.tsx:20:3)";
-        let result = ToonifiedError::new(input, ErrorType::RuntimeError);
-        let output = result.format_toon();
-        // Should have tabular array declaration
-        assert!(output.contains("frames[") && output.contains("]{fn,loc}:"));
-    }
-
-    #[test]
-    fn toon_format_no_separator_line() {
-        let result = ToonifiedError::new("TypeError: test", ErrorType::TypeError);
-        let output = result.format_toon();
-        // TOON format should NOT have the "---" separator
-        assert!(!output.contains("\n---\n"));
-    }
-
-    #[test]
-    fn toon_format_escapes_commas_in_issue() {
-        let input = "TypeError: foo, bar, baz are undefined";
-        let result = ToonifiedError::new(input, ErrorType::TypeError);
-        let output = result.format_toon();
-        // Commas in issue text should be escaped
-        assert!(output.contains("\\,") || !output.contains("issue: foo, bar"));
-    }
-
-    #[test]
-    fn parse_frame_handles_at_pattern() {
-        let (func, loc) = parse_frame("at MyComponent (http://localhost:3000/src/App.tsx:42:10)");
-        assert_eq!(func, "MyComponent");
-        assert_eq!(loc, "App.tsx:42");
-    }
-
-    #[test]
-    fn parse_frame_handles_at_symbol_pattern() {
-        let (func, loc) = parse_frame("@ MDXContent (Guide.mdx:79:5)");
-        assert_eq!(func, "MDXContent");
-        assert_eq!(loc, "Guide.mdx:79");
-    }
-
-    #[test]
-    fn simplify_location_extracts_filename_and_line() {
-        let loc = simplify_location("http://localhost:6006/node_modules/.cache/App.tsx:42:10");
-        assert_eq!(loc, "App.tsx:42");
-    }
-
-    #[test]
-    fn simplify_location_handles_simple_path() {
-        let loc = simplify_location("/Users/dev/project/src/Component.tsx:100:5");
-        assert_eq!(loc, "Component.tsx:100");
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // UTF-8 Truncation Edge Cases
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn truncate_multi_byte_chars_at_boundary() {
-        // Japanese: each char is 3 bytes
-        let japanese = "エラーメッセージ";
-        let result = truncate(japanese, 12);
-        // Should not panic and should produce valid UTF-8
-        assert!(result.ends_with("..."));
-        for c in result.chars() {
-            assert!(c.len_utf8() > 0);
-        }
-    }
-
-    #[test]
-    fn truncate_mixed_ascii_and_unicode() {
-        let mixed = "Error: 错误 in module";
-        let result = truncate(mixed, 15);
-        assert!(result.ends_with("..."));
-        assert!(result.chars().count() > 0);
-    }
-
-    #[test]
-    fn truncate_empty_string() {
-        let result = truncate("", 10);
-        assert_eq!(result, "");
-    }
-
-    #[test]
-    fn truncate_very_small_limit() {
-        let result = truncate("hello world", 3);
-        assert_eq!(result, "...");
-    }
-
-    #[test]
-    fn truncate_limit_zero() {
-        let result = truncate("hello", 0);
-        assert_eq!(result, "...");
-    }
-
-    #[test]
-    fn truncate_four_byte_emoji() {
-        // 🔥 is 4 bytes
-        let emoji_string = "🔥🔥🔥🔥🔥";
-        let result = truncate(emoji_string, 10);
-        assert!(result.ends_with("..."));
-        // Verify it's valid UTF-8 by iterating chars
-        let char_count = result.chars().count();
-        assert!(char_count > 0);
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Frame Parsing Tests (Pre-compiled Regex)
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn parse_frame_webpack_path() {
-        let (func, loc) = parse_frame("at render (webpack://my-app/src/Component.tsx:42:10)");
-        assert_eq!(func, "render");
-        assert_eq!(loc, "Component.tsx:42");
-    }
-
-    #[test]
-    fn parse_frame_vite_path() {
-        let (func, loc) = parse_frame("at onClick (http://localhost:5173/src/App.tsx?t=123:15:3)");
-        assert_eq!(func, "onClick");
-        // Should extract the file and line, ignoring query params in some cases
-        assert!(loc.contains("App.tsx") || loc.contains("15"));
-    }
-
-    #[test]
-    fn parse_frame_name_at_symbol_format() {
-        let (func, loc) = parse_frame("MyFunction@/path/to/file.js:100:5");
-        assert_eq!(func, "MyFunction");
-        assert_eq!(loc, "file.js:100");
-    }
-
-    #[test]
-    fn parse_frame_anonymous_function() {
-        let (func, loc) = parse_frame("at anonymous (app.js:10:1)");
-        assert_eq!(func, "anonymous");
-        assert_eq!(loc, "app.js:10");
-    }
-
-    #[test]
-    fn parse_frame_no_match_returns_original() {
-        let (func, loc) = parse_frame("some random text without pattern");
-        assert_eq!(func, "some random text without pattern");
-        assert_eq!(loc, "");
-    }
-
-    #[test]
-    fn parse_frame_empty_string() {
-        let (func, loc) = parse_frame("");
-        assert_eq!(func, "");
-        assert_eq!(loc, "");
-    }
-
-    #[test]
-    fn parse_frame_whitespace_only() {
-        let (func, loc) = parse_frame("   ");
-        assert_eq!(func, "");
-        assert_eq!(loc, "");
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Location Simplification Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn simplify_location_webpack_chunk() {
-        let loc = simplify_location("webpack://app/./src/components/Button.tsx:25:8");
-        assert_eq!(loc, "Button.tsx:25");
-    }
-
-    #[test]
-    fn simplify_location_with_query_string() {
-        let loc = simplify_location("http://localhost:3000/src/App.tsx?v=123:42:10");
-        // May or may not handle query strings perfectly, but shouldn't panic
-        assert!(loc.contains("42") || loc.contains("App"));
-    }
-
-    #[test]
-    fn simplify_location_windows_path() {
-        // Windows paths use backslashes, but the regex looks for forward slashes
-        // So it returns the full path since pattern doesn't match cleanly
-        let loc = simplify_location("C:\\Users\\dev\\project\\src\\App.tsx:50:1");
-        // The regex captures from the last / or start, so with backslashes it gets more
-        assert!(loc.contains("App.tsx") && loc.contains("50"));
-    }
-
-    #[test]
-    fn simplify_location_no_column() {
-        let loc = simplify_location("/path/to/file.js:100");
-        assert_eq!(loc, "file.js:100");
-    }
-
-    #[test]
-    fn simplify_location_no_line_number() {
-        let loc = simplify_location("/path/to/file.js");
-        // Should return as-is since pattern doesn't match
-        assert_eq!(loc, "/path/to/file.js");
-    }
-
-    #[test]
-    fn simplify_location_empty_string() {
-        let loc = simplify_location("");
-        assert_eq!(loc, "");
-    }
-
-    #[test]
-    fn simplify_location_various_extensions() {
-        assert_eq!(simplify_location("/a/b.tsx:1:1"), "b.tsx:1");
-        assert_eq!(simplify_location("/a/b.jsx:2:2"), "b.jsx:2");
-        assert_eq!(simplify_location("/a/b.ts:3:3"), "b.ts:3");
-        assert_eq!(simplify_location("/a/b.js:4:4"), "b.js:4");
-        assert_eq!(simplify_location("/a/b.vue:5:5"), "b.vue:5");
-        assert_eq!(simplify_location("/a/b.svelte:6:6"), "b.svelte:6");
-    }
-
-    #[test]
-    fn toon_format_is_more_compact_than_plain() {
-        let input = "Warning: validateDOMNesting(...): <p> cannot appear as a descendant of <p>.\n    at p\n    at MDXContent (http://localhost:6006/Guide.mdx:79:10)\n    at Component (http://localhost:6006/App.tsx:18:5)";
-        let result = ToonifiedError::new(input, ErrorType::DomNesting);
-        let toon = result.format_toon();
-        let plain = result.format_plain();
-        // TOON format should generally be similar or smaller
-        // (may vary based on frame parsing overhead)
-        assert!(toon.len() <= plain.len() + 50, "TOON: {} chars, Plain: {} chars", toon.len(), plain.len());
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Playwright Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn detects_playwright_timeout_error() {
-        let input = "TimeoutError: locator.click: Timeout 30000ms exceeded.\n    waiting for locator('.nonexistent')";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn detects_playwright_assertion_error() {
-        let input = "Error: Timed out 5000ms waiting for expect(locator).toBeVisible()\n    Locator: locator('.button')";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn detects_playwright_strict_mode_error() {
-        let input = "Error: strict mode violation: locator('.button') resolved to 3 elements";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn detects_playwright_page_goto_error() {
-        let input = "Error: page.goto: Navigation failed because page was closed";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn detects_playwright_locator_fill_error() {
-        let input = "Error: locator.fill: Target closed\n    at LoginPage.fillUsername (tests/login.spec.ts:15:5)";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn detects_playwright_test_import() {
-        let input = "Error: Cannot find module '@playwright/test'\n    from 'tests/example.spec.ts'";
-        let result = detect_error_type(input);
-        assert!(matches!(result, Some(ErrorType::Playwright)));
-    }
-
-    #[test]
-    fn extracts_playwright_issue() {
-        let input = "TimeoutError: locator.click: Timeout 30000ms exceeded.\n    waiting for locator('.nonexistent')";
-        let result = ToonifiedError::new(input, ErrorType::Playwright);
-        assert!(result.issue.is_some());
-        assert!(result.issue.unwrap().contains("Timeout"));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Multi-Error Splitting Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn splits_two_errors_on_blank_line() {
-        let input = "TypeError: foo\n    at a.tsx:1\n\nReferenceError: bar\n    at b.tsx:2";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-    }
-
-    #[test]
-    fn splits_errors_without_blank_line() {
-        // Browser console often has no blank lines between errors
-        let input = "TypeError: foo\n    at a.tsx:1\nReferenceError: bar\n    at b.tsx:2";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-    }
-
-    #[test]
-    fn preserves_frames_with_their_error() {
-        let input = "TypeError: foo\n    at a.tsx:1\n    at b.tsx:2\n\nReferenceError: bar\n    at c.tsx:3";
-        let blocks = split_into_error_blocks(input);
-        assert!(blocks[0].contains("a.tsx") && blocks[0].contains("b.tsx"));
-        assert!(blocks[1].contains("c.tsx"));
-        assert!(!blocks[0].contains("c.tsx")); // Frame not mixed
-    }
-
-    #[test]
-    fn single_error_returns_one_block() {
-        let input = "TypeError: foo\n    at a.tsx:1";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 1);
-    }
-
-    #[test]
-    fn handles_browser_console_prefix() {
-        let input = "bundle.js:42 TypeError: foo\n    at a.tsx:1\n\napp.js:10 ReferenceError: bar";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-    }
-
-    #[test]
-    fn keeps_multiline_message_together() {
-        // Lines without error patterns stay with current block
-        let input = "Warning: Each child should have unique key.\nCheck the render method of ProductList.\n    at ProductList (file.tsx:1)";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 1);
-        assert!(blocks[0].contains("Check the render method"));
-    }
-
-    #[test]
-    fn handles_three_different_error_types() {
-        let input = "TypeError: a\n    at x.tsx:1\nSyntaxError: b\n    at y.tsx:2\nReferenceError: c\n    at z.tsx:3";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 3);
-    }
-
-    #[test]
-    fn empty_input_returns_empty() {
-        let blocks = split_into_error_blocks("");
-        assert!(blocks.is_empty());
-    }
-
-    #[test]
-    fn unrecognized_text_returns_single_block() {
-        let input = "Some random text\nwithout errors";
-        let blocks = split_into_error_blocks(input);
-        // Returns as single block (will be filtered later if no error detected)
-        assert_eq!(blocks.len(), 1);
-    }
-
-    #[test]
-    fn multi_error_detection() {
-        let input = "TypeError: a\n    at x.tsx:1\n\nReferenceError: b\n    at y.tsx:2";
-        let blocks = split_into_error_blocks(input);
-        let errors: Vec<_> = blocks.iter()
-            .filter_map(|b| detect_error_type(b).map(|t| ToonifiedError::new(b, t)))
-            .collect();
-        assert_eq!(errors.len(), 2);
-        assert_eq!(errors[0].error_type, ErrorType::TypeError);
-        assert_eq!(errors[1].error_type, ErrorType::RefError);
-    }
-
-    #[test]
-    fn integration_original_test_case() {
-        // The exact input from the bug report
-        let input = r#"Uncaught Error: Minified React error #130; visit https://reactjs.org/docs/error-decoder.html
-    at Object.throw (react-dom.production.min.js:189:327)
-
-Warning: Each child in a list should have a unique "key" prop.
-    at ProductItem (http://localhost:3000/bundle.js:1234:17)
-    at ProductList (http://localhost:3000/bundle.js:5678:23)
-
-Error: Cannot update a component while rendering a different component
-    at UserProfile (http://localhost:3000/bundle.js:4521:19)"#;
-
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 3, "Should split into 3 separate errors");
-
-        // Verify frames are NOT mixed
-        assert!(blocks[0].contains("react-dom.production.min.js"));
-        assert!(!blocks[0].contains("ProductItem")); // Not mixed!
-
-        assert!(blocks[1].contains("ProductItem"));
-        assert!(blocks[1].contains("ProductList"));
-        assert!(!blocks[1].contains("UserProfile")); // Not mixed!
-
-        assert!(blocks[2].contains("UserProfile"));
-    }
-
-    #[test]
-    fn multi_error_plain_format() {
-        let input1 = "TypeError: foo\n    at a.tsx:1";
-        let input2 = "ReferenceError: bar\n    at b.tsx:2";
-        let errors = vec![
-            ToonifiedError::new(input1, ErrorType::TypeError),
-            ToonifiedError::new(input2, ErrorType::RefError),
-        ];
-        let output = format_multi_plain(&errors, 100);
-        assert!(output.contains("type: TYPE_ERROR"));
-        assert!(output.contains("type: REF_ERROR"));
-        assert!(output.contains("==="));
-        assert!(output.contains("2 errors"));
-    }
-
-    #[test]
-    fn multi_error_toon_format() {
-        let input1 = "TypeError: foo\n    at a.tsx:1";
-        let input2 = "ReferenceError: bar\n    at b.tsx:2";
-        let errors = vec![
-            ToonifiedError::new(input1, ErrorType::TypeError),
-            ToonifiedError::new(input2, ErrorType::RefError),
-        ];
-        let output = format_multi_toon(&errors, 100);
-        assert!(output.contains("errors[2]:"));
-        assert!(output.contains("type: TYPE_ERROR"));
-        assert!(output.contains("type: REF_ERROR"));
-        assert!(output.contains("stats{orig,comp,pct,count}:"));
-    }
-
-    #[test]
-    fn is_stack_frame_line_detects_at_pattern() {
-        assert!(is_stack_frame_line("    at foo (file.tsx:1)"));
-        assert!(is_stack_frame_line("\tat bar (file.tsx:2)"));
-        assert!(is_stack_frame_line("  @ baz (file.tsx:3)"));
-        assert!(!is_stack_frame_line("TypeError: foo"));
-        assert!(!is_stack_frame_line("at start of line"));
-    }
-
-    #[test]
-    fn is_error_boundary_detects_errors() {
-        assert!(is_error_boundary("TypeError: foo"));
-        assert!(is_error_boundary("ReferenceError: bar"));
-        assert!(is_error_boundary("SyntaxError: baz"));
-        assert!(!is_error_boundary("    at foo (file.tsx:1)"));
-        assert!(!is_error_boundary(""));
-        assert!(!is_error_boundary("   "));
-    }
-
-    #[test]
-    fn single_error_backward_compatible() {
-        // Single error should produce same output as before multi-error feature
-        let input = "TypeError: Cannot read properties of undefined (reading 'map')";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 1);
-        assert_eq!(blocks[0], input);
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Edge Case Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn handles_input_starting_with_stack_frame() {
-        // Edge case: input starts with what looks like a stack frame (orphaned)
-        let input = "    at orphan (file.tsx:1)\nTypeError: foo\n    at bar (file.tsx:2)";
-        let blocks = split_into_error_blocks(input);
-        // The orphan frame becomes part of first block, then TypeError starts new block
-        assert_eq!(blocks.len(), 2);
-        assert!(blocks[1].contains("TypeError"));
-    }
-
-    #[test]
-    fn handles_windows_line_endings() {
-        // Windows CRLF line endings
-        let input = "TypeError: foo\r\n    at a.tsx:1\r\n\r\nReferenceError: bar\r\n    at b.tsx:2";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-    }
-
-    #[test]
-    fn handles_error_without_stack_frames() {
-        // Error message without any stack trace
-        let input = "TypeError: foo\n\nReferenceError: bar";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-        assert_eq!(blocks[0].trim(), "TypeError: foo");
-        assert_eq!(blocks[1].trim(), "ReferenceError: bar");
-    }
-
-    #[test]
-    fn handles_whitespace_only_lines_between_errors() {
-        let input = "TypeError: foo\n    at a.tsx:1\n   \n   \nReferenceError: bar\n    at b.tsx:2";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 2);
-    }
-
-    #[test]
-    fn handles_mixed_indentation_in_frames() {
-        // Some stack frames use spaces, others use tabs
-        let input = "TypeError: foo\n    at a.tsx:1\n\tat b.tsx:2\n  at c.tsx:3";
-        let blocks = split_into_error_blocks(input);
-        assert_eq!(blocks.len(), 1);
-        assert!(blocks[0].contains("a.tsx"));
-        assert!(blocks[0].contains("b.tsx"));
-        assert!(blocks[0].contains("c.tsx"));
-    }
+    use clap::CommandFactory;
 
     #[test]
-    fn handles_very_long_error_message() {
-        let long_msg = "x".repeat(5000);
-        let input = format!("TypeError: {}\n    at a.tsx:1", long_msg);
-        let blocks = split_into_error_blocks(&input);
-        assert_eq!(blocks.len(), 1);
-        assert!(blocks[0].len() > 5000);
+    fn cli_is_valid() {
+        Args::command().debug_assert();
     }
 }
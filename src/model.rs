@@ -0,0 +1,82 @@
+//! Structured representation of a compressed error.
+
+use crate::detect::{classify_as, Classification};
+use crate::error_type::ErrorType;
+use crate::extract::{extract_details, extract_issue, extract_location, extract_user_frames};
+use crate::format::Original;
+use serde::{Serialize, Serializer};
+
+/// A single error compressed down to its type, location, issue, and user frames.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToonifiedError {
+    #[serde(rename = "type")]
+    pub error_type: ErrorType,
+    /// Other error types that also matched, in priority order.
+    pub tags: Vec<ErrorType>,
+    /// How strongly the input supports `error_type` over the tags (0–1). `None`
+    /// for errors decoded from output that carried no score.
+    pub confidence: Option<f64>,
+    #[serde(rename = "file")]
+    pub file_location: Option<String>,
+    pub issue: Option<String>,
+    /// Extra labeled facts (component trace, test title, retry, ...), rendered
+    /// after the issue in order. Serialized as a JSON object.
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "serialize_details")]
+    pub details: Vec<(String, String)>,
+    pub frames: Vec<String>,
+    /// Length in characters of the block this error was extracted from.
+    pub original_len: usize,
+}
+
+impl ToonifiedError {
+    /// Run all extractors over `input`, an error block already classified as `error_type`.
+    pub fn new(input: &str, error_type: ErrorType) -> Self {
+        Self::classified(input, classify_as(input, error_type))
+    }
+
+    /// Run all extractors over `input` using a [`Classification`] from [`classify`](crate::classify).
+    pub fn classified(input: &str, classification: Classification) -> Self {
+        let error_type = classification.primary;
+        Self {
+            error_type,
+            tags: classification.tags,
+            confidence: Some(classification.confidence),
+            file_location: extract_location(input, error_type),
+            issue: extract_issue(input, error_type),
+            details: extract_details(input, error_type),
+            frames: extract_user_frames(input),
+            original_len: input.chars().count(),
+        }
+    }
+
+    /// Append a labeled detail.
+    pub fn with_detail(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.details.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Value of the detail labeled `key`, if any.
+    pub fn detail(&self, key: &str) -> Option<&str> {
+        self.details.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Names of the secondary tags, in priority order.
+    pub(crate) fn tag_names(&self) -> Vec<&'static str> {
+        self.tags.iter().map(ErrorType::name).collect()
+    }
+}
+
+fn serialize_details<S: Serializer>(details: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(details.iter().map(|(key, value)| (key, value)))
+}
+
+/// Errors compressed from one input file, rendered as a labeled section of
+/// multi-file output.
+#[derive(Clone)]
+pub struct FileErrors<'a> {
+    /// Section label, usually the path as given on the command line.
+    pub name: String,
+    pub errors: Vec<ToonifiedError>,
+    /// Size of the whole file, which the section's stats compare against.
+    pub original: Original<'a>,
+}
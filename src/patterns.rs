@@ -0,0 +1,222 @@
+//! Regex patterns used for detection and extraction (compiled once at startup).
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+pub(crate) const SOURCE_EXTENSIONS: &str = r"mdx|tsx|jsx|ts|js|vue|svelte";
+
+/// Framework runtime files that show up in stacks without a `node_modules` path.
+/// Angular's `polyfills.js` and `core.mjs` only count as the bare source-mapped
+/// name, at a server root, or under `@angular/core`, so user files with those names stay.
+const VENDOR_RUNTIMES: &str = r"runtime-(?:core|dom)\.esm-bundler\.js|zone\.js|zone-evergreen|(?:^|[\s(@]|://[^/\s]+/)polyfills(?:[.-][0-9A-Za-z]+)?\.js|(?:^|[\s(@])core\.mjs:|@angular/core/fesm\d+/core\.mjs";
+
+pub(crate) static PATTERNS: Lazy<Patterns> = Lazy::new(Patterns::compile);
+
+pub(crate) struct Patterns {
+    // Detection - Frameworks
+    pub(crate) vue_warn: Regex,
+    pub(crate) vue_error: Regex,
+    pub(crate) angular: Regex,
+    pub(crate) svelte: Regex,
+    pub(crate) svelte_context: Regex,
+    // Detection - DOM/React
+    pub(crate) dom_nesting: Regex,
+    pub(crate) hydration: Regex,
+    pub(crate) react_minified: Regex,
+    pub(crate) invalid_hook: Regex,
+    pub(crate) react_key: Regex,
+    // Detection - JavaScript errors
+    pub(crate) type_error: Regex,
+    pub(crate) ref_error: Regex,
+    pub(crate) syntax_error: Regex,
+    pub(crate) range_error: Regex,
+    pub(crate) uri_error: Regex,
+    pub(crate) eval_error: Regex,
+    // Detection - Network
+    pub(crate) cors_error: Regex,
+    pub(crate) network_error: Regex,
+    pub(crate) http_error: Regex,
+    pub(crate) websocket_error: Regex,
+    // Detection - Security
+    pub(crate) csp_error: Regex,
+    pub(crate) security_error: Regex,
+    pub(crate) mixed_content: Regex,
+    // Detection - Build tools / Testing
+    pub(crate) storybook: Regex,
+    pub(crate) nextjs: Regex,
+    pub(crate) module_not_found: Regex,
+    pub(crate) playwright: Regex,
+    pub(crate) cypress: Regex,
+    pub(crate) test_failure: Regex,
+    // Detection - System
+    pub(crate) system_error: Regex,
+    // Detection - Promise
+    pub(crate) unhandled_rejection: Regex,
+    // Detection - Browser APIs
+    pub(crate) media_error: Regex,
+    pub(crate) indexeddb_error: Regex,
+    pub(crate) service_worker: Regex,
+    // Detection - Warnings
+    pub(crate) deprecation: Regex,
+    // Detection - Catch-all
+    pub(crate) stack_trace: Regex,
+    // Extraction patterns
+    pub(crate) file_location: Regex,
+    pub(crate) dom_issue: Regex,
+    pub(crate) system_code: Regex,
+    pub(crate) storybook_code: Regex,
+    pub(crate) nextjs_code: Regex,
+    pub(crate) http_status: Regex,
+    pub(crate) user_frame: Regex,
+    pub(crate) framework_noise: Regex,
+    pub(crate) vendor_runtime: Regex,
+    pub(crate) vue_message: Regex,
+    pub(crate) vue_component: Regex,
+    pub(crate) angular_code: Regex,
+    pub(crate) angular_injector: Regex,
+    pub(crate) svelte_compile: Regex,
+    pub(crate) svelte_code: Regex,
+    pub(crate) svelte_not_found: Regex,
+    pub(crate) sveltekit: Regex,
+    pub(crate) svelte_route: Regex,
+    pub(crate) test_header: Regex,
+    pub(crate) test_summary: Regex,
+    pub(crate) test_file: Regex,
+    pub(crate) test_expected: Regex,
+    pub(crate) test_received: Regex,
+    pub(crate) diff_received_header: Regex,
+    pub(crate) code_frame_line: Regex,
+    pub(crate) code_frame_caret: Regex,
+    pub(crate) cypress_spec: Regex,
+    pub(crate) cypress_timeout: Regex,
+    pub(crate) cypress_selector: Regex,
+    pub(crate) cypress_command: Regex,
+    // TOON frame parsing patterns (pre-compiled for performance)
+    pub(crate) frame_at_name_loc: Regex,
+    pub(crate) frame_at_symbol_loc: Regex,
+    pub(crate) frame_name_at_loc: Regex,
+    pub(crate) location_file_line: Regex,
+}
+
+impl Patterns {
+    fn compile() -> Self {
+        let ext = SOURCE_EXTENSIONS;
+        let runtimes = VENDOR_RUNTIMES;
+        Self {
+            // Detection - Frameworks
+            // A warning is an error report when it wraps a thrown error: "Error in render: ..." (Vue 2)
+            // or "Unhandled error during execution of ..." (Vue 3). VUE_ERROR comes first in priority.
+            vue_warn: re(r"\[Vue warn\]:"),
+            vue_error: re(r"\[Vue error\]|\[Vue warn\]:\s*(?:Error in |Unhandled error during)"),
+            angular: re(r"(?-u:\b)NG\d{4,5}:|NullInjectorError|R3InjectorError|ExpressionChangedAfterItHasBeenChecked"),
+            // Compiler (vite-plugin-svelte), runtime (Svelte 5 error codes), and SvelteKit errors
+            svelte: re(r"\[plugin:vite-plugin-svelte|Svelte error:|\[svelte\] [a-z_]+|SvelteKitError"),
+            // Svelte/SvelteKit frames: errors thrown from the runtime or a +page/+layout/+server module
+            svelte_context: re(r"svelte/internal|@sveltejs/kit|\.svelte-kit/|/\+(?:page|layout|server)(?:\.server)?\.(?:ts|js):\d"),
+
+            // Detection - DOM/React
+            dom_nesting: re(r"(?i)validateDOMNesting"),
+            hydration: re(r"(?i)hydrat(ion|e|ing).*(?:failed|mismatch|error)"),
+            react_minified: re(r"Minified React error #\d+|react\.production\.min\.js"),
+            invalid_hook: re(r"(?i)Invalid hook call|Rules of Hooks|rendered more hooks"),
+            react_key: re(r"(?i)Encountered two children with the same key|Keys should be unique|Non-unique keys may cause|unique .?key.? prop"),
+
+            // Detection - JavaScript errors (allow optional file:line prefix from browser console)
+            type_error: re(r"(?m)^(?:\S+:\d+\s+)?TypeError:|Uncaught TypeError"),
+            ref_error: re(r"(?m)^(?:\S+:\d+\s+)?ReferenceError:|Uncaught ReferenceError"),
+            syntax_error: re(r"(?m)^(?:\S+:\d+\s+)?SyntaxError:|Uncaught SyntaxError"),
+            range_error: re(r"(?m)^(?:\S+:\d+\s+)?RangeError:|Uncaught RangeError"),
+            uri_error: re(r"(?m)^(?:\S+:\d+\s+)?URIError:|Uncaught URIError"),
+            eval_error: re(r"(?m)^(?:\S+:\d+\s+)?EvalError:|Uncaught EvalError"),
+
+            // Detection - Network
+            cors_error: re(r"(?i)CORS|Access-Control-Allow-Origin|blocked by CORS|cross-origin"),
+            network_error: re(r"(?i)Failed to fetch|NetworkError|net::ERR_|NS_ERROR_|fetch.*failed"),
+            // HTTP errors: "GET /api 404" or "status: 500" but NOT "bundle.js:45892"
+            // ASCII word boundaries: a Unicode `\b` forces the combined detection RegexSet
+            // off its DFA as soon as the input contains non-ASCII text.
+            http_error: re(r"(?i)(?-u:\b)(GET|POST|PUT|DELETE|PATCH)\s+\S+\s+[45]\d{2}(?-u:\b)|status[:\s]+[45]\d{2}(?-u:\b)|(?-u:\b)[45]\d{2}\s+(Not Found|Internal Server|Bad Request|Unauthorized|Forbidden)"),
+            websocket_error: re(r"(?i)WebSocket.*(?:error|failed|closed)|ws://.*error|wss://.*error"),
+
+            // Detection - Security
+            csp_error: re(r"(?i)Content-Security-Policy|CSP|blocked.*policy|violat.*directive"),
+            security_error: re(r"(?i)SecurityError|security.*violation|insecure|blocked.*security"),
+            mixed_content: re(r"(?i)Mixed Content|blocked.*insecure|http://.*https://"),
+
+            // Detection - Build tools / Testing
+            storybook: re(r"SB_"),
+            nextjs: re(r"(?i)NEXT_|getServerSideProps|getStaticProps|NextJS|next/"),
+            module_not_found: re(r"(?i)Module not found|Cannot find module|Cannot resolve|ModuleNotFoundError"),
+            // Cypress errors, quoted `cy.*` commands, its retry timeouts, uncaught-exception wrapper, runner and spec files
+            cypress: re(&format!(r"CypressError|Timed out retrying after \d+ms|originated from your application code|`cy\.[a-z]+\(|/__cypress/|cypress_runner|\.cy\.({ext}):\d")),
+            // Jest "● describe › it" and Vitest "FAIL file > describe > it" headers, Jest's matcher
+            // line, and chai assertion messages (Vitest). Jest's "● Console" is excluded in `detect`.
+            test_failure: re(r"(?m)^\s*● |^\s*FAIL\s.* > |expect\(received\)\.|AssertionError: expected "),
+            playwright: re(r"(?i)locator\.(click|fill|waitFor|check|press|type|hover)|page\.(goto|waitFor|click)|expect\(.*\)\.(toBeVisible|toHaveText|toBeEnabled|toBeChecked|toContainText)|TimeoutError.*locator|waiting for locator|strict mode violation|playwright|@playwright/test"),
+
+            // Detection - System
+            system_error: re(r"ENOENT|EACCES|ECONNREFUSED|ETIMEDOUT|EADDRINUSE|EPERM"),
+
+            // Detection - Promise
+            unhandled_rejection: re(r"(?i)Unhandled.*rejection|UnhandledPromiseRejection|promise.*reject"),
+
+            // Detection - Browser APIs
+            media_error: re(r"(?i)MediaError|NotSupportedError.*media|play\(\).*failed|autoplay.*blocked"),
+            indexeddb_error: re(r"(?i)IndexedDB|IDBDatabase|QuotaExceededError|VersionError"),
+            service_worker: re(r"(?i)ServiceWorker|service.*worker.*(?:error|failed)|SW.*(?:error|failed)"),
+
+            // Detection - Warnings
+            deprecation: re(r"(?i)deprecated|deprecation|will be removed|no longer supported"),
+
+            // Detection - Catch-all (must be very generic)
+            stack_trace: re(r"at .* \(.*:\d+:\d+\)|Error:.*\n.*at\s"),
+
+            // Extraction patterns
+            file_location: re(&format!(r"[A-Za-z0-9_.+-]+\.({ext}):\d+")),
+            dom_issue: re(r"<[a-z]+> cannot (?:appear as a |be a )?descendant of <[a-z]+>"),
+            system_code: re(r"E[A-Z]+:[^\n]*"),
+            storybook_code: re(r"SB_[A-Z_]+[^\n]*"),
+            nextjs_code: re(r"NEXT_[A-Z_]+|(?:getServerSideProps|getStaticProps)[^\n]*error"),
+            http_status: re(r"\b[45]\d{2}\b"),
+            user_frame: re(&format!(r"(@|at |❯ ).+\.({ext}):\d+")),
+            framework_noise: re(&format!(r"chunk-|node_modules|storybook_internal|webpack|vite|/internal|react-dom|jest-circus|jest-jasmine|@vitest/|cypress_runner|/__cypress/runner|@sveltejs/kit|\.svelte-kit/|{runtimes}")),
+            vendor_runtime: re(runtimes),
+            vue_message: re(r"\[Vue (?:warn|error)\]:\s*(.+)"),
+            // Component trace line: "at <ProductList items=undefined >" (Vue 3) or
+            // "---> <ProductList> at src/components/ProductList.vue" and the "<App> at ..." lines
+            // below it (Vue 2). Unprefixed lines need a bare capitalized `<Name>`, so template markup isn't read.
+            vue_component: re(r"(?m)^[ \t]*(?:(?:at[ \t]+|--->[ \t]*)<([A-Za-z][\w.-]*)[^>\n]*>|<([A-Z][\w.-]*)>)(?:[ \t]+at[ \t]+([^\s<]\S*))?"),
+            angular_code: re(r"(?-u:\b)NG\d{4,5}(?-u:\b)"),
+            angular_injector: re(r"R3InjectorError\((\w+)\)\[([^\]]+)\]"),
+            svelte_compile: re(r"\[plugin:vite-plugin-svelte[^\]]*\]\s*(?:\S+:\d+(?::\d+)?\s+)?(.+)"),
+            svelte_code: re(r"(?:Svelte error:|\[svelte\])\s*([a-z_]+)"),
+            svelte_not_found: re(r"Not found: (/\S*)"),
+            sveltekit: re(r"@sveltejs/kit|SvelteKitError|\.svelte-kit/|src/routes/"),
+            svelte_route: re(r"routes((?:/[^/\s:]+)*?)/\+(?:page|layout|server|error)"),
+            test_header: re(r"^\s*(?:● (.+)|FAIL\s+(?:\|[^|]+\|\s+)?\S+ > (.+))$"),
+            // Runner output after the failures: Jest's "Test Suites:"/"Tests:" and Vitest's
+            // "Test Files"/"Tests" summary, "⎯⎯⎯" separators, and "❯ file (N tests ...)" lines
+            test_summary: re(r"^\s*(?:Test Suites:|Tests:|Test Files\s|Tests\s{2,}\d|⎯{3,}|❯ \S+ \(\d+ tests?)"),
+            test_file: re(&format!(r"[A-Za-z0-9_.+-]+\.(?:test|spec)\.({ext}):\d+")),
+            test_expected: re(r"(?m)^\s*Expected(?: [a-z]+)*:\s*(.+)$"),
+            test_received: re(r"(?m)^\s*Received(?: [a-z]+)*:\s*(.+)$"),
+            diff_received_header: re(r"^\+ Received"),
+            code_frame_line: re(r"^\s*(>)?\s*\d+\s*\|(.*)$"),
+            code_frame_caret: re(r"^\s*\|\s*\^"),
+            cypress_spec: re(&format!(r"[A-Za-z0-9_.+-]+\.cy\.({ext}):\d+")),
+            cypress_timeout: re(r"Timed out retrying after (\d+ms)"),
+            cypress_selector: re(r#"Expected to find (?:element|content): `([^`]+)`|cy\.(?:get|find)\(\s*['"]([^'"]+)['"]"#),
+            cypress_command: re(r"`(cy\.[^`]+)`"),
+
+            // TOON frame parsing (pre-compiled for hot path performance)
+            frame_at_name_loc: re(r"at\s+(?:(?:async|new)\s+)?([^\s(]+)\s*\(([^)]+)\)"),
+            frame_at_symbol_loc: re(r"@\s*(\w+)\s*\(([^)]+)\)"),
+            frame_name_at_loc: re(r"(\w+)\s*@\s*(.+)"),
+            location_file_line: re(r"([^/]+\.[a-z]+):(\d+)(?::\d+)?$"),
+        }
+    }
+}
+
+fn re(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("Invalid regex pattern")
+}
@@ -0,0 +1,135 @@
+//! Run a command, tee its output live, and capture it for compression.
+//!
+//! Backs `error-toon run -- npm test`: stdout and stderr are copied through as
+//! they arrive, and both are collected line by line, in arrival order, so a
+//! failing run can be split and compressed afterwards.
+
+use crate::util::strip_ansi;
+use std::io::{self, Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::thread;
+
+/// Result of a [`run_tee`] call.
+#[derive(Debug)]
+pub struct Captured {
+    pub status: ExitStatus,
+    /// Combined stdout and stderr, interleaved by line, with ANSI escapes removed.
+    pub output: String,
+}
+
+impl Captured {
+    /// Exit code to pass on: the child's code, `128 + signal` if it was killed
+    /// by a signal (Unix), otherwise 1.
+    pub fn exit_code(&self) -> i32 {
+        if let Some(code) = self.status.code() {
+            return code;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = self.status.signal() {
+                return 128 + signal;
+            }
+        }
+        1
+    }
+}
+
+/// Spawn `command` with piped stdout/stderr, copy each to `out`/`err` as it
+/// arrives, and return the exit status with the captured output. Stdin is inherited.
+pub fn run_tee(command: &mut Command, out: impl Write + Send, err: impl Write + Send) -> io::Result<Captured> {
+    let mut child = command.stdin(Stdio::inherit()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let child_out = child.stdout.take().expect("stdout is piped");
+    let child_err = child.stderr.take().expect("stderr is piped");
+    let lines = Mutex::new(Vec::new());
+
+    let copied = thread::scope(|scope| {
+        let stdout = scope.spawn(|| tee(child_out, out, &lines));
+        let stderr = scope.spawn(|| tee(child_err, err, &lines));
+        let stdout = stdout.join().expect("stdout tee thread panicked");
+        let stderr = stderr.join().expect("stderr tee thread panicked");
+        stdout.and(stderr)
+    });
+
+    // Reap the child even when copying failed, then report the copy error
+    let status = child.wait()?;
+    copied?;
+    let output = lines.into_inner().expect("capture lock poisoned").concat();
+    Ok(Captured { status, output: strip_ansi(&output).into_owned() })
+}
+
+/// Copy `source` to `sink` chunk by chunk, appending each completed line to `lines`.
+fn tee(mut source: impl Read, mut sink: impl Write, lines: &Mutex<Vec<String>>) -> io::Result<()> {
+    let mut buf = [0; 8192];
+    let mut partial = Vec::new();
+
+    loop {
+        let n = match source.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        // A closed terminal or pipe shouldn't stop capture
+        let _ = sink.write_all(&buf[..n]).and_then(|_| sink.flush());
+
+        partial.extend_from_slice(&buf[..n]);
+        if let Some(end) = partial.iter().rposition(|&b| b == b'\n') {
+            let complete: Vec<u8> = partial.drain(..=end).collect();
+            let mut lines = lines.lock().expect("capture lock poisoned");
+            lines.extend(String::from_utf8_lossy(&complete).split_inclusive('\n').map(str::to_string));
+        }
+    }
+
+    if !partial.is_empty() {
+        let mut last = String::from_utf8_lossy(&partial).into_owned();
+        last.push('\n');
+        lines.lock().expect("capture lock poisoned").push(last);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn tees_both_streams_and_captures_them() {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let captured = run_tee(&mut sh("echo one; echo two >&2; printf three"), &mut out, &mut err).unwrap();
+
+        assert!(captured.status.success());
+        assert_eq!(out, b"one\nthree");
+        assert_eq!(err, b"two\n");
+        let mut lines: Vec<&str> = captured.output.lines().collect();
+        lines.sort();
+        assert_eq!(lines, vec!["one", "three", "two"]);
+    }
+
+    #[test]
+    fn reports_exit_code_and_strips_ansi() {
+        let script = r"printf '\033[31mTypeError: boom\033[0m\n' >&2; exit 3";
+        let captured = run_tee(&mut sh(script), io::sink(), io::sink()).unwrap();
+
+        assert_eq!(captured.exit_code(), 3);
+        assert_eq!(captured.output, "TypeError: boom\n");
+    }
+
+    #[test]
+    fn signal_exit_maps_to_128_plus_signal() {
+        let captured = run_tee(&mut sh("kill -9 $$"), io::sink(), io::sink()).unwrap();
+        assert_eq!(captured.exit_code(), 137);
+    }
+
+    #[test]
+    fn missing_program_is_an_error() {
+        assert!(run_tee(&mut Command::new("error-toon-no-such-program"), io::sink(), io::sink()).is_err());
+    }
+}
@@ -0,0 +1,419 @@
+//! Multi-error splitting: separates mixed console output into one block per error.
+
+use crate::detect::matches_any;
+use crate::extract::test_title;
+use crate::patterns::PATTERNS;
+
+/// Check if a line is a stack frame (starts with whitespace + "at " or "@")
+pub fn is_stack_frame_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    // Lines that start with whitespace and contain "at " or "@" are stack frames
+    (line.starts_with(' ') || line.starts_with('\t'))
+        && (trimmed.starts_with("at ") || trimmed.starts_with("@ ") || trimmed.contains(" @ "))
+}
+
+/// Check if a line starts a new error (matches any `ErrorType` pattern and is not a stack frame)
+pub fn is_error_boundary(line: &str) -> bool {
+    // Stack frames and source lines of a code frame are never boundaries
+    if is_stack_frame_line(line) || PATTERNS.code_frame_line.is_match(line) {
+        return false;
+    }
+
+    let trimmed = line.trim();
+
+    // Empty or whitespace-only lines are not boundaries
+    if trimmed.is_empty() {
+        return false;
+    }
+
+    // Reuse existing patterns - same single-pass set as detect_error_type()
+    // Note: Some patterns (like RuntimeError's stack_trace) use multiline matching
+    // which won't work on single lines, so we also check for generic error starts
+    if matches_any(line) {
+        return true;
+    }
+
+    // Additional check for generic error starts that multiline patterns miss
+    // e.g., "Error: Cannot update a component..."
+    trimmed.starts_with("Error:") ||
+    trimmed.starts_with("Uncaught Error:") ||
+    trimmed.starts_with("Warning:")
+}
+
+/// Split input containing multiple errors into separate error blocks.
+/// Each block contains one error with its associated stack frames.
+pub fn split_into_error_blocks(input: &str) -> Vec<String> {
+    let mut splitter = BlockSplitter::new();
+    let mut blocks: Vec<String> = input.lines().filter_map(|line| splitter.push_line(line)).collect();
+
+    // Don't forget last block
+    blocks.extend(splitter.finish());
+    blocks
+}
+
+/// Incremental form of [`split_into_error_blocks`] for input that arrives line by line.
+///
+/// A block is complete once the next error boundary is seen or [`finish`](Self::finish)
+/// is called; feeding the lines of a string and then finishing yields exactly the
+/// blocks `split_into_error_blocks` would return.
+#[derive(Debug, Default)]
+pub struct BlockSplitter {
+    current_block: String,
+    /// The block in progress starts with a Jest/Vitest test header, so only the
+    /// next test header, the runner's summary, or an unindented error after a
+    /// blank line ends it: the assertion, diff, and any error the test threw all
+    /// belong to that test.
+    in_test: bool,
+}
+
+impl BlockSplitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one line (without its line terminator). Returns the previous block if
+    /// this line starts a new error.
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        let mut finished = None;
+        let test_header = test_title(line).is_some();
+        let test_summary = self.in_test && PATTERNS.test_summary.is_match(line);
+        if test_summary || (self.in_test && self.after_blank() && !line.starts_with([' ', '\t']) && is_error_boundary(line)) {
+            self.in_test = false;
+        }
+        let boundary =
+            test_header || test_summary || (!self.in_test && is_error_boundary(line) && !self.introduces(line));
+
+        if boundary && !self.current_block.trim().is_empty() {
+            // Save previous block, start new one
+            finished = Some(self.current_block.trim().to_string());
+            self.current_block.clear();
+        }
+        if self.current_block.trim().is_empty() {
+            self.in_test = test_header;
+        }
+
+        if !self.current_block.is_empty() {
+            self.current_block.push('\n');
+        }
+        self.current_block.push_str(line);
+
+        finished
+    }
+
+    /// Whether `line` is indented detail directly under an error header ending in `:`,
+    /// such as Angular's `R3InjectorError(...)[...]:` followed by `  NullInjectorError: ...`.
+    fn introduces(&self, line: &str) -> bool {
+        if !line.starts_with([' ', '\t']) {
+            return false;
+        }
+        let last = self.current_block.trim_end().lines().last().unwrap_or_default();
+        last.ends_with(':') && is_error_boundary(last)
+    }
+
+    /// Whether the last line of the block in progress is blank.
+    fn after_blank(&self) -> bool {
+        self.current_block.rsplit('\n').next().is_some_and(|last| last.trim().is_empty())
+    }
+
+    /// Whether a non-blank block is in progress.
+    pub fn has_pending(&self) -> bool {
+        !self.current_block.trim().is_empty()
+    }
+
+    /// Flush the block in progress (end of input or idle timeout). The splitter
+    /// can keep being fed afterwards.
+    pub fn finish(&mut self) -> Option<String> {
+        self.in_test = false;
+        let block = std::mem::take(&mut self.current_block);
+        if block.trim().is_empty() {
+            None
+        } else {
+            Some(block.trim().to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Multi-Error Splitting Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn splits_two_errors_on_blank_line() {
+        let input = "TypeError: foo\n    at a.tsx:1\n\nReferenceError: bar\n    at b.tsx:2";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn splits_errors_without_blank_line() {
+        // Browser console often has no blank lines between errors
+        let input = "TypeError: foo\n    at a.tsx:1\nReferenceError: bar\n    at b.tsx:2";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn preserves_frames_with_their_error() {
+        let input = "TypeError: foo\n    at a.tsx:1\n    at b.tsx:2\n\nReferenceError: bar\n    at c.tsx:3";
+        let blocks = split_into_error_blocks(input);
+        assert!(blocks[0].contains("a.tsx") && blocks[0].contains("b.tsx"));
+        assert!(blocks[1].contains("c.tsx"));
+        assert!(!blocks[0].contains("c.tsx")); // Frame not mixed
+    }
+
+    #[test]
+    fn single_error_returns_one_block() {
+        let input = "TypeError: foo\n    at a.tsx:1";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn handles_browser_console_prefix() {
+        let input = "bundle.js:42 TypeError: foo\n    at a.tsx:1\n\napp.js:10 ReferenceError: bar";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn keeps_multiline_message_together() {
+        // Lines without error patterns stay with current block
+        let input = "Warning: Each child should have unique key.\nCheck the render method of ProductList.\n    at ProductList (file.tsx:1)";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].contains("Check the render method"));
+    }
+
+    #[test]
+    fn handles_three_different_error_types() {
+        let input = "TypeError: a\n    at x.tsx:1\nSyntaxError: b\n    at y.tsx:2\nReferenceError: c\n    at z.tsx:3";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 3);
+    }
+
+    #[test]
+    fn empty_input_returns_empty() {
+        let blocks = split_into_error_blocks("");
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn unrecognized_text_returns_single_block() {
+        let input = "Some random text\nwithout errors";
+        let blocks = split_into_error_blocks(input);
+        // Returns as single block (will be filtered later if no error detected)
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn integration_original_test_case() {
+        // The exact input from the bug report
+        let input = r#"Uncaught Error: Minified React error #130; visit https://reactjs.org/docs/error-decoder.html
+    at Object.throw (react-dom.production.min.js:189:327)
+
+Warning: Each child in a list should have a unique "key" prop.
+    at ProductItem (http://localhost:3000/bundle.js:1234:17)
+    at ProductList (http://localhost:3000/bundle.js:5678:23)
+
+Error: Cannot update a component while rendering a different component
+    at UserProfile (http://localhost:3000/bundle.js:4521:19)"#;
+
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 3, "Should split into 3 separate errors");
+
+        // Verify frames are NOT mixed
+        assert!(blocks[0].contains("react-dom.production.min.js"));
+        assert!(!blocks[0].contains("ProductItem")); // Not mixed!
+
+        assert!(blocks[1].contains("ProductItem"));
+        assert!(blocks[1].contains("ProductList"));
+        assert!(!blocks[1].contains("UserProfile")); // Not mixed!
+
+        assert!(blocks[2].contains("UserProfile"));
+    }
+
+    #[test]
+    fn is_stack_frame_line_detects_at_pattern() {
+        assert!(is_stack_frame_line("    at foo (file.tsx:1)"));
+        assert!(is_stack_frame_line("\tat bar (file.tsx:2)"));
+        assert!(is_stack_frame_line("  @ baz (file.tsx:3)"));
+        assert!(!is_stack_frame_line("TypeError: foo"));
+        assert!(!is_stack_frame_line("at start of line"));
+    }
+
+    #[test]
+    fn is_error_boundary_detects_errors() {
+        assert!(is_error_boundary("TypeError: foo"));
+        assert!(is_error_boundary("ReferenceError: bar"));
+        assert!(is_error_boundary("SyntaxError: baz"));
+        assert!(!is_error_boundary("    at foo (file.tsx:1)"));
+        assert!(!is_error_boundary(""));
+        assert!(!is_error_boundary("   "));
+    }
+
+    #[test]
+    fn code_frame_lines_are_not_boundaries() {
+        let input = "CypressError: Timed out retrying after 4000ms: `cy.click()` failed\n\n  12 |   cy.visit('/login')\n> 13 |   throw new TypeError('boom')\n     |   ^";
+        assert!(!is_error_boundary("> 13 |   throw new TypeError('boom')"));
+        assert_eq!(split_into_error_blocks(input).len(), 1);
+    }
+
+    #[test]
+    fn single_error_backward_compatible() {
+        // Single error should produce same output as before multi-error feature
+        let input = "TypeError: Cannot read properties of undefined (reading 'map')";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0], input);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Edge Case Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn handles_input_starting_with_stack_frame() {
+        // Edge case: input starts with what looks like a stack frame (orphaned)
+        let input = "    at orphan (file.tsx:1)\nTypeError: foo\n    at bar (file.tsx:2)";
+        let blocks = split_into_error_blocks(input);
+        // The orphan frame becomes part of first block, then TypeError starts new block
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[1].contains("TypeError"));
+    }
+
+    #[test]
+    fn handles_windows_line_endings() {
+        // Windows CRLF line endings
+        let input = "TypeError: foo\r\n    at a.tsx:1\r\n\r\nReferenceError: bar\r\n    at b.tsx:2";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn handles_error_without_stack_frames() {
+        // Error message without any stack trace
+        let input = "TypeError: foo\n\nReferenceError: bar";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].trim(), "TypeError: foo");
+        assert_eq!(blocks[1].trim(), "ReferenceError: bar");
+    }
+
+    #[test]
+    fn handles_whitespace_only_lines_between_errors() {
+        let input = "TypeError: foo\n    at a.tsx:1\n   \n   \nReferenceError: bar\n    at b.tsx:2";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn handles_mixed_indentation_in_frames() {
+        // Some stack frames use spaces, others use tabs
+        let input = "TypeError: foo\n    at a.tsx:1\n\tat b.tsx:2\n  at c.tsx:3";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].contains("a.tsx"));
+        assert!(blocks[0].contains("b.tsx"));
+        assert!(blocks[0].contains("c.tsx"));
+    }
+
+    #[test]
+    fn handles_very_long_error_message() {
+        let long_msg = "x".repeat(5000);
+        let input = format!("TypeError: {}\n    at a.tsx:1", long_msg);
+        let blocks = split_into_error_blocks(&input);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].len() > 5000);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Incremental Splitter Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn block_splitter_emits_block_when_next_error_starts() {
+        let mut splitter = BlockSplitter::new();
+        assert_eq!(splitter.push_line("TypeError: foo"), None);
+        assert_eq!(splitter.push_line("    at a.tsx:1"), None);
+        assert_eq!(splitter.push_line("ReferenceError: bar"), Some("TypeError: foo\n    at a.tsx:1".to_string()));
+        assert_eq!(splitter.finish(), Some("ReferenceError: bar".to_string()));
+        assert_eq!(splitter.finish(), None);
+    }
+
+    #[test]
+    fn block_splitter_ignores_trailing_whitespace_lines() {
+        let mut splitter = BlockSplitter::new();
+        splitter.push_line("TypeError: foo");
+        splitter.push_line("   ");
+        assert_eq!(splitter.finish(), Some("TypeError: foo".to_string()));
+    }
+
+    #[test]
+    fn block_splitter_reports_pending_block() {
+        let mut splitter = BlockSplitter::new();
+        assert!(!splitter.has_pending());
+        splitter.push_line("  ");
+        assert!(!splitter.has_pending());
+        splitter.push_line("TypeError: foo");
+        assert!(splitter.has_pending());
+        splitter.finish();
+        assert!(!splitter.has_pending());
+    }
+
+    #[test]
+    fn keeps_indented_detail_after_colon_header() {
+        let input = "ERROR NullInjectorError: R3InjectorError(AppModule)[UserService -> HttpClient]: \n  NullInjectorError: No provider for HttpClient!\n    at NullInjector.get (core.mjs:7599:27)";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn keeps_indented_cause_after_any_colon_header() {
+        let cause = "    Error: connect ECONNREFUSED 127.0.0.1:5432";
+        assert!(is_error_boundary(cause));
+        let input = format!("Error: Failed to load config:\n{}\n    at TCPConnectWrap.afterConnect (node:net:1595:16)", cause);
+        assert_eq!(split_into_error_blocks(&input).len(), 1);
+    }
+
+    #[test]
+    fn colon_rule_only_covers_the_line_after_an_error_header() {
+        // Not indented
+        assert_eq!(split_into_error_blocks("Warning: something went wrong:\nTypeError: foo").len(), 2);
+        // Not directly after the header
+        let input = "Error: Failed to load config:\n    at loadConfig (src/config.ts:12:5)\n    Error: connect ECONNREFUSED 127.0.0.1:5432";
+        assert_eq!(split_into_error_blocks(input).len(), 2);
+        // Colon line is log output, not an error header
+        let input = "TypeError: a\n    at x (a.tsx:1)\nRetrying request:\n    Error: connect ECONNREFUSED 127.0.0.1:5432";
+        assert_eq!(split_into_error_blocks(input).len(), 2);
+    }
+
+    #[test]
+    fn test_failure_blocks_run_until_next_test_header() {
+        let input = "  ● suite › first\n\n    TypeError: boom\n      at a.test.ts:1\n\n    ReferenceError: inside the same test\n  ● suite › second\n\n    expect(received).toBe(expected)";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].contains("ReferenceError"));
+        assert!(blocks[1].starts_with("● suite › second"));
+    }
+
+    #[test]
+    fn test_blocks_end_at_runner_summary_and_later_errors() {
+        let input = "  ● suite › first\n\n    expect(received).toBe(expected)\n\nTest Suites: 1 failed, 1 total\nTests:       1 failed, 1 total\nnpm ERR! code ELIFECYCLE\n\n  ● suite › second\n\n    expect(received).toBe(expected)\n\nError: connect ECONNREFUSED 127.0.0.1:5432\n    at TCPConnectWrap.afterConnect (node:net:1595:16)";
+        let blocks = split_into_error_blocks(input);
+        assert_eq!(blocks.len(), 4, "{:#?}", blocks);
+        assert!(blocks[0].ends_with("toBe(expected)"));
+        assert!(blocks[1].starts_with("Test Suites:"));
+        assert!(blocks[3].starts_with("Error: connect ECONNREFUSED"));
+    }
+
+    #[test]
+    fn jest_console_output_is_not_a_test_header() {
+        let input = "  ● Console\n\n    console.log\n      hello";
+        assert!(test_title("  ● Console").is_none());
+        assert_eq!(split_into_error_blocks(input).len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+//! Streaming block splitter for unbounded input such as `tail -f app.log | error-toon`.
+//!
+//! [`BlockStream`] reads lines on a background thread and yields each error block
+//! as soon as it is finished: when the next error boundary arrives, when no new
+//! line has arrived for the idle timeout, or at end of input.
+
+use crate::split::BlockSplitter;
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Default time without new input after which the block in progress is flushed.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Iterator over the error blocks of a line-oriented reader, yielded as they complete.
+///
+/// Without an idle timeout the blocks are exactly those
+/// [`split_into_error_blocks`](crate::split_into_error_blocks) returns for the
+/// whole input.
+pub struct BlockStream {
+    lines: Receiver<io::Result<Vec<u8>>>,
+    splitter: BlockSplitter,
+    idle_timeout: Option<Duration>,
+    bytes_read: usize,
+    chars_read: usize,
+    done: bool,
+}
+
+impl BlockStream {
+    /// Start reading `reader` on a background thread. Uses [`DEFAULT_IDLE_TIMEOUT`].
+    pub fn new<R: BufRead + Send + 'static>(mut reader: R) -> Self {
+        let (sender, lines) = mpsc::channel();
+
+        thread::spawn(move || loop {
+            let mut buf = Vec::new();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if sender.send(Ok(buf)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    let _ = sender.send(Err(e));
+                    break;
+                }
+            }
+        });
+
+        Self {
+            lines,
+            splitter: BlockSplitter::new(),
+            idle_timeout: Some(DEFAULT_IDLE_TIMEOUT),
+            bytes_read: 0,
+            chars_read: 0,
+            done: false,
+        }
+    }
+
+    /// Flush the block in progress after `timeout` without new input; `None` waits
+    /// for the next boundary or end of input.
+    pub fn idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Bytes consumed from the reader so far.
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Characters consumed from the reader so far (invalid UTF-8 counts as U+FFFD).
+    pub fn chars_read(&self) -> usize {
+        self.chars_read
+    }
+
+    fn next_line(&self) -> Result<io::Result<Vec<u8>>, RecvTimeoutError> {
+        match self.idle_timeout {
+            // Only time out while there is a block to flush
+            Some(timeout) if self.splitter.has_pending() => self.lines.recv_timeout(timeout),
+            _ => self.lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        }
+    }
+}
+
+impl Iterator for BlockStream {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.next_line() {
+                Ok(Ok(buf)) => {
+                    self.bytes_read += buf.len();
+                    let line = String::from_utf8_lossy(&buf);
+                    self.chars_read += line.chars().count();
+                    if let Some(block) = self.splitter.push_line(line.trim_end_matches(['\n', '\r'])) {
+                        return Some(Ok(block));
+                    }
+                }
+                Ok(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some(block) = self.splitter.finish() {
+                        return Some(Ok(block));
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.done = true;
+                    return self.splitter.finish().map(Ok);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split::split_into_error_blocks;
+    use std::io::{BufReader, Cursor, Read};
+    use std::time::Instant;
+
+    const INPUT: &str = "TypeError: foo\n    at a.tsx:1\nReferenceError: bar\n    at b.tsx:2\n";
+
+    #[test]
+    fn yields_same_blocks_as_split_at_eof() {
+        let stream = BlockStream::new(Cursor::new(INPUT)).idle_timeout(None);
+        let blocks: Vec<String> = stream.map(Result::unwrap).collect();
+        assert_eq!(blocks, split_into_error_blocks(INPUT));
+    }
+
+    #[test]
+    fn counts_bytes_and_chars_read() {
+        let mut stream = BlockStream::new(Cursor::new(INPUT));
+        assert_eq!(stream.by_ref().count(), 2);
+        assert_eq!(stream.bytes_read(), INPUT.len());
+        assert_eq!(stream.chars_read(), INPUT.chars().count());
+    }
+
+    #[test]
+    fn handles_crlf_and_invalid_utf8() {
+        let input = b"TypeError: caf\xe9\r\n    at a.tsx:1\r\n".to_vec();
+        let blocks: Vec<String> = BlockStream::new(Cursor::new(input)).map(Result::unwrap).collect();
+        assert_eq!(blocks, vec!["TypeError: caf\u{fffd}\n    at a.tsx:1".to_string()]);
+    }
+
+    /// Reader that serves its contents and then stalls like an idle `tail -f`.
+    struct Stalled(Cursor<&'static str>);
+
+    impl Read for Stalled {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.read(buf)? {
+                0 => {
+                    thread::sleep(Duration::from_secs(60));
+                    Ok(0)
+                }
+                n => Ok(n),
+            }
+        }
+    }
+
+    #[test]
+    fn flushes_pending_block_after_idle_timeout() {
+        let reader = BufReader::new(Stalled(Cursor::new("TypeError: foo\n    at a.tsx:1\n")));
+        let mut stream = BlockStream::new(reader).idle_timeout(Some(Duration::from_millis(50)));
+
+        let start = Instant::now();
+        let block = stream.next().unwrap().unwrap();
+        assert_eq!(block, "TypeError: foo\n    at a.tsx:1");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
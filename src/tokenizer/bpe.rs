@@ -0,0 +1,151 @@
+//! Pre-tokenization and byte-pair merging, following tiktoken.
+
+use super::Encoding;
+use regex::Regex;
+use std::collections::HashMap;
+
+const CL100K_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+";
+
+const O200K_PATTERN: &str = concat!(
+    r"[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]*[\p{Ll}\p{Lm}\p{Lo}\p{M}]+(?i:'s|'t|'re|'ve|'m|'ll|'d)?",
+    r"|[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]+[\p{Ll}\p{Lm}\p{Lo}\p{M}]*(?i:'s|'t|'re|'ve|'m|'ll|'d)?",
+    r"|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n/]*|\s*[\r\n]+|\s+",
+);
+
+/// Pre-tokenizer pattern for `encoding`. tiktoken's patterns end in
+/// `\s+(?!\S)|\s+`; the `regex` crate has no lookahead, so the pattern ends in
+/// `\s+` and [`pieces`] gives back the last whitespace character instead.
+pub(super) fn pattern(encoding: Encoding) -> Regex {
+    let pattern = match encoding {
+        Encoding::Cl100k => CL100K_PATTERN,
+        Encoding::O200k => O200K_PATTERN,
+    };
+    Regex::new(pattern).expect("Invalid tokenizer pattern")
+}
+
+/// Split `text` into the pieces tiktoken merges independently.
+pub(super) fn pieces<'a>(pattern: &'a Regex, text: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let m = pattern.find_at(text, pos)?;
+        let mut end = m.end();
+
+        // `\s+(?!\S)`: a whitespace run followed by text leaves its last
+        // character to start the next piece (" world" rather than "world")
+        let piece = m.as_str();
+        if end < text.len() && piece.chars().all(char::is_whitespace) && !piece.ends_with(['\r', '\n']) {
+            if let Some((last, _)) = piece.char_indices().last().filter(|&(i, _)| i > 0) {
+                end = m.start() + last;
+            }
+        }
+
+        pos = end;
+        Some(&text[m.start()..end])
+    })
+}
+
+/// Number of tokens `piece` merges into.
+pub(super) fn count_piece(ranks: &HashMap<Vec<u8>, u32>, piece: &[u8]) -> usize {
+    if piece.is_empty() {
+        return 0;
+    }
+    if piece.len() == 1 || ranks.contains_key(piece) {
+        return 1;
+    }
+    byte_pair_merge(ranks, piece).len() - 1
+}
+
+/// tiktoken's merge loop: repeatedly join the adjacent pair with the lowest
+/// rank. Returns part boundaries (start offsets plus a final end offset).
+fn byte_pair_merge(ranks: &HashMap<Vec<u8>, u32>, piece: &[u8]) -> Vec<(usize, u32)> {
+    let rank_of = |bytes: &[u8]| ranks.get(bytes).copied().unwrap_or(u32::MAX);
+
+    let mut parts: Vec<(usize, u32)> = (0..piece.len() - 1).map(|i| (i, rank_of(&piece[i..i + 2]))).collect();
+    parts.push((piece.len() - 1, u32::MAX));
+    parts.push((piece.len(), u32::MAX));
+
+    // Rank of the part starting at `i` merged with the one after it
+    let merged_rank = |parts: &[(usize, u32)], i: usize| {
+        if i + 3 < parts.len() {
+            rank_of(&piece[parts[i].0..parts[i + 3].0])
+        } else {
+            u32::MAX
+        }
+    };
+
+    loop {
+        let (i, rank) = parts[..parts.len() - 1]
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &(_, rank))| rank)
+            .map(|(i, &(_, rank))| (i, rank))
+            .expect("piece has at least two bytes");
+        if rank == u32::MAX {
+            break;
+        }
+
+        if i > 0 {
+            parts[i - 1].1 = merged_rank(&parts, i - 1);
+        }
+        parts[i].1 = merged_rank(&parts, i);
+        parts.remove(i + 1);
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(encoding: Encoding, text: &str) -> Vec<String> {
+        let pattern = pattern(encoding);
+        pieces(&pattern, text).map(str::to_string).collect()
+    }
+
+    #[test]
+    fn splits_words_with_leading_space() {
+        assert_eq!(split(Encoding::Cl100k, "Hello world"), vec!["Hello", " world"]);
+    }
+
+    #[test]
+    fn whitespace_run_leaves_one_space_for_next_word() {
+        assert_eq!(split(Encoding::Cl100k, "a   b"), vec!["a", "  ", " b"]);
+        assert_eq!(split(Encoding::Cl100k, "a   "), vec!["a", "   "]);
+    }
+
+    #[test]
+    fn newlines_and_indentation() {
+        assert_eq!(split(Encoding::Cl100k, "x\n    at y"), vec!["x", "\n", "   ", " at", " y"]);
+    }
+
+    #[test]
+    fn numbers_split_in_threes() {
+        assert_eq!(split(Encoding::Cl100k, "14985:18"), vec!["149", "85", ":", "18"]);
+    }
+
+    #[test]
+    fn contractions_and_punctuation() {
+        assert_eq!(split(Encoding::Cl100k, "can't (reading"), vec!["can", "'t", " (", "reading"]);
+    }
+
+    #[test]
+    fn o200k_splits_camel_case() {
+        assert_eq!(split(Encoding::O200k, "TypeError: foo"), vec!["Type", "Error", ":", " foo"]);
+    }
+
+    #[test]
+    fn o200k_attaches_leading_punctuation_to_words() {
+        assert_eq!(split(Encoding::O200k, "src/App.tsx"), vec!["src", "/App", ".tsx"]);
+    }
+
+    #[test]
+    fn merge_prefers_lowest_rank() {
+        let ranks: HashMap<Vec<u8>, u32> =
+            [("a", 0), ("b", 1), ("c", 2), ("ab", 4), ("bc", 3)].map(|(t, r)| (t.as_bytes().to_vec(), r)).into();
+        // "bc" (rank 3) merges before "ab" (rank 4): a|bc
+        assert_eq!(count_piece(&ranks, b"abc"), 2);
+        assert_eq!(count_piece(&ranks, b"ab"), 1);
+        assert_eq!(count_piece(&ranks, b""), 0);
+    }
+}
@@ -0,0 +1,228 @@
+//! Offline BPE tokenizer for token-count statistics.
+//!
+//! Reads OpenAI's `.tiktoken` vocabulary files (`cl100k_base`, `o200k_base`)
+//! from local disk and counts tokens the same way tiktoken does: split the text
+//! with the encoding's pre-tokenizer pattern, then byte-pair merge each piece by
+//! rank. Nothing is downloaded; [`Tokenizer::load`] looks for the vocabulary in
+//! the directories listed by [`search_dirs`].
+
+mod bpe;
+mod vocab;
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a directory that holds `.tiktoken` files.
+pub const TOKENIZER_DIR_ENV: &str = "ERROR_TOON_TOKENIZER_DIR";
+
+/// A supported BPE encoding: vocabulary file name and pre-tokenizer pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// GPT-3.5 / GPT-4 vocabulary.
+    Cl100k,
+    /// GPT-4o vocabulary.
+    O200k,
+}
+
+impl Encoding {
+    pub const ALL: &'static [Encoding] = &[Self::Cl100k, Self::O200k];
+
+    /// Short name accepted by `--tokenizer`, e.g. `cl100k`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cl100k => "cl100k",
+            Self::O200k => "o200k",
+        }
+    }
+
+    /// Look up an encoding by short name or tiktoken name (`cl100k` or `cl100k_base`).
+    pub fn from_name(name: &str) -> Option<Encoding> {
+        let name = name.strip_suffix("_base").unwrap_or(name);
+        Self::ALL.iter().find(|e| e.name() == name).copied()
+    }
+
+    /// Vocabulary file name, as published by tiktoken.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Cl100k => "cl100k_base.tiktoken",
+            Self::O200k => "o200k_base.tiktoken",
+        }
+    }
+
+    /// Where tiktoken publishes the vocabulary, for error messages.
+    pub fn download_url(&self) -> String {
+        format!("https://openaipublic.blob.core.windows.net/encodings/{}", self.file_name())
+    }
+}
+
+/// Error loading a tokenizer vocabulary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenizerError {
+    pub message: String,
+}
+
+impl TokenizerError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl fmt::Display for TokenizerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for TokenizerError {}
+
+/// A loaded BPE vocabulary that counts tokens.
+pub struct Tokenizer {
+    encoding: Encoding,
+    ranks: HashMap<Vec<u8>, u32>,
+    pattern: Regex,
+}
+
+impl Tokenizer {
+    /// Build a tokenizer from the contents of a `.tiktoken` file (`base64 rank` per line).
+    pub fn from_tiktoken(data: &str, encoding: Encoding) -> Result<Self, TokenizerError> {
+        let ranks = vocab::parse(data)?;
+        Ok(Self { encoding, ranks, pattern: bpe::pattern(encoding) })
+    }
+
+    /// Read a `.tiktoken` file. The encoding is taken from the file name
+    /// (`o200k` anywhere in it selects o200k, anything else cl100k).
+    pub fn from_file(path: &Path) -> Result<Self, TokenizerError> {
+        let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        let encoding = if file_name.contains("o200k") { Encoding::O200k } else { Encoding::Cl100k };
+
+        let data = fs::read_to_string(path)
+            .map_err(|e| TokenizerError::new(format!("failed to read {}: {}", path.display(), e)))?;
+        Self::from_tiktoken(&data, encoding)
+            .map_err(|e| TokenizerError::new(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Load by encoding name (`cl100k`, `o200k`), searching [`search_dirs`] for its
+    /// vocabulary file, or by path to a `.tiktoken` file.
+    pub fn load(spec: &str) -> Result<Self, TokenizerError> {
+        let Some(encoding) = Encoding::from_name(spec) else {
+            return Self::from_file(Path::new(spec));
+        };
+
+        let dirs = search_dirs();
+        match dirs.iter().map(|dir| dir.join(encoding.file_name())).find(|path| path.is_file()) {
+            Some(path) => Self::from_file(&path),
+            None => {
+                let searched: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+                Err(TokenizerError::new(format!(
+                    "{} not found (searched: {}). Download it from {} into one of these directories or pass its path",
+                    encoding.file_name(),
+                    searched.join(", "),
+                    encoding.download_url()
+                )))
+            }
+        }
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Number of tokens `text` encodes to. Special tokens are not recognized.
+    pub fn count(&self, text: &str) -> usize {
+        bpe::pieces(&self.pattern, text)
+            .map(|piece| bpe::count_piece(&self.ranks, piece.as_bytes()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+impl Tokenizer {
+    /// Tokenizer whose vocabulary is the 256 single bytes followed by `merges`, in rank order.
+    pub(crate) fn with_merges(merges: &[&str]) -> Self {
+        let mut tokens: Vec<Vec<u8>> = (0..=255u8).map(|b| vec![b]).collect();
+        tokens.extend(merges.iter().map(|m| m.as_bytes().to_vec()));
+        let data: String = tokens
+            .iter()
+            .enumerate()
+            .map(|(rank, bytes)| format!("{} {}\n", vocab::encode_base64(bytes), rank))
+            .collect();
+        Self::from_tiktoken(&data, Encoding::Cl100k).unwrap()
+    }
+}
+
+/// Directories searched for `.tiktoken` files, in order: `$ERROR_TOON_TOKENIZER_DIR`,
+/// then `$XDG_DATA_HOME/error-toon` (default `~/.local/share/error-toon`).
+pub fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os(TOKENIZER_DIR_ENV) {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+        dirs.push(PathBuf::from(data_home).join("error-toon"));
+    } else if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".local/share/error-toon"));
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERGES: &[&str] = &["he", "ll", "hell", "hello", " w", " wor", " world", "Er", "ror", "Error", "  "];
+
+    #[test]
+    fn encoding_names_round_trip() {
+        for encoding in Encoding::ALL {
+            assert_eq!(Encoding::from_name(encoding.name()), Some(*encoding));
+        }
+        assert_eq!(Encoding::from_name("o200k_base"), Some(Encoding::O200k));
+        assert_eq!(Encoding::from_name("p50k"), None);
+    }
+
+    #[test]
+    fn counts_merged_tokens() {
+        let tokenizer = Tokenizer::with_merges(MERGES);
+        assert_eq!(tokenizer.count("hello world"), 2);
+        assert_eq!(tokenizer.count("Error"), 1);
+        assert_eq!(tokenizer.count("hi"), 2);
+        assert_eq!(tokenizer.count(""), 0);
+    }
+
+    #[test]
+    fn counts_bytes_of_unknown_text() {
+        let tokenizer = Tokenizer::with_merges(MERGES);
+        // "é" is two UTF-8 bytes with no merge between them
+        assert_eq!(tokenizer.count("é"), 2);
+    }
+
+    #[test]
+    fn loads_from_file_and_infers_encoding() {
+        let dir = std::env::temp_dir().join(format!("error-toon-tokenizer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("o200k_base.tiktoken");
+        let vocab: String = MERGES
+            .iter()
+            .map(|m| m.as_bytes().to_vec())
+            .chain((0..=255u8).map(|b| vec![b]))
+            .enumerate()
+            .map(|(rank, bytes)| format!("{} {}\n", vocab::encode_base64(&bytes), rank))
+            .collect();
+        fs::write(&path, vocab).unwrap();
+
+        let tokenizer = Tokenizer::load(path.to_str().unwrap()).unwrap();
+        assert_eq!(tokenizer.encoding(), Encoding::O200k);
+        assert_eq!(tokenizer.count("hello world"), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file_reports_path() {
+        let err = Tokenizer::load("/nonexistent/cl100k_base.tiktoken").err().unwrap();
+        assert!(err.message.contains("/nonexistent/cl100k_base.tiktoken"));
+    }
+}
@@ -0,0 +1,107 @@
+//! `.tiktoken` vocabulary files: one `base64-token rank` pair per line.
+
+use super::TokenizerError;
+use std::collections::HashMap;
+
+pub(super) fn parse(data: &str) -> Result<HashMap<Vec<u8>, u32>, TokenizerError> {
+    let mut ranks = HashMap::new();
+
+    for (i, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = || TokenizerError::new(format!("line {}: expected `<base64 token> <rank>`", i + 1));
+
+        let (token, rank) = line.split_once(' ').ok_or_else(invalid)?;
+        let token = decode_base64(token).ok_or_else(invalid)?;
+        let rank = rank.trim().parse::<u32>().map_err(|_| invalid())?;
+        ranks.insert(token, rank);
+    }
+
+    if ranks.is_empty() {
+        return Err(TokenizerError::new("vocabulary is empty"));
+    }
+    Ok(ranks)
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decode standard padded base64, or `None` if `s` is not valid base64.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    for chunk in s.chunks(4) {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 {
+            return None;
+        }
+
+        let mut buf = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+            buf = (buf << 6) | value;
+        }
+        buf <<= 6 * padding as u32;
+
+        let bytes = buf.to_be_bytes();
+        out.extend_from_slice(&bytes[1..4 - padding]);
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+pub(super) fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, buf[0], buf[1], buf[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64() {
+        assert_eq!(decode_base64("SGVsbG8="), Some(b"Hello".to_vec()));
+        assert_eq!(decode_base64("IHdvcmxk"), Some(b" world".to_vec()));
+        assert_eq!(decode_base64("IQ=="), Some(b"!".to_vec()));
+        assert_eq!(decode_base64("abc"), None);
+        assert_eq!(decode_base64("a*c="), None);
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        for bytes in [&b""[..], b"a", b"ab", b"abc", &[0xff, 0x00, 0x80]] {
+            assert_eq!(decode_base64(&encode_base64(bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn parses_ranks() {
+        let ranks = parse("SGVsbG8= 0\nIHdvcmxk 1\n").unwrap();
+        assert_eq!(ranks.get(&b"Hello"[..]), Some(&0));
+        assert_eq!(ranks.get(&b" world"[..]), Some(&1));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse("SGVsbG8=\n").unwrap_err().message.contains("line 1"));
+        assert!(parse("").is_err());
+    }
+}
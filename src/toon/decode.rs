@@ -0,0 +1,472 @@
+//! TOON decoder for the subset of the spec that the encoder emits.
+//!
+//! Also accepts the inline-object form `stats{orig,comp,pct}: 1,2,3` written by
+//! error-toon 1.2 and earlier, so stored output can still be read back.
+
+use super::{Delimiter, Value};
+use std::fmt;
+
+/// A TOON syntax or structure error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 1-based line of a syntax error; `None` when the document as a whole is invalid.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl DecodeError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> Self {
+        Self { line: Some(line), message: message.into() }
+    }
+
+    pub(crate) fn document(message: impl Into<String>) -> Self {
+        Self { line: None, message: message.into() }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+type Result<T> = std::result::Result<T, DecodeError>;
+
+/// Decode a TOON document. A document of `key: value` lines decodes to
+/// [`Value::Object`]; a lone `[N]:` header decodes to [`Value::Array`].
+pub fn decode(input: &str) -> Result<Value> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(i, text)| {
+            let content = text.trim_start_matches(' ');
+            let spaces = text.len() - content.len();
+            if spaces % 2 != 0 {
+                return Err(DecodeError::new(i + 1, "indentation must be a multiple of two spaces"));
+            }
+            Ok(Line { number: i + 1, depth: spaces / 2, text: content.trim_end_matches('\r') })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut parser = Parser { lines, pos: 0 };
+    let value = match parser.lines.first() {
+        Some(line) if line.text.starts_with('[') => {
+            let line = line.clone();
+            parser.pos += 1;
+            parser.field_value(&line, 0)?.1
+        }
+        _ => Value::Object(parser.object(0)?),
+    };
+
+    if let Some(line) = parser.lines.get(parser.pos) {
+        return Err(DecodeError::new(line.number, "unexpected indentation"));
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone)]
+struct Line<'a> {
+    number: usize,
+    depth: usize,
+    text: &'a str,
+}
+
+struct Parser<'a> {
+    lines: Vec<Line<'a>>,
+    pos: usize,
+}
+
+/// The parts of a `key[N]{fields}: value` line.
+struct Header<'a> {
+    key: String,
+    length: Option<usize>,
+    delimiter: Delimiter,
+    fields: Option<Vec<String>>,
+    value: Option<&'a str>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Line<'a>> {
+        self.lines.get(self.pos)
+    }
+
+    /// Parse consecutive `key: ...` lines at `depth` into object fields.
+    fn object(&mut self, depth: usize) -> Result<Vec<(String, Value)>> {
+        let mut fields = Vec::new();
+        while let Some(line) = self.peek() {
+            if line.depth != depth || is_list_item(line.text) {
+                break;
+            }
+            let line = line.clone();
+            self.pos += 1;
+            fields.push(self.field_value(&line, depth)?);
+        }
+        Ok(fields)
+    }
+
+    /// Parse one field line (already consumed) plus any child lines.
+    fn field_value(&mut self, line: &Line<'a>, depth: usize) -> Result<(String, Value)> {
+        let header = parse_header(line)?;
+        let key = header.key.clone();
+
+        let value = match (header.length, &header.fields) {
+            // key[N]{a,b}: followed by N rows
+            (Some(length), Some(fields)) => self.table(line, depth, length, fields, header.delimiter)?,
+            // key[N]: a,b,c
+            (Some(length), None) if header.value.is_some() => {
+                let cells = split_cells(header.value.unwrap_or_default(), header.delimiter, line.number)?;
+                if cells.len() != length {
+                    return Err(DecodeError::new(line.number, format!("expected {} values, found {}", length, cells.len())));
+                }
+                Value::Array(cells.iter().map(|c| parse_primitive(c, line.number)).collect::<Result<_>>()?)
+            }
+            // key[N]: followed by N list items
+            (Some(length), None) => self.list(line, depth, length)?,
+            // Legacy inline object: key{a,b}: 1,2
+            (None, Some(fields)) => {
+                let cells = split_cells(header.value.unwrap_or_default(), Delimiter::Comma, line.number)?;
+                if cells.len() != fields.len() {
+                    return Err(DecodeError::new(line.number, format!("expected {} values, found {}", fields.len(), cells.len())));
+                }
+                Value::Object(
+                    fields
+                        .iter()
+                        .zip(&cells)
+                        .map(|(f, c)| Ok((f.clone(), parse_primitive(c, line.number)?)))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            (None, None) => match header.value {
+                Some(value) => parse_primitive(value, line.number)?,
+                None => Value::Object(self.object(depth + 1)?),
+            },
+        };
+
+        Ok((key, value))
+    }
+
+    fn table(&mut self, line: &Line<'a>, depth: usize, length: usize, fields: &[String], delimiter: Delimiter) -> Result<Value> {
+        let mut rows = Vec::with_capacity(length);
+        while rows.len() < length {
+            let Some(row) = self.peek().filter(|l| l.depth == depth + 1).cloned() else {
+                return Err(DecodeError::new(line.number, format!("expected {} rows, found {}", length, rows.len())));
+            };
+            self.pos += 1;
+
+            let cells = split_cells(row.text, delimiter, row.number)?;
+            if cells.len() != fields.len() {
+                return Err(DecodeError::new(row.number, format!("expected {} cells, found {}", fields.len(), cells.len())));
+            }
+            let object = fields
+                .iter()
+                .zip(&cells)
+                .map(|(f, c)| Ok((f.clone(), parse_primitive(c, row.number)?)))
+                .collect::<Result<_>>()?;
+            rows.push(Value::Object(object));
+        }
+        Ok(Value::Array(rows))
+    }
+
+    fn list(&mut self, line: &Line<'a>, depth: usize, length: usize) -> Result<Value> {
+        let mut items = Vec::with_capacity(length);
+        while items.len() < length {
+            let Some(item) = self.peek().filter(|l| l.depth == depth + 1 && is_list_item(l.text)).cloned() else {
+                return Err(DecodeError::new(line.number, format!("expected {} items, found {}", length, items.len())));
+            };
+            self.pos += 1;
+            items.push(self.list_item(&item, depth + 1)?);
+        }
+        Ok(Value::Array(items))
+    }
+
+    /// Parse a `- ...` line at `depth`. An object item's first field sits on the
+    /// hyphen line and its remaining fields continue at `depth + 1`.
+    fn list_item(&mut self, line: &Line<'a>, depth: usize) -> Result<Value> {
+        let content = line.text.strip_prefix('-').unwrap_or_default().trim_start();
+        if content.is_empty() {
+            return Ok(Value::Object(Vec::new()));
+        }
+
+        let inner = Line { number: line.number, depth: depth + 1, text: content };
+        if content.starts_with('[') {
+            return Ok(self.field_value(&inner, depth + 1)?.1);
+        }
+        if find_unquoted(content, ':').is_none() {
+            return parse_primitive(content, line.number);
+        }
+
+        let mut fields = vec![self.field_value(&inner, depth + 1)?];
+        fields.extend(self.object(depth + 1)?);
+        Ok(Value::Object(fields))
+    }
+}
+
+fn is_list_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+fn parse_header<'a>(line: &Line<'a>) -> Result<Header<'a>> {
+    let text = line.text;
+    let err = |message: &str| DecodeError::new(line.number, message);
+
+    // Key: quoted string or bare run up to '[', '{' or ':'
+    let (key, mut rest) = if text.starts_with('"') {
+        let end = closing_quote(text).ok_or_else(|| err("unterminated quoted key"))?;
+        (unescape(&text[1..end], line.number)?, &text[end + 1..])
+    } else {
+        let end = text.find(['[', '{', ':']).ok_or_else(|| err("expected ':' after key"))?;
+        (text[..end].trim_end().to_string(), &text[end..])
+    };
+
+    let mut length = None;
+    let mut delimiter = Delimiter::Comma;
+    if let Some(after) = rest.strip_prefix('[') {
+        let close = after.find(']').ok_or_else(|| err("unterminated '[' in array header"))?;
+        let mut marker = after[..close].trim_start_matches('#');
+        if let Some(stripped) = marker.strip_suffix('\t') {
+            delimiter = Delimiter::Tab;
+            marker = stripped;
+        } else if let Some(stripped) = marker.strip_suffix('|') {
+            delimiter = Delimiter::Pipe;
+            marker = stripped;
+        }
+        length = Some(marker.parse().map_err(|_| err("invalid array length"))?);
+        rest = &after[close + 1..];
+    }
+
+    let mut fields = None;
+    if let Some(after) = rest.strip_prefix('{') {
+        let close = after.find('}').ok_or_else(|| err("unterminated '{' in field list"))?;
+        let names = split_cells(&after[..close], delimiter, line.number)?
+            .iter()
+            .map(|name| {
+                if name.starts_with('"') {
+                    unescape(name.trim_matches('"'), line.number)
+                } else {
+                    Ok(name.to_string())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        fields = Some(names);
+        rest = &after[close + 1..];
+    }
+
+    let rest = rest.strip_prefix(':').ok_or_else(|| err("expected ':' after key"))?;
+    let value = match rest.trim() {
+        "" => None,
+        _ => Some(rest.strip_prefix(' ').unwrap_or(rest)),
+    };
+
+    Ok(Header { key, length, delimiter, fields, value })
+}
+
+/// Split a row on `delimiter`, ignoring delimiters inside quoted cells.
+fn split_cells(text: &str, delimiter: Delimiter, line: usize) -> Result<Vec<&str>> {
+    let delimiter = delimiter.as_char();
+    let mut cells = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                cells.push(text[start..i].trim_matches(' '));
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if in_quotes {
+        return Err(DecodeError::new(line, "unterminated quoted value"));
+    }
+    cells.push(text[start..].trim_matches(' '));
+    Ok(cells)
+}
+
+fn parse_primitive(token: &str, line: usize) -> Result<Value> {
+    let token = token.trim_matches(' ');
+
+    if token.starts_with('"') {
+        return match closing_quote(token) {
+            Some(end) if end == token.len() - 1 => Ok(Value::Str(unescape(&token[1..end], line)?)),
+            _ => Err(DecodeError::new(line, "invalid quoted value")),
+        };
+    }
+
+    Ok(match token {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ if is_number(token) => match token.parse::<i64>() {
+            Ok(n) => Value::Int(n),
+            Err(_) => token.parse::<f64>().map(Value::Float).unwrap_or_else(|_| Value::Str(token.to_string())),
+        },
+        _ => Value::Str(token.to_string()),
+    })
+}
+
+/// Decimal numbers only; forbidden leading zeros (`05`) stay strings.
+fn is_number(token: &str) -> bool {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && !leading_zero
+        && digits.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+        && token.parse::<f64>().is_ok()
+}
+
+/// Byte index of the quote closing the string that opens at index 0.
+fn closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn find_unquoted(text: &str, needle: char) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == needle && !in_quotes => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(s: &str, line: usize) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            _ => return Err(DecodeError::new(line, "invalid escape sequence")),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toon::encode;
+
+    fn obj(fields: Vec<(&str, Value)>) -> Value {
+        Value::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    #[test]
+    fn decodes_primitives() {
+        let value = decode("a: 1\nb: -2.5\nc: true\nd: null\ne: \"42\"\nf: 05\ng: hello world").unwrap();
+        assert_eq!(
+            value,
+            obj(vec![
+                ("a", Value::Int(1)),
+                ("b", Value::Float(-2.5)),
+                ("c", Value::Bool(true)),
+                ("d", Value::Null),
+                ("e", "42".into()),
+                ("f", "05".into()),
+                ("g", "hello world".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn decodes_quoted_strings_with_escapes() {
+        let value = decode("issue: \"TypeError: a, \\\"b\\\"\\nnext\"").unwrap();
+        assert_eq!(value, obj(vec![("issue", "TypeError: a, \"b\"\nnext".into())]));
+    }
+
+    #[test]
+    fn decodes_tables_with_each_delimiter() {
+        let expected = obj(vec![(
+            "frames",
+            Value::Array(vec![obj(vec![("fn", "App".into()), ("loc", "App.tsx:12".into())])]),
+        )]);
+        assert_eq!(decode("frames[1]{fn,loc}:\n  App,\"App.tsx:12\"").unwrap(), expected);
+        assert_eq!(decode("frames[1\t]{fn\tloc}:\n  App\t\"App.tsx:12\"").unwrap(), expected);
+        assert_eq!(decode("frames[1|]{fn|loc}:\n  App|\"App.tsx:12\"").unwrap(), expected);
+    }
+
+    #[test]
+    fn decodes_legacy_inline_object() {
+        let value = decode("stats{orig,comp,pct}: 4521,198,95").unwrap();
+        assert_eq!(
+            value,
+            obj(vec![("stats", obj(vec![("orig", Value::Int(4521)), ("comp", Value::Int(198)), ("pct", Value::Int(95))]))])
+        );
+    }
+
+    #[test]
+    fn decodes_inline_arrays_and_root_arrays() {
+        assert_eq!(decode("tags[2]: a,\"b,c\"").unwrap(), obj(vec![("tags", Value::Array(vec!["a".into(), "b,c".into()]))]));
+        assert_eq!(decode("[2]: 1,2").unwrap(), Value::Array(vec![Value::Int(1), Value::Int(2)]));
+        assert_eq!(decode("none[0]:").unwrap(), obj(vec![("none", Value::Array(vec![]))]));
+    }
+
+    #[test]
+    fn rejects_length_mismatch() {
+        let err = decode("frames[2]{fn,loc}:\n  App,x").unwrap_err();
+        assert_eq!(err.line, Some(1));
+        assert!(err.message.contains("expected 2 rows"));
+    }
+
+    #[test]
+    fn rejects_odd_indentation() {
+        assert_eq!(decode("a:\n   b: 1").unwrap_err().line, Some(2));
+    }
+
+    #[test]
+    fn round_trips_encoder_output() {
+        let value = obj(vec![
+            (
+                "errors",
+                Value::Array(vec![
+                    obj(vec![("type", "A".into()), ("issue", "x: y, z".into())]),
+                    obj(vec![
+                        ("type", "B".into()),
+                        ("frames", Value::Array(vec![obj(vec![("fn", "f".into()), ("loc", "g.ts:1".into())])])),
+                    ]),
+                    obj(vec![("rows", Value::Array(vec![obj(vec![("a", Value::Int(1))])])), ("n", Value::Int(2))]),
+                    Value::Array(vec![Value::Int(1), "two".into()]),
+                    " padded ".into(),
+                ]),
+            ),
+            ("stats", obj(vec![("orig", Value::Int(10)), ("pct", Value::Float(0.5))])),
+        ]);
+
+        for delimiter in [Delimiter::Comma, Delimiter::Tab, Delimiter::Pipe] {
+            let text = encode(&value, delimiter);
+            assert_eq!(decode(&text).unwrap(), value, "delimiter {:?}:\n{}", delimiter, text);
+        }
+    }
+}
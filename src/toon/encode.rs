@@ -0,0 +1,328 @@
+//! TOON encoder.
+
+use super::{Delimiter, Value};
+
+/// Encode `value` as a TOON document (two-space indentation, no trailing newline).
+pub fn encode(value: &Value, delimiter: Delimiter) -> String {
+    let mut encoder = Encoder { lines: Vec::new(), delimiter };
+
+    match value {
+        Value::Object(fields) => encoder.object_fields(fields, 0),
+        Value::Array(items) => encoder.array("", items, 0),
+        primitive => {
+            let line = encoder.primitive(primitive);
+            encoder.lines.push(line);
+        }
+    }
+
+    encoder.lines.join("\n")
+}
+
+struct Encoder {
+    lines: Vec<String>,
+    delimiter: Delimiter,
+}
+
+impl Encoder {
+    fn push(&mut self, depth: usize, text: String) {
+        self.lines.push(format!("{}{}", indent(depth), text));
+    }
+
+    fn object_fields(&mut self, fields: &[(String, Value)], depth: usize) {
+        for (key, value) in fields {
+            self.field(key, value, depth);
+        }
+    }
+
+    fn field(&mut self, key: &str, value: &Value, depth: usize) {
+        let key = encode_key(key);
+        match value {
+            Value::Object(fields) => {
+                self.push(depth, format!("{}:", key));
+                self.object_fields(fields, depth + 1);
+            }
+            Value::Array(items) => self.array(&key, items, depth),
+            primitive => {
+                let value = self.primitive(primitive);
+                self.push(depth, format!("{}: {}", key, value));
+            }
+        }
+    }
+
+    /// Write `key[N]...` in inline, tabular, or list form depending on the items.
+    fn array(&mut self, key: &str, items: &[Value], depth: usize) {
+        let delimiter = self.delimiter.as_char().to_string();
+        let header = format!("{}[{}{}]", key, items.len(), self.delimiter.header_marker());
+
+        if items.iter().all(Value::is_primitive) {
+            let values: Vec<String> = items.iter().map(|v| self.primitive(v)).collect();
+            if values.is_empty() {
+                self.push(depth, format!("{}:", header));
+            } else {
+                self.push(depth, format!("{}: {}", header, values.join(&delimiter)));
+            }
+        } else if let Some(fields) = tabular_fields(items) {
+            let fields: Vec<String> = fields.iter().map(|f| encode_key(f)).collect();
+            self.push(depth, format!("{}{{{}}}:", header, fields.join(&delimiter)));
+            for item in items {
+                if let Value::Object(cells) = item {
+                    let row: Vec<String> = cells.iter().map(|(_, v)| self.primitive(v)).collect();
+                    self.push(depth + 1, row.join(&delimiter));
+                }
+            }
+        } else {
+            self.push(depth, format!("{}:", header));
+            for item in items {
+                self.list_item(item, depth + 1);
+            }
+        }
+    }
+
+    /// Write one `- ` list item. Objects put their first field on the hyphen line.
+    fn list_item(&mut self, item: &Value, depth: usize) {
+        let start = self.lines.len();
+        match item {
+            Value::Object(fields) if !fields.is_empty() => {
+                let (first_key, first_value) = &fields[0];
+                self.field(first_key, first_value, depth + 1);
+                self.hyphenate(start, depth);
+                self.object_fields(&fields[1..], depth + 1);
+            }
+            Value::Object(_) => self.push(depth, "-".to_string()),
+            Value::Array(items) => {
+                self.array("", items, depth + 1);
+                self.hyphenate(start, depth);
+            }
+            primitive => {
+                let value = self.primitive(primitive);
+                self.push(depth, format!("- {}", value));
+            }
+        }
+    }
+
+    /// Replace the indentation of line `index` (written at `depth + 1`) with a `- ` marker at `depth`.
+    fn hyphenate(&mut self, index: usize, depth: usize) {
+        let line = &self.lines[index];
+        let content = &line[indent(depth + 1).len()..];
+        self.lines[index] = format!("{}- {}", indent(depth), content);
+    }
+
+    fn primitive(&self, value: &Value) -> String {
+        match value {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Int(n) => n.to_string(),
+            Value::Float(f) => format_float(*f),
+            Value::Str(s) => encode_string(s, self.delimiter),
+            Value::Array(_) | Value::Object(_) => unreachable!("primitive() called on a container"),
+        }
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+/// Field names shared by every item, if `items` can be written as a table:
+/// all objects with the same keys in the same order and only primitive values.
+fn tabular_fields(items: &[Value]) -> Option<Vec<&str>> {
+    let Some(Value::Object(first)) = items.first() else {
+        return None;
+    };
+    if first.is_empty() {
+        return None;
+    }
+    let keys: Vec<&str> = first.iter().map(|(k, _)| k.as_str()).collect();
+
+    let uniform = items.iter().all(|item| match item {
+        Value::Object(fields) => {
+            fields.len() == keys.len()
+                && fields.iter().zip(&keys).all(|((k, v), key)| k == key && v.is_primitive())
+        }
+        _ => false,
+    });
+
+    uniform.then_some(keys)
+}
+
+/// Canonical number form: no exponent, no trailing `.0`, `-0` as `0`, non-finite as `null`.
+fn format_float(f: f64) -> String {
+    if !f.is_finite() {
+        return "null".to_string();
+    }
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    f.to_string()
+}
+
+/// Keys matching `[A-Za-z_][A-Za-z0-9_.]*` stay bare; anything else is quoted.
+fn encode_key(key: &str) -> String {
+    let mut chars = key.chars();
+    let bare = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+
+    if bare {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn encode_string(s: &str, delimiter: Delimiter) -> String {
+    if needs_quotes(s, delimiter) {
+        quote(s)
+    } else {
+        s.to_string()
+    }
+}
+
+/// TOON quoting rules: quote anything a decoder could misread as structure,
+/// a number, a literal, or a delimiter-separated value.
+pub(crate) fn needs_quotes(s: &str, delimiter: Delimiter) -> bool {
+    s.is_empty()
+        || s != s.trim()
+        || matches!(s, "true" | "false" | "null")
+        || s.parse::<f64>().is_ok()
+        || s.starts_with('-')
+        || s.contains(delimiter.as_char())
+        || s.chars().any(|c| matches!(c, ':' | '"' | '\\' | '[' | ']' | '{' | '}') || c.is_control())
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obj(fields: Vec<(&str, Value)>) -> Value {
+        Value::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    #[test]
+    fn leaves_safe_strings_bare() {
+        assert_eq!(encode_string("Minified React error #130", Delimiter::Comma), "Minified React error #130");
+        assert_eq!(encode_string("MDXContent", Delimiter::Comma), "MDXContent");
+    }
+
+    #[test]
+    fn quotes_strings_with_structural_characters() {
+        assert_eq!(encode_string("Guide.mdx:79", Delimiter::Comma), "\"Guide.mdx:79\"");
+        assert_eq!(encode_string("foo, bar", Delimiter::Comma), "\"foo, bar\"");
+        assert_eq!(encode_string(" leading", Delimiter::Comma), "\" leading\"");
+        assert_eq!(encode_string("a[0]", Delimiter::Comma), "\"a[0]\"");
+        assert_eq!(encode_string("- item", Delimiter::Comma), "\"- item\"");
+        assert_eq!(encode_string("", Delimiter::Comma), "\"\"");
+    }
+
+    #[test]
+    fn quotes_literal_and_numeric_lookalikes() {
+        assert_eq!(encode_string("true", Delimiter::Comma), "\"true\"");
+        assert_eq!(encode_string("null", Delimiter::Comma), "\"null\"");
+        assert_eq!(encode_string("42", Delimiter::Comma), "\"42\"");
+        assert_eq!(encode_string("05", Delimiter::Comma), "\"05\"");
+        assert_eq!(encode_string("1e-6", Delimiter::Comma), "\"1e-6\"");
+    }
+
+    #[test]
+    fn escapes_only_spec_escapes() {
+        assert_eq!(quote("say \"hi\"\n\\"), "\"say \\\"hi\\\"\\n\\\\\"");
+    }
+
+    #[test]
+    fn quoting_follows_active_delimiter() {
+        assert_eq!(encode_string("a,b", Delimiter::Pipe), "a,b");
+        assert_eq!(encode_string("a|b", Delimiter::Pipe), "\"a|b\"");
+        assert_eq!(encode_string("a\tb", Delimiter::Tab), "\"a\\tb\"");
+    }
+
+    #[test]
+    fn quotes_non_identifier_keys() {
+        assert_eq!(encode_key("frames"), "frames");
+        assert_eq!(encode_key("user.name"), "user.name");
+        assert_eq!(encode_key("my key"), "\"my key\"");
+        assert_eq!(encode_key("1st"), "\"1st\"");
+    }
+
+    #[test]
+    fn encodes_nested_objects_and_numbers() {
+        let value = obj(vec![
+            ("type", "TYPE_ERROR".into()),
+            ("stats", obj(vec![("orig", Value::Int(100)), ("ratio", Value::Float(0.5))])),
+        ]);
+        assert_eq!(encode(&value, Delimiter::Comma), "type: TYPE_ERROR\nstats:\n  orig: 100\n  ratio: 0.5");
+    }
+
+    #[test]
+    fn encodes_canonical_floats() {
+        assert_eq!(format_float(1.0), "1");
+        assert_eq!(format_float(-0.0), "0");
+        assert_eq!(format_float(f64::NAN), "null");
+        assert_eq!(format_float(0.25), "0.25");
+    }
+
+    #[test]
+    fn encodes_inline_primitive_arrays() {
+        let value = obj(vec![("tags", Value::Array(vec!["a".into(), "b c".into()])), ("none", Value::Array(vec![]))]);
+        assert_eq!(encode(&value, Delimiter::Comma), "tags[2]: a,b c\nnone[0]:");
+        assert_eq!(encode(&value, Delimiter::Pipe), "tags[2|]: a|b c\nnone[0|]:");
+    }
+
+    #[test]
+    fn encodes_uniform_objects_as_table() {
+        let frames = Value::Array(vec![
+            obj(vec![("fn", "App".into()), ("loc", "App.tsx:12".into())]),
+            obj(vec![("fn", "render, main".into()), ("loc", "".into())]),
+        ]);
+        let value = obj(vec![("frames", frames)]);
+
+        assert_eq!(
+            encode(&value, Delimiter::Comma),
+            "frames[2]{fn,loc}:\n  App,\"App.tsx:12\"\n  \"render, main\",\"\""
+        );
+        assert_eq!(
+            encode(&value, Delimiter::Tab),
+            "frames[2\t]{fn\tloc}:\n  App\t\"App.tsx:12\"\n  render, main\t\"\""
+        );
+    }
+
+    #[test]
+    fn encodes_mixed_objects_as_list_items() {
+        let errors = Value::Array(vec![
+            obj(vec![("type", "A".into()), ("issue", "x".into())]),
+            obj(vec![
+                ("type", "B".into()),
+                ("frames", Value::Array(vec![obj(vec![("fn", "f".into()), ("loc", "g".into())])])),
+            ]),
+        ]);
+        let value = obj(vec![("errors", errors)]);
+
+        assert_eq!(
+            encode(&value, Delimiter::Comma),
+            "errors[2]:\n  - type: A\n    issue: x\n  - type: B\n    frames[1]{fn,loc}:\n      f,g"
+        );
+    }
+
+    #[test]
+    fn list_item_with_table_as_first_field_nests_rows_two_levels() {
+        let item = obj(vec![("rows", Value::Array(vec![obj(vec![("a", Value::Int(1))])])), ("n", Value::Int(2))]);
+        let value = obj(vec![("items", Value::Array(vec![item, Value::Int(3)]))]);
+
+        assert_eq!(encode(&value, Delimiter::Comma), "items[2]:\n  - rows[1]{a}:\n      1\n    n: 2\n  - 3");
+    }
+}
@@ -0,0 +1,76 @@
+//! Minimal TOON (Token-Oriented Object Notation) document model, encoder, and decoder.
+//!
+//! Follows the [TOON spec](https://github.com/toon-format/toon): strings are
+//! quoted only when required, arrays carry `[N]` length markers, uniform arrays
+//! of objects become tables (`frames[2]{fn,loc}:`), and the array delimiter can
+//! be comma, tab, or pipe.
+
+mod decode;
+mod encode;
+
+pub use decode::{decode, DecodeError};
+pub use encode::encode;
+
+/// A TOON value. Objects keep their field order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn is_primitive(&self) -> bool {
+        !matches!(self, Self::Array(_) | Self::Object(_))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Self::Int(n as i64)
+    }
+}
+
+/// Delimiter used between array values and table cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delimiter {
+    #[default]
+    Comma,
+    Tab,
+    Pipe,
+}
+
+impl Delimiter {
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Comma => ',',
+            Self::Tab => '\t',
+            Self::Pipe => '|',
+        }
+    }
+
+    /// Suffix written inside `[N]` headers; the comma default is implicit.
+    fn header_marker(self) -> &'static str {
+        match self {
+            Self::Comma => "",
+            Self::Tab => "\t",
+            Self::Pipe => "|",
+        }
+    }
+}
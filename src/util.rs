@@ -0,0 +1,134 @@
+//! Small string utilities.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// ANSI escape sequences (colors, cursor movement, hyperlinks) that test runners emit.
+static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07").unwrap());
+
+/// Remove ANSI escape sequences from `s`.
+pub(crate) fn strip_ansi(s: &str) -> Cow<'_, str> {
+    ANSI_ESCAPE.replace_all(s, "")
+}
+
+/// Truncate `s` to at most `max_len` bytes, ending with `...` and respecting UTF-8 boundaries.
+pub(crate) fn truncate(s: &str, max_len: usize) -> String {
+    if s.len() <= max_len {
+        return s.to_string();
+    }
+
+    let truncate_at = max_len.saturating_sub(3);
+
+    // Find nearest UTF-8 character boundary to avoid panic on multi-byte chars
+    let mut boundary = truncate_at.min(s.len());
+    while boundary > 0 && !s.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+
+    format!("{}...", &s[..boundary])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Utility Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn strip_ansi_removes_colors_and_links() {
+        assert_eq!(strip_ansi("\x1b[31mError\x1b[39m: \x1b]8;;http://x\x07link\x1b]8;;\x07"), "Error: link");
+    }
+
+    #[test]
+    fn truncate_short_string_unchanged() {
+        let result = truncate("short", 10);
+        assert_eq!(result, "short");
+    }
+
+    #[test]
+    fn truncate_long_string_with_ellipsis() {
+        let result = truncate("this is a very long string", 10);
+        assert_eq!(result, "this is...");
+        assert_eq!(result.len(), 10);
+    }
+
+    #[test]
+    fn truncate_exact_length_unchanged() {
+        let result = truncate("exactly10!", 10);
+        assert_eq!(result, "exactly10!");
+    }
+
+    #[test]
+    fn truncate_handles_unicode_without_panic() {
+        // This would panic before the fix if truncation landed mid-character
+        let chinese = "错误：无法读取属性的值";
+        let result = truncate(chinese, 10);
+        assert!(result.ends_with("..."));
+        // Ensure we can iterate over chars (proves valid UTF-8)
+        assert!(result.chars().count() > 0);
+    }
+
+    #[test]
+    fn truncate_handles_emoji_without_panic() {
+        let emoji = "Error: 🔥🔥🔥🔥🔥 something failed";
+        let result = truncate(emoji, 15);
+        assert!(result.ends_with("..."));
+        assert!(result.chars().count() > 0);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // UTF-8 Truncation Edge Cases
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn truncate_multi_byte_chars_at_boundary() {
+        // Japanese: each char is 3 bytes
+        let japanese = "エラーメッセージ";
+        let result = truncate(japanese, 12);
+        // Should not panic and should produce valid UTF-8
+        assert!(result.ends_with("..."));
+        for c in result.chars() {
+            assert!(c.len_utf8() > 0);
+        }
+    }
+
+    #[test]
+    fn truncate_mixed_ascii_and_unicode() {
+        let mixed = "Error: 错误 in module";
+        let result = truncate(mixed, 15);
+        assert!(result.ends_with("..."));
+        assert!(result.chars().count() > 0);
+    }
+
+    #[test]
+    fn truncate_empty_string() {
+        let result = truncate("", 10);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn truncate_very_small_limit() {
+        let result = truncate("hello world", 3);
+        assert_eq!(result, "...");
+    }
+
+    #[test]
+    fn truncate_limit_zero() {
+        let result = truncate("hello", 0);
+        assert_eq!(result, "...");
+    }
+
+    #[test]
+    fn truncate_four_byte_emoji() {
+        // 🔥 is 4 bytes
+        let emoji_string = "🔥🔥🔥🔥🔥";
+        let result = truncate(emoji_string, 10);
+        assert!(result.ends_with("..."));
+        // Verify it's valid UTF-8 by iterating chars
+        let char_count = result.chars().count();
+        assert!(char_count > 0);
+    }
+}
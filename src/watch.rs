@@ -0,0 +1,139 @@
+//! Clipboard watching: decide when copied text should be replaced by its
+//! compressed form.
+//!
+//! [`ClipboardWatcher`] holds no clipboard handle; the caller polls the
+//! clipboard and passes each reading to [`check`](ClipboardWatcher::check).
+//! It only acts on text it hasn't seen before, never on error-toon's own output
+//! (its previous replacement, or anything that reads as plain, TOON, or JSON
+//! output), and only when compression actually shrinks the text.
+
+use crate::detect::detect_error_type;
+use crate::format::{decode_toon, format_multi_plain, format_multi_toon_with, CompressionStats};
+use crate::model::ToonifiedError;
+use crate::toon::Delimiter;
+use crate::toonify;
+
+/// Format used for replacements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchFormat {
+    Plain,
+    Toon(Delimiter),
+}
+
+/// Compressed text to put on the clipboard in place of a copied error.
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pub text: String,
+    pub errors: Vec<ToonifiedError>,
+    pub stats: CompressionStats,
+}
+
+/// Tracks clipboard contents across polls. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct ClipboardWatcher {
+    format: WatchFormat,
+    last_seen: Option<String>,
+}
+
+impl ClipboardWatcher {
+    pub fn new(format: WatchFormat) -> Self {
+        Self { format, last_seen: None }
+    }
+
+    /// Mark `text` as seen without acting on it, e.g. whatever was on the
+    /// clipboard when watching started or resumed.
+    pub fn skip(&mut self, text: &str) {
+        self.last_seen = Some(text.to_string());
+    }
+
+    /// Examine the current clipboard text. Returns the replacement if `text`
+    /// is newly copied, recognized as an error, and smaller once compressed.
+    pub fn check(&mut self, text: &str) -> Option<Replacement> {
+        if self.last_seen.as_deref() == Some(text) {
+            return None;
+        }
+        self.skip(text);
+
+        if text.trim().is_empty() || is_tool_output(text) || detect_error_type(text).is_none() {
+            return None;
+        }
+
+        let errors = toonify(text);
+        if errors.is_empty() {
+            return None;
+        }
+
+        let original = text.chars().count();
+        let output = match (self.format, errors.len()) {
+            (WatchFormat::Plain, 1) => errors[0].format_plain_against(original),
+            (WatchFormat::Plain, _) => format_multi_plain(&errors, original),
+            (WatchFormat::Toon(delimiter), 1) => errors[0].format_toon_against(delimiter, original),
+            (WatchFormat::Toon(delimiter), _) => format_multi_toon_with(&errors, original, delimiter),
+        };
+        let stats = CompressionStats::measure(original, &output);
+        if stats.compressed_chars >= stats.original_chars {
+            return None;
+        }
+
+        // The replacement is what the next poll will read back
+        self.skip(&output);
+        Some(Replacement { text: output, errors, stats })
+    }
+}
+
+/// Whether `text` looks like error-toon output, which is never compressed again.
+fn is_tool_output(text: &str) -> bool {
+    let plain = text.contains("\n---\ncompressed: ") || text.contains("\n===\ntotal: ");
+    let json = text.trim_start().starts_with('[') && text.contains("\"stats\"") && text.contains("\"comp\"");
+    plain || json || decode_toon(text).is_ok_and(|decoded| !decoded.errors.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR: &str = "TypeError: Cannot read properties of undefined (reading 'map')\n    at ProductList (src/components/ProductList.tsx:42:17)\n    at renderWithHooks (node_modules/react-dom/cjs/react-dom.development.js:14985:18)\n    at mountIndeterminateComponent (node_modules/react-dom/cjs/react-dom.development.js:17811:13)";
+
+    #[test]
+    fn replaces_newly_copied_error_once() {
+        let mut watcher = ClipboardWatcher::new(WatchFormat::Plain);
+        let replacement = watcher.check(ERROR).expect("error is compressed");
+
+        assert!(replacement.text.starts_with("type: TYPE_ERROR\n"));
+        assert_eq!(replacement.errors.len(), 1);
+        assert!(replacement.stats.compressed_chars < replacement.stats.original_chars);
+        assert!(watcher.check(&replacement.text).is_none());
+        assert!(watcher.check(ERROR).is_some(), "copying the error again is new text");
+    }
+
+    #[test]
+    fn uses_toon_when_configured() {
+        let mut watcher = ClipboardWatcher::new(WatchFormat::Toon(Delimiter::Comma));
+        let replacement = watcher.check(ERROR).unwrap();
+        assert!(replacement.text.contains("\nstats:\n  orig: "));
+    }
+
+    #[test]
+    fn ignores_skipped_and_unchanged_text() {
+        let mut watcher = ClipboardWatcher::new(WatchFormat::Plain);
+        watcher.skip(ERROR);
+        assert!(watcher.check(ERROR).is_none());
+    }
+
+    #[test]
+    fn ignores_non_errors_and_tool_output() {
+        let mut watcher = ClipboardWatcher::new(WatchFormat::Plain);
+        assert!(watcher.check("just some notes").is_none());
+
+        let errors = toonify(ERROR);
+        for output in [errors[0].format_plain(), errors[0].format_toon(), crate::format_json(&errors, 300)] {
+            assert!(watcher.check(&output).is_none(), "{}", output);
+        }
+    }
+
+    #[test]
+    fn leaves_errors_that_would_grow() {
+        let mut watcher = ClipboardWatcher::new(WatchFormat::Plain);
+        assert!(watcher.check("TypeError: x").is_none());
+    }
+}